@@ -1,6 +1,7 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use regex::Regex;
-use std::collections::HashMap;
+//! Alternative approaches to 2021 day 21 part 2, the quantum Dirac dice game:
+//! a table of universe counts per round versus memoized recursion
+use criterion::Criterion;
+use std::{collections::HashMap, hint::black_box};
 
 fn index(pos1: i32, pos2: i32, score1: i32, score2: i32) -> usize {
     ((((pos1 - 1) as usize) * 10 + ((pos2 - 1) as usize)) * 21 + (score1 as usize)) * 21
@@ -8,7 +9,7 @@ fn index(pos1: i32, pos2: i32, score1: i32, score2: i32) -> usize {
 }
 
 pub fn solve_21_2(start_pos1: i32, start_pos2: i32) -> (usize, usize) {
-    let probs = vec![1, 3, 6, 7, 6, 3, 1];
+    let probs = [1, 3, 6, 7, 6, 3, 1];
     let mut pos_score = vec![0; 10 * 21 * 10 * 21];
     pos_score[index(start_pos1, start_pos2, 0, 0)] = 1;
     let mut win1: usize = 0;
@@ -80,7 +81,7 @@ pub fn solve_21_3(
     for dice in dices {
         let mut new_pos1 = pos1 as usize + dice;
         new_pos1 = (new_pos1 - 1) % 10 + 1;
-        let new_score1 = score1 + new_pos1 as usize;
+        let new_score1 = score1 + new_pos1;
         if new_score1 >= 21 {
             oc.0 += 1;
         } else {
@@ -93,18 +94,13 @@ pub fn solve_21_3(
     oc
 }
 
-fn criterion_benchmark1(c: &mut Criterion) {
-    c.bench_function("day 21.2", |b| {
+pub fn bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dirac dice");
+    group.bench_function("table", |b| {
         b.iter(|| solve_21_2(black_box(5), black_box(6)))
     });
-}
-
-fn criterion_benchmark2(c: &mut Criterion) {
-    let mut cache = HashMap::new();
-    c.bench_function("day 21.3", |b| {
-        b.iter(|| solve_21_3(black_box(5), black_box(6), 0, 0, &mut cache))
+    group.bench_function("memoized", |b| {
+        b.iter(|| solve_21_3(black_box(5), black_box(6), 0, 0, &mut HashMap::new()))
     });
+    group.finish();
 }
-
-criterion_group!(benches, criterion_benchmark1, criterion_benchmark2);
-criterion_main!(benches);
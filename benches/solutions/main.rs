@@ -0,0 +1,32 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::{fs, hint::black_box};
+
+use advent::{input_path, solutions};
+
+mod dirac_dice;
+
+/// Benchmarks every registered solution on its input in data/, skipping
+/// days without one. Select a single day with e.g. `cargo bench -- 2023/05`.
+fn bench_solutions(c: &mut Criterion) {
+    for solution in solutions() {
+        let Ok(input) = fs::read(input_path(solution.year(), solution.day())) else {
+            continue;
+        };
+        let mut group = c.benchmark_group(format!("{}/{:02}", solution.year(), solution.day()));
+        group.sample_size(10);
+        if solution.parse(&mut &input[..]).is_some() {
+            group.bench_function("parse", |b| {
+                b.iter(|| solution.parse(&mut black_box(&input[..])))
+            });
+        }
+        for part in 1..=solution.parts() {
+            group.bench_function(format!("part {part}"), |b| {
+                b.iter(|| solution.solve(part, &mut black_box(&input[..])))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_solutions, dirac_dice::bench);
+criterion_main!(benches);
@@ -1,5 +1,6 @@
 use cfgrammar::yacc::YaccKind;
 use lrlex::CTLexerBuilder;
+use std::{collections::HashMap, env, fmt::Write, fs, path::Path};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     CTLexerBuilder::new()
@@ -10,5 +11,67 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         })
         .lexer_in_src_dir("year2022/list.l")?
         .build()?;
+    generate_answer_tests()?;
     Ok(())
 }
+
+/// Writes one test per entry of the answer and example manifests in data/,
+/// which `answers::test` and `examples::test` include
+fn generate_answer_tests() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=data");
+    let mut years: Vec<u32> = fs::read_dir("data")?
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+        .collect();
+    years.sort();
+    let out = env::var("OUT_DIR")?;
+    let mut tests = String::new();
+    for year in &years {
+        for (day, part) in manifest_entries(&format!("data/{year}/answers.txt"))? {
+            writeln!(
+                tests,
+                "#[test]\nfn test_{year}_{day}_{part}() {{\n    check({year}, {day}, {part});\n}}\n"
+            )?;
+        }
+    }
+    fs::write(Path::new(&out).join("answer_tests.rs"), tests)?;
+
+    let mut tests = String::new();
+    for year in &years {
+        let mut count = HashMap::new();
+        let entries = manifest_entries(&format!("data/{year}/examples.txt"))?;
+        for (index, (day, part)) in entries.into_iter().enumerate() {
+            let n = count.entry((day, part)).or_insert(0);
+            *n += 1;
+            writeln!(
+                tests,
+                "#[test]\nfn test_{year}_{day}_{part}_example_{n}() {{\n    check({year}, {index});\n}}\n"
+            )?;
+        }
+    }
+    fs::write(Path::new(&out).join("example_tests.rs"), tests)?;
+    Ok(())
+}
+
+/// Day and part of each entry of a manifest, if it exists
+fn manifest_entries(manifest: &str) -> Result<Vec<(u32, u32)>, Box<dyn std::error::Error>> {
+    if !Path::new(manifest).exists() {
+        return Ok(Vec::new());
+    }
+    println!("cargo:rerun-if-changed={manifest}");
+    let mut entries = Vec::new();
+    for line in fs::read_to_string(manifest)?.lines() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split(' ');
+        let (Some(Ok(day)), Some(Ok(part))) = (
+            fields.next().map(str::parse::<u32>),
+            fields.next().map(str::parse::<u32>),
+        ) else {
+            return Err(format!("invalid entry '{line}' in {manifest}").into());
+        };
+        entries.push((day, part));
+    }
+    Ok(entries)
+}
@@ -0,0 +1,111 @@
+use anyhow::{bail, Context, Result};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use crate::{input_path, open_input, AdventError, Solution};
+
+/// Known-good answers of one year, keyed by day and part
+pub type Answers = BTreeMap<(u32, u32), String>;
+
+/// Location of the answer manifest of the given year
+///
+/// Each line holds day, part and answer separated by single spaces, lines
+/// starting with `#` are comments. A `\n` in the answer stands for a line
+/// break.
+pub fn answers_path(year: u32) -> PathBuf {
+    format!("data/{year}/answers.txt").into()
+}
+
+/// Reads the answer manifest of the given year, which is empty if there is none
+pub fn read_answers(year: u32) -> Result<Answers> {
+    let path = answers_path(year);
+    if !path.exists() {
+        return Ok(Answers::new());
+    }
+    let content = fs::read_to_string(&path).context(AdventError::MissingInput(path.clone()))?;
+    parse_answers(&content).with_context(|| format!("in {}", path.display()))
+}
+
+fn parse_answers(content: &str) -> Result<Answers> {
+    let mut answers = Answers::new();
+    for (idx, line) in content.lines().enumerate() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.splitn(3, ' ');
+        let (Some(day), Some(part), Some(answer)) = (fields.next(), fields.next(), fields.next())
+        else {
+            bail!(AdventError::at_line(
+                idx,
+                "expected '<day> <part> <answer>'"
+            ));
+        };
+        let key = (
+            day.parse()
+                .map_err(|_| AdventError::at_line(idx, "invalid day"))?,
+            part.parse()
+                .map_err(|_| AdventError::at_line(idx, "invalid part"))?,
+        );
+        if answers.insert(key, answer.replace("\\n", "\n")).is_some() {
+            bail!(AdventError::at_line(idx, "duplicate answer"));
+        }
+    }
+    Ok(answers)
+}
+
+/// Outcome of checking one puzzle part against its recorded answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// Either the input or the recorded answer is missing
+    Missing,
+}
+
+/// Solves a puzzle part on its input in data/ and compares the result with `expected`
+pub fn verify(solution: &dyn Solution, part: u32, expected: Option<&str>) -> Result<Verdict> {
+    let path = input_path(solution.year(), solution.day());
+    let Some(expected) = expected.filter(|_| path.exists()) else {
+        return Ok(Verdict::Missing);
+    };
+    let mut input = open_input(&path).context(AdventError::MissingInput(path))?;
+    let actual = solution.solve(part, &mut input)?;
+    if actual == expected {
+        Ok(Verdict::Pass)
+    } else {
+        Ok(Verdict::Fail {
+            expected: expected.to_string(),
+            actual,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_answers, read_answers, verify, Verdict};
+    use crate::find_solution;
+
+    fn check(year: u32, day: u32, part: u32) {
+        let answers = read_answers(year).unwrap();
+        let solution = find_solution(year, day).unwrap();
+        let verdict = verify(
+            solution,
+            part,
+            answers.get(&(day, part)).map(|s| s.as_str()),
+        );
+        assert_eq!(verdict.unwrap(), Verdict::Pass);
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers("# day part answer\n1 1 42\n\n1 2 a b\\nc\n").unwrap();
+        assert_eq!(answers[&(1, 1)], "42");
+        assert_eq!(answers[&(1, 2)], "a b\nc");
+        assert!(parse_answers("1 1 1\n1 1 2\n").is_err());
+        assert!(parse_answers("1 x 1\n").is_err());
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+}
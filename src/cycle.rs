@@ -0,0 +1,206 @@
+//! Cycle detection for simulations that eventually repeat, so that the state
+//! after a huge number of steps can be derived from a few periods
+//!
+//! The sequence of states starts with `initial` and continues by applying
+//! `step`; two states count as equal if their `key`s are equal, which lets
+//! callers ignore parts of a state that don't influence the future, like a
+//! step counter.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// The states from `start` on repeat every `length` steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Smallest number of steps leading to the same state as `n` steps
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// Value after `n` steps of a quantity that grows by the same amount in
+    /// every cycle, e.g. the height of a tower, from its values after at most
+    /// `start + length` steps
+    pub fn extrapolate(&self, n: usize, mut value: impl FnMut(usize) -> i64) -> i64 {
+        if n < self.start {
+            return value(n);
+        }
+        let per_cycle = value(self.start + self.length) - value(self.start);
+        let cycles = ((n - self.start) / self.length) as i64;
+        value(self.reduce(n)) + cycles * per_cycle
+    }
+}
+
+/// Finds the cycle by remembering the keys of all states, returning it with
+/// all states up to its second start
+pub fn find_cycle<S, K, F, G>(initial: S, mut step: F, mut key: G) -> (Cycle, Vec<S>)
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![initial];
+    loop {
+        let idx = states.len() - 1;
+        if let Some(start) = seen.insert(key(&states[idx]), idx) {
+            let cycle = Cycle {
+                start,
+                length: idx - start,
+            };
+            return (cycle, states);
+        }
+        let next = step(&states[idx]);
+        states.push(next);
+    }
+}
+
+/// State after `n` steps, skipping the repetitions once a cycle is found
+pub fn state_after<S, K, F, G>(initial: S, n: usize, mut step: F, mut key: G) -> S
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![initial];
+    loop {
+        let idx = states.len() - 1;
+        if idx == n {
+            return states.swap_remove(idx);
+        }
+        if let Some(start) = seen.insert(key(&states[idx]), idx) {
+            let cycle = Cycle {
+                start,
+                length: idx - start,
+            };
+            return states.swap_remove(cycle.reduce(n));
+        }
+        let next = step(&states[idx]);
+        states.push(next);
+    }
+}
+
+/// Finds the cycle with Floyd's tortoise and hare, keeping only two states
+/// at a time at the price of more steps than [`brent`]
+pub fn floyd<S, K, F, G>(initial: S, mut step: F, mut key: G) -> Cycle
+where
+    S: Clone,
+    K: PartialEq,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    tortoise = initial;
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    let mut length = 1;
+    hare = step(&tortoise);
+    while key(&tortoise) != key(&hare) {
+        hare = step(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Finds the cycle with Brent's algorithm, keeping only two states at a time
+pub fn brent<S, K, F, G>(initial: S, mut step: F, mut key: G) -> Cycle
+where
+    S: Clone,
+    K: PartialEq,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while key(&tortoise) != key(&hare) {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{brent, find_cycle, floyd, state_after, Cycle};
+
+    /// 0, 1, 2, 3, 4, 5, 6, 7, 3, 4, ...
+    fn step(x: &u32) -> u32 {
+        if *x == 7 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let expected = Cycle {
+            start: 3,
+            length: 5,
+        };
+        let (cycle, states) = find_cycle(0, step, |x| *x);
+        assert_eq!(cycle, expected);
+        assert_eq!(states.len(), 9);
+        assert_eq!(floyd(0, step, |x| *x), expected);
+        assert_eq!(brent(0, step, |x| *x), expected);
+        assert_eq!(floyd(3, step, |x| *x).start, 0);
+        assert_eq!(brent(3, step, |x| *x).start, 0);
+    }
+
+    #[test]
+    fn test_state_after() {
+        assert_eq!(state_after(0, 2, step, |x| *x), 2);
+        assert_eq!(state_after(0, 1_000_000_000, step, |x| *x), 5);
+        let cycle = Cycle {
+            start: 3,
+            length: 5,
+        };
+        assert_eq!(cycle.reduce(1_000_000_000), 5);
+        // a counter growing by 10 per cycle, after the first 3 steps
+        let value = |n: usize| {
+            if n < 3 {
+                n as i64
+            } else {
+                3 + 10 * (n as i64 - 3) / 5
+            }
+        };
+        assert_eq!(cycle.extrapolate(13, value), 23);
+        assert_eq!(cycle.extrapolate(2, value), 2);
+    }
+}
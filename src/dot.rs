@@ -0,0 +1,132 @@
+//! Graphviz DOT export of the graphs some puzzles are about, for looking at
+//! their structure with `run --dot`, e.g. piped into `dot -Tsvg`
+//!
+//! Nodes are named by strings, their attributes like `label` or `shape` are
+//! passed on to Graphviz as they are.
+
+use std::fmt::{self, Display, Write};
+
+type Attributes = Vec<(String, String)>;
+
+/// Graph with attributes, written out in the order its parts were added
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    name: String,
+    directed: bool,
+    nodes: Vec<(String, Attributes)>,
+    edges: Vec<(String, String, Attributes)>,
+}
+
+fn attributes(attrs: &[(&str, &dyn Display)]) -> Attributes {
+    attrs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// `s` as a quoted DOT identifier
+fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn write_attributes(f: &mut fmt::Formatter, attrs: &Attributes) -> fmt::Result {
+    if attrs.is_empty() {
+        return Ok(());
+    }
+    let mut list = String::new();
+    for (key, value) in attrs {
+        if !list.is_empty() {
+            list.push_str(", ");
+        }
+        write!(list, "{key}={}", quote(value))?;
+    }
+    write!(f, " [{list}]")
+}
+
+impl Graph {
+    /// Graph whose edges have a direction
+    pub fn directed(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            directed: true,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn undirected(name: &str) -> Self {
+        Self {
+            directed: false,
+            ..Self::directed(name)
+        }
+    }
+
+    /// Adds a node, which is only necessary for nodes with attributes or
+    /// without edges
+    pub fn node(&mut self, id: &str, attrs: &[(&str, &dyn Display)]) {
+        self.nodes.push((id.to_string(), attributes(attrs)));
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, attrs: &[(&str, &dyn Display)]) {
+        self.edges
+            .push((from.to_string(), to.to_string(), attributes(attrs)));
+    }
+}
+
+impl Display for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        writeln!(f, "{kind} {} {{", quote(&self.name))?;
+        for (id, attrs) in &self.nodes {
+            write!(f, "  {}", quote(id))?;
+            write_attributes(f, attrs)?;
+            writeln!(f, ";")?;
+        }
+        for (from, to, attrs) in &self.edges {
+            write!(f, "  {} {arrow} {}", quote(from), quote(to))?;
+            write_attributes(f, attrs)?;
+            writeln!(f, ";")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Graph;
+
+    #[test]
+    fn test_directed() {
+        let mut graph = Graph::directed("2023-20");
+        graph.node("broadcaster", &[("shape", &"box")]);
+        graph.node("a", &[("label", &"%a"), ("weight", &3)]);
+        graph.edge("broadcaster", "a", &[]);
+        assert_eq!(
+            graph.to_string(),
+            "digraph \"2023-20\" {\n  \"broadcaster\" [shape=\"box\"];\n  \
+             \"a\" [label=\"%a\", weight=\"3\"];\n  \"broadcaster\" -> \"a\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_quoting() {
+        let mut graph = Graph::undirected("caves");
+        graph.edge("say \"hi\"", "back\\slash", &[("label", &5)]);
+        assert_eq!(
+            graph.to_string(),
+            "graph \"caves\" {\n  \"say \\\"hi\\\"\" -- \"back\\\\slash\" [label=\"5\"];\n}\n"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use std::{error::Error, fmt, path::PathBuf};
+
+/// Errors reported by the puzzle solvers and the command line driver
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdventError {
+    /// Malformed puzzle input; `line` and `column` are 1-based, a column
+    /// of 0 refers to the line as a whole
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    UnknownYear(u32),
+    UnknownDay {
+        year: u32,
+        day: u32,
+    },
+    UnknownPart {
+        year: u32,
+        day: u32,
+        part: u32,
+    },
+    MissingInput(PathBuf),
+    /// The input is well-formed, but the solver found no answer for it
+    NoSolution,
+}
+
+impl AdventError {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Parse error referring to a whole line of the input given by its 0-based index
+    pub fn at_line(idx: usize, message: impl Into<String>) -> Self {
+        Self::parse(idx + 1, 0, message)
+    }
+
+    /// Parse error without any known position, e.g. for missing lines
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::parse(0, 0, message)
+    }
+}
+
+impl fmt::Display for AdventError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse {
+                line: 0, message, ..
+            } => write!(f, "invalid input: {message}"),
+            Self::Parse {
+                line,
+                column: 0,
+                message,
+            } => write!(f, "invalid input in line {line}: {message}"),
+            Self::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "invalid input in line {line}, column {column}: {message}"
+            ),
+            Self::UnknownYear(year) => write!(f, "no solutions for year {year}"),
+            Self::UnknownDay { year, day } => write!(f, "no solution for {year}, day {day}"),
+            Self::UnknownPart { year, day, part } => {
+                write!(f, "{year}, day {day} has no part {part}")
+            }
+            Self::MissingInput(path) => write!(f, "couldn't read input file {}", path.display()),
+            Self::NoSolution => write!(f, "no solution found"),
+        }
+    }
+}
+
+impl Error for AdventError {}
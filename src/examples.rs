@@ -0,0 +1,254 @@
+use anyhow::{bail, Context, Result};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use crate::AdventError;
+
+/// Expected answer of a puzzle part for an example input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: u32,
+    pub part: u32,
+    /// Name of the input file in data/<year>/
+    pub file: String,
+    pub answer: String,
+}
+
+impl Example {
+    pub fn input_path(&self, year: u32) -> PathBuf {
+        format!("data/{year}/{}", self.file).into()
+    }
+}
+
+/// Location of the example manifest of the given year
+///
+/// Each line holds day, part, the name of the input file and the answer
+/// separated by single spaces, lines starting with `#` are comments.
+pub fn examples_path(year: u32) -> PathBuf {
+    format!("data/{year}/examples.txt").into()
+}
+
+/// Reads the example manifest of the given year, which is empty if there is none
+pub fn read_examples(year: u32) -> Result<Vec<Example>> {
+    let path = examples_path(year);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).context(AdventError::MissingInput(path.clone()))?;
+    parse_examples(&content).with_context(|| format!("in {}", path.display()))
+}
+
+fn parse_examples(content: &str) -> Result<Vec<Example>> {
+    let mut examples = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.splitn(4, ' ');
+        let (Some(day), Some(part), Some(file), Some(answer)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            bail!(AdventError::at_line(
+                idx,
+                "expected '<day> <part> <file> <answer>'"
+            ));
+        };
+        examples.push(Example {
+            day: day
+                .parse()
+                .map_err(|_| AdventError::at_line(idx, "invalid day"))?,
+            part: part
+                .parse()
+                .map_err(|_| AdventError::at_line(idx, "invalid part"))?,
+            file: file.to_string(),
+            answer: answer.to_string(),
+        });
+    }
+    Ok(examples)
+}
+
+/// Example of one puzzle part found on its page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extracted {
+    pub part: u32,
+    pub input: String,
+    pub answer: String,
+}
+
+/// Finds the examples on a saved puzzle page: each part's description
+/// (an `<article>`) shows its first `<pre><code>` block as the example,
+/// falling back to the one of part 1, and the last emphasized code as the
+/// expected answer
+pub fn extract_examples(page: &str) -> Vec<Extracted> {
+    let mut examples = Vec::new();
+    let mut last_input = None;
+    for (idx, (_, article)) in sections(page, "<article", "</article>").enumerate() {
+        let input = sections(article, "<pre><code>", "</code></pre>")
+            .next()
+            .map(|(_, code)| unescape(&strip_tags(code)));
+        let input = input.or(last_input.take());
+        let answer = sections(article, "<code><em>", "</em></code>")
+            .chain(sections(article, "<em><code>", "</code></em>"))
+            .max_by_key(|(offset, _)| *offset)
+            .map(|(_, answer)| unescape(&strip_tags(answer)));
+        if let (Some(input), Some(answer)) = (&input, answer) {
+            examples.push(Extracted {
+                part: idx as u32 + 1,
+                input: input.clone(),
+                answer,
+            });
+        }
+        last_input = input;
+    }
+    examples
+}
+
+/// Parts of `text` between each `start` and the next `end` with their
+/// offsets, where `start` may be the beginning of a tag with attributes
+fn sections<'a>(
+    text: &'a str,
+    start: &'a str,
+    end: &'a str,
+) -> impl Iterator<Item = (usize, &'a str)> {
+    let mut offset = 0;
+    std::iter::from_fn(move || {
+        let mut from = offset + text[offset..].find(start)? + start.len();
+        if start.ends_with(|c: char| c.is_alphanumeric()) {
+            from += text[from..].find('>')? + 1;
+        }
+        let to = from + text[from..].find(end)?;
+        offset = to + end.len();
+        Some((from, &text[from..to]))
+    })
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn unescape(html: &str) -> String {
+    html.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Saves the inputs of `extracted` as example files of the given day,
+/// reusing files with the same content, and records their answers in the
+/// example manifest, returning the new manifest entries
+pub fn save_examples(year: u32, day: u32, extracted: &[Extracted]) -> Result<Vec<Example>> {
+    let known = read_examples(year)?;
+    let mut added = Vec::new();
+    for example in extracted {
+        let file = example_file(year, day, &example.input)?;
+        let entry = Example {
+            day,
+            part: example.part,
+            file,
+            answer: example.answer.clone(),
+        };
+        let recorded = |e: &Example| (e.day, e.part, &e.file) == (day, entry.part, &entry.file);
+        if !known.iter().chain(&added).any(recorded) {
+            added.push(entry);
+        }
+    }
+    if !added.is_empty() {
+        let mut manifest = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(examples_path(year))?;
+        for e in &added {
+            writeln!(manifest, "{} {} {} {}", e.day, e.part, e.file, e.answer)?;
+        }
+    }
+    Ok(added)
+}
+
+/// Name of the example file of the given day holding `input`, which is
+/// written to the next free name if there is none yet
+fn example_file(year: u32, day: u32, input: &str) -> Result<String> {
+    let names = std::iter::once(format!("{day}_test.txt"))
+        .chain((1..).map(|n| format!("{day}_test{n}.txt")));
+    for name in names {
+        let path = PathBuf::from(format!("data/{year}/{name}"));
+        if !path.exists() {
+            fs::create_dir_all(format!("data/{year}"))?;
+            fs::write(&path, input)
+                .with_context(|| format!("couldn't write {}", path.display()))?;
+            return Ok(name);
+        }
+        if fs::read_to_string(&path)? == input {
+            return Ok(name);
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{extract_examples, parse_examples, read_examples, Example};
+    use crate::{find_solution, open_input};
+
+    fn check(year: u32, index: usize) {
+        let example = &read_examples(year).unwrap()[index];
+        let solution = find_solution(year, example.day).unwrap();
+        let mut input = open_input(example.input_path(year)).unwrap();
+        let actual = solution.solve(example.part, &mut input).unwrap();
+        assert_eq!(actual, example.answer);
+    }
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2>
+<p>For example:</p>
+<pre><code>1 &lt; 2
+<em>3</em>
+</code></pre>
+<p>Here, the total is <code><em>7</em></code>, not <code>8</code>.</p>
+</article>
+<p>Your puzzle answer was <code>42</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Now the total is <em><code>21</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract_examples() {
+        let examples = extract_examples(PAGE);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].input, "1 < 2\n3\n");
+        assert_eq!(examples[0].answer, "7");
+        assert_eq!((examples[1].part, examples[1].answer.as_str()), (2, "21"));
+        assert_eq!(examples[1].input, examples[0].input);
+    }
+
+    #[test]
+    fn test_parse_examples() {
+        let examples = parse_examples("# comment\n12 1 12_test1.txt 10\n").unwrap();
+        assert_eq!(
+            examples,
+            vec![Example {
+                day: 12,
+                part: 1,
+                file: "12_test1.txt".into(),
+                answer: "10".into(),
+            }]
+        );
+        assert!(parse_examples("12 1 12_test1.txt\n").is_err());
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+}
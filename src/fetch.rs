@@ -0,0 +1,265 @@
+//! Download of puzzle inputs from the Advent of Code website
+//!
+//! Inputs differ per account, so requests carry the session cookie of a
+//! logged in browser, read from `AOC_SESSION` or the file `.session`.
+
+use anyhow::{bail, Context, Result};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// File holding the session token if `AOC_SESSION` isn't set
+pub const SESSION_FILE: &str = ".session";
+
+/// Sends the requests, so that tests can do without a network
+pub trait Client {
+    /// Body of the page at `url`, requested with the given session cookie
+    fn get(&self, url: &str, session: &str) -> Result<String>;
+
+    /// Body of the response to posting the form `fields` to `url`
+    fn post(&self, url: &str, session: &str, fields: &[(&str, &str)]) -> Result<String>;
+}
+
+/// [`Client`] talking HTTP(S) to a real server
+pub struct HttpClient {
+    agent: ureq::Agent,
+}
+
+impl HttpClient {
+    pub fn new() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(concat!("advent/", env!("CARGO_PKG_VERSION")))
+                .build(),
+        }
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client for HttpClient {
+    fn get(&self, url: &str, session: &str) -> Result<String> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={session}"))
+            .call()
+            .with_context(|| format!("request to {url} failed"))?;
+        Ok(response.into_string()?)
+    }
+
+    fn post(&self, url: &str, session: &str, fields: &[(&str, &str)]) -> Result<String> {
+        let response = self
+            .agent
+            .post(url)
+            .set("Cookie", &format!("session={session}"))
+            .send_form(fields)
+            .with_context(|| format!("request to {url} failed"))?;
+        Ok(response.into_string()?)
+    }
+}
+
+/// Year, month and day of `time` in EST (UTC-5), the time zone in which the
+/// puzzles unlock at midnight
+fn release_date(time: SystemTime) -> (u32, u32, u32) {
+    let seconds = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let days = (seconds.saturating_sub(5 * 3600) / 86_400) as u32;
+    // civil date from the days since 1970-01-01, in eras of 400 years
+    // starting on March 1, 0000
+    let days = days + 719_468;
+    let (era, day_of_era) = (days / 146_097, days % 146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = era * 400 + year_of_era + u32::from(month <= 2);
+    (year, month, day)
+}
+
+/// Fails unless the puzzle for `year` and `day` is unlocked at `now`
+pub fn check_puzzle(year: u32, day: u32, now: SystemTime) -> Result<()> {
+    let today = release_date(now);
+    let latest = if today.1 == 12 { today.0 } else { today.0 - 1 };
+    if !(2015..=latest).contains(&year) {
+        bail!("there are puzzles for the years 2015 to {latest} only, not {year}");
+    }
+    if !(1..=25).contains(&day) {
+        bail!("there are puzzles for days 1 to 25 only, not {day}");
+    }
+    if (year, 12, day) > today {
+        bail!("day {day} of {year} unlocks at midnight EST on December {day}");
+    }
+    Ok(())
+}
+
+/// Session token from `AOC_SESSION`, or else from [`SESSION_FILE`]
+pub fn read_session() -> Result<String> {
+    let session = match env::var("AOC_SESSION") {
+        Ok(session) => session,
+        Err(_) => fs::read_to_string(SESSION_FILE).with_context(|| {
+            format!("no session token, set AOC_SESSION or write it to {SESSION_FILE}")
+        })?,
+    };
+    let session = session.trim();
+    if session.is_empty() {
+        bail!("the session token is empty");
+    }
+    Ok(session.to_string())
+}
+
+/// Whether an input was downloaded or already on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    Cached(PathBuf),
+}
+
+pub struct Fetcher<C> {
+    client: C,
+    base_url: String,
+    session: String,
+}
+
+impl<C: Client> Fetcher<C> {
+    pub fn new(client: C, base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+        }
+    }
+
+    /// Downloads the input of the given puzzle to `path`, unless that file
+    /// already exists, as inputs never change
+    pub fn fetch_input(&self, year: u32, day: u32, path: &Path) -> Result<Fetched> {
+        if path.exists() {
+            return Ok(Fetched::Cached(path.to_path_buf()));
+        }
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let input = self.client.get(&url, &self.session)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, input).with_context(|| format!("couldn't write {}", path.display()))?;
+        Ok(Fetched::Downloaded(path.to_path_buf()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_puzzle, release_date, Client, Fetched, Fetcher, HttpClient};
+    use anyhow::Result;
+    use std::{
+        cell::RefCell,
+        env, fs,
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    /// Answers every request with the same body, recording the URLs
+    struct Stub {
+        urls: RefCell<Vec<String>>,
+    }
+
+    impl Client for Stub {
+        fn get(&self, url: &str, _session: &str) -> Result<String> {
+            self.urls.borrow_mut().push(url.to_string());
+            Ok("1\n2\n".to_string())
+        }
+
+        fn post(&self, _url: &str, _session: &str, _fields: &[(&str, &str)]) -> Result<String> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_check_puzzle() {
+        let at = |seconds| UNIX_EPOCH + Duration::from_secs(seconds);
+        // 2024-12-01T05:00:00Z, midnight in EST
+        let now = at(1_733_029_200);
+        assert_eq!(release_date(now), (2024, 12, 1));
+        assert_eq!(release_date(at(1_733_029_199)), (2024, 11, 30));
+        assert!(check_puzzle(2015, 1, now).is_ok());
+        assert!(check_puzzle(2023, 25, now).is_ok());
+        assert!(check_puzzle(2024, 1, now).is_ok());
+        assert!(check_puzzle(2024, 2, now).is_err());
+        assert!(check_puzzle(2014, 1, now).is_err());
+        assert!(check_puzzle(2025, 1, now).is_err());
+        assert!(check_puzzle(2023, 0, now).is_err());
+        assert!(check_puzzle(2023, 26, now).is_err());
+
+        // 2024-07-01T00:00:00Z, months before the puzzles of 2024
+        let july = at(1_719_792_000);
+        assert_eq!(release_date(july), (2024, 6, 30));
+        assert!(check_puzzle(2023, 25, july).is_ok());
+        assert!(check_puzzle(2024, 1, july).is_err());
+
+        // 2025-01-05T12:00:00Z, after all puzzles of 2024
+        let january = at(1_736_078_400);
+        assert_eq!(release_date(january), (2025, 1, 5));
+        assert!(check_puzzle(2024, 25, january).is_ok());
+        assert!(check_puzzle(2025, 1, january).is_err());
+    }
+
+    #[test]
+    fn test_fetch_input_is_cached() {
+        let dir = env::temp_dir().join(format!("advent-fetch-{}", std::process::id()));
+        let path = dir.join("2023/1.txt");
+        let stub = Stub {
+            urls: RefCell::new(Vec::new()),
+        };
+        let fetcher = Fetcher::new(stub, "http://stub/", "secret");
+        assert_eq!(
+            fetcher.fetch_input(2023, 1, &path).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(
+            fetcher.fetch_input(2023, 1, &path).unwrap(),
+            Fetched::Cached(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n");
+        assert_eq!(
+            *fetcher.client.urls.borrow(),
+            vec!["http://stub/2023/day/1/input"]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_http_client_sends_session() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/2023/day/1/input", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request: Vec<String> = BufReader::new(&stream)
+                .lines()
+                .map_while(|l| l.ok().filter(|l| !l.is_empty()))
+                .collect();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\n1\n2\n")
+                .unwrap();
+            request
+        });
+        let body = HttpClient::new().get(&url, "secret").unwrap();
+        let request = server.join().unwrap();
+        assert_eq!(body, "1\n2\n");
+        assert_eq!(request[0], "GET /2023/day/1/input HTTP/1.1");
+        assert!(request.iter().any(|l| l == "Cookie: session=secret"));
+    }
+}
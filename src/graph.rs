@@ -0,0 +1,202 @@
+//! Partitions of undirected graphs whose nodes are numbered `0..n`, like
+//! the connected components or the cheapest way to cut a graph in two
+
+use std::collections::{BinaryHeap, HashMap};
+
+/// Disjoint sets of nodes, which can only be merged, with union by size and
+/// path halving
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSet {
+    /// Every node in a set of its own
+    pub fn new(n: usize) -> Self {
+        Self {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+        }
+    }
+
+    /// Representative of the set containing `node`
+    pub fn find(&mut self, mut node: usize) -> usize {
+        while self.parents[node] != node {
+            self.parents[node] = self.parents[self.parents[node]];
+            node = self.parents[node];
+        }
+        node
+    }
+
+    /// Merges the sets containing `a` and `b`, returning false if they were
+    /// the same already
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.sizes[a] < self.sizes[b] {
+            (a, b) = (b, a);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        true
+    }
+
+    /// Size of the set containing `node`
+    pub fn size(&mut self, node: usize) -> usize {
+        let root = self.find(node);
+        self.sizes[root]
+    }
+
+    /// Sizes of all sets, ordered by their representatives
+    pub fn set_sizes(&mut self) -> Vec<usize> {
+        let roots: Vec<usize> = (0..self.parents.len())
+            .filter(|&node| self.find(node) == node)
+            .collect();
+        roots.into_iter().map(|root| self.sizes[root]).collect()
+    }
+}
+
+/// Sizes of the connected components of the graph
+pub fn component_sizes(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut sets = DisjointSet::new(n);
+    for &(a, b) in edges {
+        sets.union(a, b);
+    }
+    sets.set_sizes()
+}
+
+/// Smallest set of edges whose removal disconnects a graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    pub edges: Vec<(usize, usize)>,
+    /// Sizes of the connected components without the cut edges
+    pub component_sizes: Vec<usize>,
+}
+
+/// Global minimum cut by the Stoer-Wagner algorithm, `None` for graphs with
+/// less than two nodes
+///
+/// Parallel edges count multiple times, so the result might contain the same
+/// edge more than once.
+pub fn min_cut(n: usize, edges: &[(usize, usize)]) -> Option<Cut> {
+    if n < 2 {
+        return None;
+    }
+    let mut weights: Vec<HashMap<usize, usize>> = vec![HashMap::new(); n];
+    for &(a, b) in edges {
+        if a != b {
+            *weights[a].entry(b).or_default() += 1;
+            *weights[b].entry(a).or_default() += 1;
+        }
+    }
+    // the original nodes merged into each remaining one
+    let mut members: Vec<Vec<usize>> = (0..n).map(|node| vec![node]).collect();
+    let mut active: Vec<usize> = (0..n).collect();
+    let mut best: Option<(usize, Vec<usize>)> = None;
+    while active.len() > 1 {
+        let (s, t, cut_weight) = min_cut_phase(&active, &weights, n);
+        if best.as_ref().is_none_or(|(w, _)| cut_weight < *w) {
+            best = Some((cut_weight, members[t].clone()));
+        }
+        // merge t into s
+        let t_weights = std::mem::take(&mut weights[t]);
+        for (node, w) in t_weights {
+            weights[node].remove(&t);
+            if node != s {
+                *weights[s].entry(node).or_default() += w;
+                *weights[node].entry(s).or_default() += w;
+            }
+        }
+        let t_members = std::mem::take(&mut members[t]);
+        members[s].extend(t_members);
+        active.retain(|&node| node != t);
+    }
+
+    let (_, side) = best?;
+    let mut on_side = vec![false; n];
+    for node in side {
+        on_side[node] = true;
+    }
+    let (cut_edges, kept): (Vec<_>, Vec<_>) = edges
+        .iter()
+        .filter(|(a, b)| a != b)
+        .partition(|&&(a, b)| on_side[a] != on_side[b]);
+    Some(Cut {
+        edges: cut_edges,
+        component_sizes: component_sizes(n, &kept),
+    })
+}
+
+/// Adds the active nodes one by one, always the one most tightly connected
+/// to those added before, returning the last two and the weight of the edges
+/// between the last and all others
+fn min_cut_phase(
+    active: &[usize],
+    weights: &[HashMap<usize, usize>],
+    n: usize,
+) -> (usize, usize, usize) {
+    let mut added = vec![false; n];
+    let mut connection = vec![0; n];
+    let mut queue: BinaryHeap<(usize, usize)> = active.iter().map(|&node| (0, node)).collect();
+    let (mut previous, mut last) = (active[0], active[0]);
+    while let Some((w, node)) = queue.pop() {
+        if added[node] || w != connection[node] {
+            continue;
+        }
+        added[node] = true;
+        (previous, last) = (last, node);
+        for (&other, &ow) in &weights[node] {
+            if !added[other] {
+                connection[other] += ow;
+                queue.push((connection[other], other));
+            }
+        }
+    }
+    (previous, last, connection[last])
+}
+
+#[cfg(test)]
+mod test {
+    use super::{component_sizes, min_cut, DisjointSet};
+
+    #[test]
+    fn test_disjoint_set() {
+        let mut sets = DisjointSet::new(6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 1));
+        assert!(!sets.union(0, 2));
+        assert!(sets.union(4, 5));
+        assert_eq!(sets.size(2), 3);
+        assert_eq!(sets.find(0), sets.find(2));
+        assert_ne!(sets.find(0), sets.find(4));
+        let mut sizes = sets.set_sizes();
+        sizes.sort();
+        assert_eq!(sizes, vec![1, 2, 3]);
+        assert_eq!(component_sizes(3, &[]), vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_min_cut() {
+        // two complete graphs of four nodes, joined by two edges
+        let mut edges = Vec::new();
+        for offset in [0, 4] {
+            for a in 0..4 {
+                for b in a + 1..4 {
+                    edges.push((a + offset, b + offset));
+                }
+            }
+        }
+        edges.extend([(0, 4), (3, 7)]);
+        let mut cut = min_cut(8, &edges).unwrap();
+        cut.edges.sort();
+        assert_eq!(cut.edges, vec![(0, 4), (3, 7)]);
+        assert_eq!(cut.component_sizes, vec![4, 4]);
+
+        let cut = min_cut(3, &[(0, 1)]).unwrap();
+        assert!(cut.edges.is_empty());
+        assert_eq!(min_cut(1, &[]), None);
+    }
+}
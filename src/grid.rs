@@ -0,0 +1,315 @@
+use std::{
+    fmt,
+    io::{self, BufRead},
+    ops::{Index, IndexMut},
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::{parse_lines, AdventError, Direction, DirectionType, Position};
+
+/// Rectangular map of cells, addressed by [`Position`] with `x` as column
+/// and `y` as row, starting at the top left corner
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Builds a grid from its rows, which must all have the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, AdventError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (idx, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(AdventError::at_line(idx, "unexpected row length"));
+            }
+            cells.extend(row);
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses one row per input line, converting each byte with `parse`
+    pub fn parse_with<F>(lines: io::Lines<impl BufRead>, mut parse: F) -> Result<Self>
+    where
+        F: FnMut(u8) -> Result<T>,
+    {
+        let rows = parse_lines(lines, |line| {
+            line.bytes()
+                .enumerate()
+                .map(|(col, b)| {
+                    parse(b).map_err(|err| AdventError::parse(0, col + 1, err.to_string()).into())
+                })
+                .collect()
+        })?;
+        Ok(Self::from_rows(rows)?)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: &Position) -> bool {
+        pos.check_on_grid(self.width, self.height)
+    }
+
+    pub fn get(&self, pos: &Position) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.offset(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: &Position) -> Option<&mut T> {
+        if self.contains(pos) {
+            let offset = self.offset(pos);
+            Some(&mut self.cells[offset])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // not `chunks`, which yields no chunk at all for a grid of empty rows
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Cells of column `x` from top to bottom, none if `x` is outside the grid
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// All positions on the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height)
+            .flat_map(move |y| (0..width).map(move |x| Position::new(x as i32, y as i32)))
+    }
+
+    /// All cells with their positions, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Positions on the grid next to `pos` in the given directions
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: &Position,
+        directions: &'a [DirectionType],
+    ) -> impl Iterator<Item = Position> + 'a {
+        let pos = pos.clone();
+        directions
+            .iter()
+            .map(move |d| &pos + &Direction::from_type(*d))
+            .filter(|p| self.contains(p))
+    }
+
+    /// Horizontal and vertical neighbours of `pos`
+    pub fn neighbours4(&self, pos: &Position) -> impl Iterator<Item = Position> + '_ {
+        self.neighbours(pos, &DirectionType::STRAIGHT)
+    }
+
+    /// Horizontal, vertical and diagonal neighbours of `pos`
+    pub fn neighbours8(&self, pos: &Position) -> impl Iterator<Item = Position> + '_ {
+        self.neighbours(pos, &DirectionType::ALL)
+    }
+
+    /// Position of the first cell equal to `value`, row by row
+    pub fn find(&self, value: &T) -> Option<Position>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, v)| *v == value).map(|(pos, _)| pos)
+    }
+
+    /// Positions of all cells equal to `value`, row by row
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Position> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, v)| *v == value)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Mirrors the grid along its main diagonal, so that rows become columns
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotates the grid by 90 degrees clockwise
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
+
+    /// Rotates the grid by 90 degrees counterclockwise
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (self.width - 1 - y, x))
+    }
+
+    /// New grid of the given size, whose cell (x, y) is the cell of `self` at `source(x, y)`
+    fn remap<F>(&self, width: usize, height: usize, source: F) -> Self
+    where
+        T: Clone,
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = source(x, y);
+                cells.push(self.cells[sy * self.width + sx].clone());
+            }
+        }
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    fn offset(&self, pos: &Position) -> usize {
+        pos.y as usize * self.width + pos.x as usize
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a map given as text, keeping its bytes
+    pub fn parse(lines: io::Lines<impl BufRead>) -> Result<Self> {
+        Self::parse_with(lines, Ok)
+    }
+
+    /// Parses a map of single digits
+    pub fn parse_digits(lines: io::Lines<impl BufRead>) -> Result<Self> {
+        Self::parse_with(lines, |b| {
+            if b.is_ascii_digit() {
+                Ok(b - b'0')
+            } else {
+                Err(anyhow!("expected digit, found '{}'", b as char))
+            }
+        })
+    }
+}
+
+impl<T> Index<&Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: &Position) -> &Self::Output {
+        assert!(self.contains(pos), "{pos:?} is not on the grid");
+        &self.cells[self.offset(pos)]
+    }
+}
+
+impl<T> IndexMut<&Position> for Grid<T> {
+    fn index_mut(&mut self, pos: &Position) -> &mut Self::Output {
+        assert!(self.contains(pos), "{pos:?} is not on the grid");
+        let offset = self.offset(pos);
+        &mut self.cells[offset]
+    }
+}
+
+/// Writes one line per row with one character per cell, i.e. a parsed map
+/// is printed as it was read
+impl<T> fmt::Display for Grid<T>
+where
+    T: Copy + Into<char>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            let line: String = row.iter().map(|c| (*c).into()).collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Grid;
+    use crate::Position;
+    use std::io::BufRead;
+
+    const MAP: &str = "#..\n.#S\n";
+
+    #[test]
+    fn test_grid_parse() {
+        let grid = Grid::parse(MAP.as_bytes().lines()).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[&Position::new(2, 1)], b'S');
+        assert_eq!(grid.find(&b'S'), Some(Position::new(2, 1)));
+        assert_eq!(grid.find_all(&b'#').count(), 2);
+        assert_eq!(grid.get(&Position::new(3, 0)), None);
+        assert_eq!(grid.to_string(), MAP);
+        assert!(Grid::parse("#.\n#\n".as_bytes().lines()).is_err());
+        assert!(Grid::parse_digits("12\n3x\n".as_bytes().lines()).is_err());
+    }
+
+    #[test]
+    fn test_grid_rows_and_columns() {
+        let grid = Grid::parse_digits("123\n456\n".as_bytes().lines()).unwrap();
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.transpose().row(0), &[1, 4]);
+        assert_eq!(grid.rotate_right().row(0), &[4, 1]);
+        assert_eq!(grid.rotate_left().row(0), &[3, 6]);
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.column(4).count(), 0);
+    }
+
+    #[test]
+    fn test_grid_without_columns() {
+        let grid = Grid::new(0, 2, 0u8);
+        assert_eq!((grid.width(), grid.height()), (0, 2));
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&[] as &[u8], &[]]);
+        assert_eq!(grid.column(0).count(), 0);
+    }
+
+    #[test]
+    fn test_grid_neighbours() {
+        let grid = Grid::new(3, 3, b'.');
+        let corner = Position::new(0, 0);
+        let center = Position::new(1, 1);
+        assert_eq!(grid.neighbours4(&corner).count(), 2);
+        assert_eq!(grid.neighbours8(&corner).count(), 3);
+        assert_eq!(grid.neighbours4(&center).count(), 4);
+        assert_eq!(grid.neighbours8(&center).count(), 8);
+    }
+}
@@ -0,0 +1,329 @@
+//! Sets of integers kept as ranges, for puzzles whose ranges are far too
+//! large to enumerate, and their counterpart in several dimensions
+//!
+//! All ranges are half-open like [`Range`], so inclusive puzzle bounds
+//! `a..=b` become `a..b + 1`.
+
+use num::PrimInt;
+use std::ops::Range;
+
+/// Set of integers as sorted, disjoint and non-adjacent ranges
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: PrimInt> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: PrimInt> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of integers in the set
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |len, r| len + (r.end - r.start))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(idx).is_some_and(|r| r.start <= value)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // ranges touching the new one are merged with it
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let mut merged = range;
+        if first < last {
+            merged.start = merged.start.min(self.ranges[first].start);
+            merged.end = merged.end.max(self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first == last {
+            return;
+        }
+        let mut rest = Vec::new();
+        if self.ranges[first].start < range.start {
+            rest.push(self.ranges[first].start..range.start);
+        }
+        if self.ranges[last - 1].end > range.end {
+            rest.push(range.end..self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, rest);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for r in &other.ranges {
+            union.insert(r.clone());
+        }
+        union
+    }
+
+    pub fn intersect(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for r in &other.ranges {
+            difference.remove(r.clone());
+        }
+        difference
+    }
+
+    /// Moves every value `x` to `x - from + to`, which must not overflow
+    pub fn translate(&self, from: T, to: T) -> Self {
+        let shift = |x: T| {
+            if to >= from {
+                x + (to - from)
+            } else {
+                x - (from - to)
+            }
+        };
+        Self {
+            ranges: self
+                .ranges
+                .iter()
+                .map(|r| shift(r.start)..shift(r.end))
+                .collect(),
+        }
+    }
+}
+
+/// The integer points within a range along each of `N` axes
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cuboid<T, const N: usize> {
+    pub ranges: [Range<T>; N],
+}
+
+impl<T: PrimInt, const N: usize> Cuboid<T, N> {
+    pub fn new(ranges: [Range<T>; N]) -> Self {
+        Self { ranges }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.iter().any(|r| r.is_empty())
+    }
+
+    /// Number of points in the cuboid
+    pub fn volume(&self) -> T {
+        if self.is_empty() {
+            return T::zero();
+        }
+        self.ranges
+            .iter()
+            .fold(T::one(), |volume, r| volume * (r.end - r.start))
+    }
+
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let overlap = Self {
+            ranges: std::array::from_fn(|axis| {
+                let (a, b) = (&self.ranges[axis], &other.ranges[axis]);
+                a.start.max(b.start)..a.end.min(b.end)
+            }),
+        };
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    /// Parts of the cuboid below and from `value` on along `axis`, if not empty
+    pub fn split_at(&self, axis: usize, value: T) -> (Option<Self>, Option<Self>) {
+        let range = &self.ranges[axis];
+        let value = value.max(range.start).min(range.end);
+        let part = |r: Range<T>| {
+            let mut part = self.clone();
+            part.ranges[axis] = r;
+            (!part.is_empty()).then_some(part)
+        };
+        (part(range.start..value), part(value..range.end))
+    }
+
+    /// Disjoint cuboids covering the points of this one outside `other`
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        if self.intersect(other).is_none() {
+            return vec![self.clone()];
+        }
+        let mut pieces = Vec::new();
+        let mut rest = self.clone();
+        for axis in 0..N {
+            let (below, from) = rest.split_at(axis, other.ranges[axis].start);
+            pieces.extend(below);
+            // the cuboids intersect, so there is a middle part on every axis
+            let (middle, above) = from.unwrap().split_at(axis, other.ranges[axis].end);
+            pieces.extend(above);
+            rest = middle.unwrap();
+        }
+        pieces
+    }
+}
+
+/// Union of points as disjoint cuboids, the counterpart of [`IntervalSet`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CuboidSet<T, const N: usize> {
+    cuboids: Vec<Cuboid<T, N>>,
+}
+
+impl<T: PrimInt, const N: usize> Default for CuboidSet<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PrimInt, const N: usize> CuboidSet<T, N> {
+    pub fn new() -> Self {
+        Self {
+            cuboids: Vec::new(),
+        }
+    }
+
+    pub fn cuboids(&self) -> &[Cuboid<T, N>] {
+        &self.cuboids
+    }
+
+    pub fn insert(&mut self, cuboid: Cuboid<T, N>) {
+        if cuboid.is_empty() {
+            return;
+        }
+        self.remove(&cuboid);
+        self.cuboids.push(cuboid);
+    }
+
+    pub fn remove(&mut self, cuboid: &Cuboid<T, N>) {
+        self.cuboids = self
+            .cuboids
+            .iter()
+            .flat_map(|c| c.subtract(cuboid))
+            .collect();
+    }
+
+    /// Number of points in the set
+    pub fn volume(&self) -> T {
+        self.cuboids
+            .iter()
+            .fold(T::zero(), |volume, c| volume + c.volume())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Cuboid, CuboidSet, IntervalSet};
+
+    #[test]
+    fn test_insert_merges() {
+        let mut set: IntervalSet<i32> = [0..2, 5..7, 10..12].into_iter().collect();
+        set.insert(2..5);
+        assert_eq!(set.ranges(), &[0..7, 10..12]);
+        set.insert(-3..20);
+        assert_eq!(set, IntervalSet::from(-3..20));
+        set.insert(25..25);
+        assert_eq!(set.len(), 23);
+    }
+
+    #[test]
+    fn test_remove_splits() {
+        let mut set = IntervalSet::from(0u64..10);
+        set.remove(3..5);
+        assert_eq!(set.ranges(), &[0..3, 5..10]);
+        set.remove(2..6);
+        assert_eq!(set.ranges(), &[0..2, 6..10]);
+        assert!(set.contains(1) && !set.contains(2) && set.contains(6));
+        set.remove(0..100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: IntervalSet<i64> = [0..10, 20..30].into_iter().collect();
+        let b = IntervalSet::from(5..25);
+        assert_eq!(a.intersect(&b).ranges(), &[5..10, 20..25]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..30]);
+        assert_eq!(a.union(&b), IntervalSet::from(0..30));
+        assert_eq!(a.translate(20, 0).ranges(), &[-20..-10, 0..10]);
+        assert_eq!(
+            IntervalSet::from(98u64..100).translate(98, 50),
+            IntervalSet::from(50..52)
+        );
+    }
+
+    #[test]
+    fn test_cuboid_subtract() {
+        let a = Cuboid::new([0..3, 0..3, 0..3]);
+        let b = Cuboid::new([1..2, 1..2, 1..5]);
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.iter().map(|c| c.volume()).sum::<i32>(), 27 - 2);
+        assert!(pieces.iter().all(|c| c.intersect(&b).is_none()));
+        assert_eq!(
+            a.subtract(&Cuboid::new([5..6, 0..3, 0..3])),
+            vec![a.clone()]
+        );
+
+        let mut set = CuboidSet::new();
+        set.insert(a);
+        set.insert(b);
+        assert_eq!(set.volume(), 27 + 2);
+        set.remove(&Cuboid::new([0..3, 0..3, 2..5]));
+        assert_eq!(set.volume(), 18);
+    }
+}
@@ -1,4 +1,162 @@
-use std::ops::{Add, Mul, Sub};
+#![allow(clippy::needless_range_loop)]
+
+use std::{
+    fs::File,
+    io::{self, BufRead},
+    ops::{Add, Mul, Sub},
+    path::{Path, PathBuf},
+};
+
+mod answers;
+pub mod cycle;
+pub mod dot;
+mod error;
+mod examples;
+pub mod fetch;
+pub mod graph;
+mod grid;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod ocr;
+pub mod parallel;
+pub mod polygon;
+pub mod report;
+pub mod scaffold;
+pub mod search;
+#[macro_use]
+mod solution;
+pub mod submit;
+pub mod vec3;
+pub mod visualize;
+
+mod year2015;
+mod year2016;
+mod year2020;
+mod year2021;
+mod year2022;
+mod year2023;
+mod year2024;
+
+pub use answers::{answers_path, read_answers, verify, Answers, Verdict};
+pub use error::AdventError;
+pub use examples::{
+    examples_path, extract_examples, read_examples, save_examples, Example, Extracted,
+};
+pub use grid::Grid;
+pub use solution::Solution;
+
+/// Iterates over all registered puzzle solutions, ordered by year and day
+pub fn solutions() -> impl Iterator<Item = &'static dyn Solution> {
+    [
+        year2015::SOLUTIONS,
+        year2016::SOLUTIONS,
+        year2020::SOLUTIONS,
+        year2021::SOLUTIONS,
+        year2022::SOLUTIONS,
+        year2023::SOLUTIONS,
+        year2024::SOLUTIONS,
+    ]
+    .into_iter()
+    .flatten()
+    .copied()
+}
+
+pub fn find_solution(year: u32, day: u32) -> Result<&'static dyn Solution, AdventError> {
+    if !solutions().any(|s| s.year() == year) {
+        return Err(AdventError::UnknownYear(year));
+    }
+    solutions()
+        .find(|s| s.day() == day && s.year() == year)
+        .ok_or(AdventError::UnknownDay { year, day })
+}
+
+/// Default location of the puzzle input for the given day
+pub fn input_path(year: u32, day: u32) -> PathBuf {
+    format!("data/{year}/{day}.txt").into()
+}
+
+/// Opens a puzzle input for reading, where `-` denotes stdin
+pub fn open_input<P>(filename: P) -> io::Result<Box<dyn BufRead>>
+where
+    P: AsRef<Path>,
+{
+    let filename = filename.as_ref();
+    if filename == Path::new("-") {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(io::BufReader::new(File::open(filename)?)))
+    }
+}
+
+/// Returns the next line of the input, failing at its end
+pub fn next_line(lines: &mut io::Lines<impl BufRead>) -> anyhow::Result<String> {
+    Ok(lines
+        .next()
+        .ok_or(AdventError::invalid("unexpected end of input"))??)
+}
+
+/// Parses the input line by line, attaching the line number to any error
+pub fn parse_lines<T, F>(lines: io::Lines<impl BufRead>, parse: F) -> anyhow::Result<Vec<T>>
+where
+    F: FnMut(&str) -> anyhow::Result<T>,
+{
+    parse_lines_after(lines, 0, parse)
+}
+
+/// Like [`parse_lines`], for the rest of the input once `skipped` lines
+/// have been read, so that errors still refer to the right line
+pub fn parse_lines_after<T, F>(
+    lines: io::Lines<impl BufRead>,
+    skipped: usize,
+    mut parse: F,
+) -> anyhow::Result<Vec<T>>
+where
+    F: FnMut(&str) -> anyhow::Result<T>,
+{
+    lines
+        .enumerate()
+        .map(|(idx, line)| (idx + skipped, line))
+        .map(|(idx, line)| {
+            parse(&line?).map_err(|err| match err.downcast::<AdventError>() {
+                Ok(AdventError::Parse {
+                    line: 0,
+                    column,
+                    message,
+                }) => AdventError::parse(idx + 1, column, message).into(),
+                Ok(err) => err.into(),
+                Err(err) => AdventError::at_line(idx, err.to_string()).into(),
+            })
+        })
+        .collect()
+}
+
+/// Parses a line of single decimal digits, e.g. a row of a height map
+pub fn parse_digits(line: &str) -> anyhow::Result<Vec<u8>> {
+    line.bytes()
+        .enumerate()
+        .map(|(col, b)| {
+            if b.is_ascii_digit() {
+                Ok(b - b'0')
+            } else {
+                Err(AdventError::parse(
+                    0,
+                    col + 1,
+                    format!("expected digit, found '{}'", b as char),
+                )
+                .into())
+            }
+        })
+        .collect()
+}
+
+pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+where
+    P: AsRef<Path>,
+{
+    let file = File::open(filename)?;
+    Ok(io::BufReader::new(file).lines())
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DirectionType {
@@ -12,6 +170,22 @@ pub enum DirectionType {
     UpRight,
 }
 
+impl DirectionType {
+    /// Horizontal and vertical directions, clockwise starting upwards
+    pub const STRAIGHT: [DirectionType; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+    /// All eight directions, clockwise starting upwards
+    pub const ALL: [DirectionType; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+}
+
 #[derive(Debug, Clone)]
 pub struct Direction {
     pub x: i32,
@@ -153,3 +327,23 @@ impl Sub<&Direction> for &Position {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::solutions;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_registry_unique() {
+        let mut seen = HashSet::new();
+        for s in solutions() {
+            assert!(seen.insert((s.year(), s.day())), "{}/{}", s.year(), s.day());
+        }
+    }
+
+    #[test]
+    fn test_registry_ordered() {
+        let keys: Vec<_> = solutions().map(|s| (s.year(), s.day())).collect();
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+    }
+}
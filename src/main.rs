@@ -1,61 +1,491 @@
-#![feature(iter_advance_by)]
-
-use clap::Parser;
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::{
-    fs::File,
-    io::{self, BufRead},
+    env, fs,
     path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use advent::{
+    examples_path, extract_examples,
+    fetch::{self, Fetched, Fetcher, HttpClient},
+    find_solution, input_path, ocr, open_input, parallel, read_answers,
+    report::{self, Record, Status},
+    save_examples, scaffold, solutions,
+    submit::{rejected_path, History, Outcome, Submitter},
+    verify,
+    visualize::{self, FrameFormat},
+    AdventError, Answers, Solution, Verdict,
 };
 
 #[derive(Parser)]
 struct Args {
-    #[arg(short, long)]
-    year: u32,
-    #[arg(short, long)]
-    day: u32,
-    #[arg(short, long)]
-    part: u32,
-    #[arg(short, long)]
-    file: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+    /// Output of `run` and `verify`, for reading by other programs
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-mod year2015;
-mod year2016;
-mod year2020;
-mod year2021;
-mod year2022;
-mod year2023;
-mod year2024;
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
 
-fn main() {
+impl Format {
+    /// Prints the records of a run or verification unless the format is text,
+    /// which is printed while running
+    fn print(self, records: &[Record]) {
+        match self {
+            Format::Text => {}
+            Format::Json => print!("{}", report::to_json(records)),
+            Format::Csv => print!("{}", report::to_csv(records)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Frames {
+    Ppm,
+    Png,
+    /// A single animated GIF per simulation
+    Gif,
+}
+
+impl From<Frames> for FrameFormat {
+    fn from(frames: Frames) -> Self {
+        match frames {
+            Frames::Ppm => FrameFormat::Ppm,
+            Frames::Png => FrameFormat::Png,
+            Frames::Gif => FrameFormat::Gif,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single puzzle part, or all of them with `--all`
+    Run {
+        #[arg(short, long, required_unless_present = "all")]
+        year: Option<u32>,
+        #[arg(short, long, required_unless_present = "all")]
+        day: Option<u32>,
+        #[arg(short, long, required_unless_present_any = ["all", "dot"])]
+        part: Option<u32>,
+        /// Input file, use `-` to read from stdin
+        #[arg(short, long, conflicts_with = "all")]
+        file: Option<PathBuf>,
+        /// Run every registered solution (of `--year`, if given) against its input in data/
+        #[arg(short, long, conflicts_with_all = ["day", "part"])]
+        all: bool,
+        /// Also print the pictures that answers drawn in block letters are read from
+        #[arg(short, long)]
+        verbose: bool,
+        /// Write the steps of simulations that draw them as image frames to this directory
+        #[arg(long, value_name = "DIR")]
+        visualize: Option<PathBuf>,
+        /// Image format of the frames, PNG and GIF need the visualize feature
+        #[arg(long, value_enum, default_value_t = Frames::Ppm, requires = "visualize")]
+        frames: Frames,
+        /// Size of a grid cell in pixels
+        #[arg(long, default_value_t = 4, requires = "visualize")]
+        scale: usize,
+        /// Print the graph the puzzle is about in Graphviz DOT format instead of solving it
+        #[arg(long, conflicts_with_all = ["all", "part", "visualize"])]
+        dot: bool,
+    },
+    /// Download the input of a puzzle to data/, unless it is there already
+    Fetch {
+        #[arg(short, long)]
+        year: u32,
+        #[arg(short, long)]
+        day: u32,
+        /// Server to download from instead of adventofcode.com, also read from AOC_BASE_URL
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+    /// Solve a puzzle part and send the answer, unless it is known to be wrong
+    Submit {
+        #[arg(short, long)]
+        year: u32,
+        #[arg(short, long)]
+        day: u32,
+        #[arg(short, long)]
+        part: u32,
+        /// Input file, use `-` to read from stdin
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+        /// Server to submit to instead of adventofcode.com, also read from AOC_BASE_URL
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+    /// Create the module of a new day and register it, along with its year if new
+    New {
+        #[arg(short, long)]
+        year: u32,
+        #[arg(short, long)]
+        day: u32,
+        /// Title of the puzzle
+        #[arg(short, long)]
+        title: Option<String>,
+    },
+    /// Record the examples of a saved puzzle page as test cases in data/<year>/examples.txt
+    Examples {
+        #[arg(short, long)]
+        year: u32,
+        #[arg(short, long)]
+        day: u32,
+        /// The puzzle page as saved from the browser
+        page: PathBuf,
+    },
+    /// Check the solutions against the answers recorded in data/<year>/answers.txt
+    Verify {
+        #[arg(short, long)]
+        year: Option<u32>,
+    },
+}
+
+fn main() -> Result<()> {
     let args = Args::parse();
-    let lines = if let Some(file) = args.file.as_deref() {
-        read_lines(file)
-    } else {
-        let file = format!("data/{}/{}.txt", args.year, args.day);
-        read_lines(file)
+    // the solutions rely on a stack as large as the one of the main thread
+    #[cfg(feature = "parallel")]
+    rayon::ThreadPoolBuilder::new()
+        .stack_size(8 << 20)
+        .build_global()?;
+    if let Command::Run { verbose: true, .. } = args.command {
+        ocr::set_verbose(true);
+    }
+    let frames_dir = match &args.command {
+        Command::Run {
+            visualize: Some(dir),
+            frames,
+            scale,
+            ..
+        } => {
+            visualize::start(dir, (*frames).into(), *scale)?;
+            Some(dir.clone())
+        }
+        _ => None,
     };
-    if let Ok(lines) = lines {
-        let solution = match args.year {
-            2015 => year2015::execute(args.day, args.part, lines),
-            2016 => year2016::execute(args.day, args.part, lines),
-            2020 => year2020::execute(args.day, args.part, lines),
-            2021 => year2021::execute(args.day, args.part, lines),
-            2022 => year2022::execute(args.day, args.part, lines),
-            2023 => year2023::execute(args.day, args.part, lines),
-            2024 => year2024::execute(args.day, args.part, lines),
-            _ => format!("Error: solution for year {} not implemented", args.year),
-        };
+    let result = match args.command {
+        Command::Run {
+            year: Some(year),
+            day: Some(day),
+            file,
+            dot: true,
+            ..
+        } => dot(year, day, file),
+        Command::Run {
+            year: Some(year),
+            day: Some(day),
+            part: Some(part),
+            file,
+            all: false,
+            ..
+        } => run(year, day, part, file, args.format),
+        Command::Run { year, .. } => run_all(year, args.format),
+        Command::Fetch {
+            year,
+            day,
+            base_url,
+        } => fetch(year, day, base_url),
+        Command::Submit {
+            year,
+            day,
+            part,
+            file,
+            base_url,
+        } => submit(year, day, part, file, base_url),
+        Command::New { year, day, title } => {
+            let title = title.unwrap_or_else(|| format!("Day {day}"));
+            for path in scaffold::new_day(Path::new("."), year, day, &title)? {
+                println!("Wrote {}", path.display());
+            }
+            Ok(())
+        }
+        Command::Examples { year, day, page } => examples(year, day, &page),
+        Command::Verify { year } => verify_all(year, args.format),
+    };
+    if let Some(dir) = frames_dir {
+        let frames = visualize::finish()?;
+        eprintln!("Recorded {frames} frames in {}", dir.display());
+    }
+    result
+}
+
+fn run(year: u32, day: u32, part: u32, file: Option<PathBuf>, format: Format) -> Result<()> {
+    if format == Format::Text {
+        let solution = solve(year, day, part, file)?;
         println!("The solution is: {solution}");
-    } else {
-        eprintln!("Couldn't read input file");
+        return Ok(());
+    }
+    let start = Instant::now();
+    let answer = solve(year, day, part, file);
+    let mut record = Record::new(year, day, part, Status::Ok);
+    record.duration = Some(start.elapsed());
+    match &answer {
+        Ok(answer) => record.answer = Some(answer.clone()),
+        Err(err) => {
+            record.status = Status::Error;
+            record.message = Some(format!("{err:#}"));
+        }
+    }
+    format.print(&[record]);
+    answer.map(|_| ())
+}
+
+fn solve(year: u32, day: u32, part: u32, file: Option<PathBuf>) -> Result<String> {
+    let solution = find_solution(year, day)?;
+    let file = file.unwrap_or_else(|| input_path(year, day));
+    let mut input = open_input(&file).context(AdventError::MissingInput(file))?;
+    solution.solve(part, &mut input)
+}
+
+fn dot(year: u32, day: u32, file: Option<PathBuf>) -> Result<()> {
+    let solution = find_solution(year, day)?;
+    let file = file.unwrap_or_else(|| input_path(year, day));
+    let mut input = open_input(&file).context(AdventError::MissingInput(file))?;
+    let Some(graph) = solution.dot(&mut input) else {
+        bail!("{year} day {day} has no graph to export");
+    };
+    print!("{}", graph?);
+    Ok(())
+}
+
+fn check_year(year: Option<u32>) -> Result<()> {
+    if let Some(year) = year {
+        if !solutions().any(|s| s.year() == year) {
+            bail!(AdventError::UnknownYear(year));
+        }
     }
+    Ok(())
 }
 
-pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+fn run_all(year: Option<u32>, format: Format) -> Result<()> {
+    check_year(year)?;
+    let text = format == Format::Text;
+    let mut skipped = 0;
+    let mut failed = 0;
+    let mut total = Duration::ZERO;
+    let mut records = Vec::new();
+    if text {
+        println!("year day part        time  answer");
+    }
+    let mut parts = Vec::new();
+    for solution in solutions().filter(|s| year.is_none_or(|y| s.year() == y)) {
+        let path = Some(input_path(solution.year(), solution.day())).filter(|p| p.exists());
+        if path.is_none() {
+            skipped += 1;
+        }
+        parts.extend((1..=solution.parts()).map(|part| (solution, part, path.clone())));
+    }
+    let results = parallel::map(&parts, |(solution, part, path)| {
+        path.as_ref().map(|path| time_part(*solution, *part, path))
+    });
+    for (&(solution, part, _), result) in parts.iter().zip(results) {
+        let Some((answer, elapsed)) = result else {
+            records.push(Record::new(
+                solution.year(),
+                solution.day(),
+                part,
+                Status::Missing,
+            ));
+            continue;
+        };
+        total += elapsed;
+        let mut record = Record::new(solution.year(), solution.day(), part, Status::Ok);
+        record.duration = Some(elapsed);
+        let answer = match answer {
+            Ok(answer) => {
+                record.answer = Some(answer.clone());
+                answer
+            }
+            Err(err) => {
+                failed += 1;
+                record.status = Status::Error;
+                record.message = Some(format!("{err:#}"));
+                format!("error: {err:#}")
+            }
+        };
+        if text {
+            println!(
+                "{:4} {:3} {:4} {:>11}  {answer}",
+                solution.year(),
+                solution.day(),
+                part,
+                format!("{elapsed:.2?}"),
+            );
+        }
+        records.push(record);
+    }
+    if text {
+        println!("total time {total:.2?}, skipped {skipped} days without input");
+    }
+    format.print(&records);
+    if failed > 0 {
+        bail!("{failed} parts failed");
+    }
+    Ok(())
+}
+
+fn time_part(solution: &dyn Solution, part: u32, path: &Path) -> (Result<String>, Duration) {
+    let start = Instant::now();
+    let answer = open_input(path)
+        .context(AdventError::MissingInput(path.to_path_buf()))
+        .and_then(|mut input| solution.solve(part, &mut input));
+    (answer, start.elapsed())
+}
+
+fn fetch(year: u32, day: u32, base_url: Option<String>) -> Result<()> {
+    fetch::check_puzzle(year, day, SystemTime::now())?;
+    let path = input_path(year, day);
+    // an existing input needs no session token
+    if path.exists() {
+        println!("{} is already there", path.display());
+        return Ok(());
+    }
+    let fetcher = Fetcher::new(
+        HttpClient::new(),
+        resolve_base_url(base_url),
+        fetch::read_session()?,
+    );
+    match fetcher.fetch_input(year, day, &path)? {
+        Fetched::Downloaded(path) => println!("Downloaded {}", path.display()),
+        Fetched::Cached(path) => println!("{} is already there", path.display()),
+    }
+    Ok(())
+}
+
+fn submit(
+    year: u32,
+    day: u32,
+    part: u32,
+    file: Option<PathBuf>,
+    base_url: Option<String>,
+) -> Result<()> {
+    let answer = solve(year, day, part, file)?;
+    println!("The solution is: {answer}");
+    let path = rejected_path(year);
+    let mut history = History::read(&path)?;
+    let submitter = Submitter::new(
+        HttpClient::new(),
+        resolve_base_url(base_url),
+        fetch::read_session()?,
+    );
+    let outcome = submitter.submit_new(&mut history, &path, year, day, part, &answer)?;
+    match outcome {
+        Outcome::Correct => println!("That's the right answer"),
+        Outcome::Wrong => println!("That's not the right answer"),
+        Outcome::TooHigh => println!("That's not the right answer, it is too high"),
+        Outcome::TooLow => println!("That's not the right answer, it is too low"),
+        Outcome::Wait(left) => println!("Answered too recently, wait {left}"),
+        Outcome::WrongLevel => println!("That part is solved already or still locked"),
+        Outcome::Unknown(text) => println!("Unexpected response: {text}"),
+    }
+    Ok(())
+}
+
+fn examples(year: u32, day: u32, page: &Path) -> Result<()> {
+    find_solution(year, day)?;
+    let page = fs::read_to_string(page).context(AdventError::MissingInput(page.into()))?;
+    let extracted = extract_examples(&page);
+    if extracted.is_empty() {
+        bail!("found no example with an emphasized answer");
+    }
+    let added = save_examples(year, day, &extracted)?;
+    for e in &added {
+        println!("day {} part {}: {} -> {}", e.day, e.part, e.file, e.answer);
+    }
+    println!(
+        "recorded {} examples in {}",
+        added.len(),
+        examples_path(year).display()
+    );
+    Ok(())
+}
+
+/// Server from the command line, from AOC_BASE_URL or else adventofcode.com
+fn resolve_base_url(base_url: Option<String>) -> String {
+    base_url
+        .or_else(|| env::var("AOC_BASE_URL").ok())
+        .unwrap_or_else(|| fetch::DEFAULT_BASE_URL.to_string())
+}
+
+fn verify_all(year: Option<u32>, format: Format) -> Result<()> {
+    check_year(year)?;
+    let text = format == Format::Text;
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    let mut records = Vec::new();
+    let mut answers = Answers::new();
+    let mut answers_year = None;
+    let mut parts = Vec::new();
+    for solution in solutions().filter(|s| year.is_none_or(|y| s.year() == y)) {
+        if answers_year != Some(solution.year()) {
+            answers = read_answers(solution.year())?;
+            answers_year = Some(solution.year());
+        }
+        for part in 1..=solution.parts() {
+            parts.push((
+                solution,
+                part,
+                answers.get(&(solution.day(), part)).cloned(),
+            ));
+        }
+    }
+    let results = parallel::map(&parts, |(solution, part, expected)| {
+        let start = Instant::now();
+        let verdict = verify(*solution, *part, expected.as_deref());
+        (verdict, start.elapsed())
+    });
+    for (&(solution, part, ref expected), (verdict, elapsed)) in parts.iter().zip(results) {
+        let mut record = Record::new(solution.year(), solution.day(), part, Status::Pass);
+        record.expected = expected.clone();
+        let verdict = match verdict {
+            Ok(Verdict::Pass) => {
+                passed += 1;
+                record.answer = record.expected.clone();
+                record.duration = Some(elapsed);
+                "pass".to_string()
+            }
+            Ok(Verdict::Fail { expected, actual }) => {
+                failed += 1;
+                let verdict = format!("FAIL: expected {expected:?}, got {actual:?}");
+                record.status = Status::Fail;
+                record.answer = Some(actual);
+                record.duration = Some(elapsed);
+                verdict
+            }
+            Ok(Verdict::Missing) => {
+                missing += 1;
+                record.status = Status::Missing;
+                "missing".to_string()
+            }
+            Err(err) => {
+                failed += 1;
+                record.status = Status::Error;
+                record.message = Some(format!("{err:#}"));
+                record.duration = Some(elapsed);
+                format!("FAIL: {err:#}")
+            }
+        };
+        if text {
+            println!(
+                "{:4} {:3} {:4}  {verdict}",
+                solution.year(),
+                solution.day(),
+                part
+            );
+        }
+        records.push(record);
+    }
+    if text {
+        println!("{passed} passed, {failed} failed, {missing} missing");
+    }
+    format.print(&records);
+    if failed > 0 {
+        bail!("{failed} parts failed");
+    }
+    Ok(())
 }
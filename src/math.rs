@@ -0,0 +1,117 @@
+//! Number theory for combining cycles of different lengths
+//!
+//! A congruence `(r, m)` stands for all `x` with `x ≡ r (mod m)`, where the
+//! remainder is kept in `0..m`. Intermediate products are computed in `i128`,
+//! so that moduli up to `i64::MAX` don't overflow.
+
+/// Greatest common divisor `g` of `a` and `b`, which is never negative, with
+/// coefficients `x` and `y` such that `a * x + b * y == g`
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Least common multiple, `None` if it doesn't fit into an `i64`
+pub fn checked_lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    let (g, _, _) = extended_gcd(a, b);
+    (a / g).checked_mul(b)?.checked_abs()
+}
+
+/// Least common multiple of all values, 1 for none
+pub fn lcm_all(values: impl IntoIterator<Item = i64>) -> Option<i64> {
+    values.into_iter().try_fold(1, checked_lcm)
+}
+
+/// The congruence satisfied by exactly the numbers satisfying both given
+/// ones, `None` if there are no such numbers
+///
+/// The moduli need not be coprime, the combined modulus is their least
+/// common multiple.
+pub fn crt_pair((r1, m1): (i64, i64), (r2, m2): (i64, i64)) -> Option<(i64, i64)> {
+    let (g, p, _) = extended_gcd(m1, m2);
+    let diff = r2 - r1;
+    if diff % g != 0 {
+        return None;
+    }
+    let lcm = checked_lcm(m1, m2)?;
+    // r1 + m1 * k solves both for k ≡ diff / g * p (mod m2 / g)
+    let k = (diff / g) as i128 * p as i128 % (m2 / g) as i128;
+    let x = (r1 as i128 + m1 as i128 * k).rem_euclid(lcm as i128);
+    Some((x as i64, lcm))
+}
+
+/// Chinese remainder theorem for any number of congruences, `(0, 1)` for none
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |acc, &c| crt_pair(acc, (c.0.rem_euclid(c.1), c.1)))
+}
+
+/// Smallest number of at least `min` satisfying the congruence `(r, m)`,
+/// e.g. the first step at which a cycle starting late is in a given state
+pub fn first_at_least((r, m): (i64, i64), min: i64) -> i64 {
+    min + (r - min).rem_euclid(m)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{checked_lcm, crt, crt_pair, extended_gcd, first_at_least, lcm_all, mod_inverse};
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-12, 18), (7, 0), (0, 5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(g, num::integer::gcd(a, b));
+        }
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(checked_lcm(4, 6), Some(12));
+        assert_eq!(lcm_all([2, 3, 4, 10]), Some(60));
+        assert_eq!(lcm_all([]), Some(1));
+        assert_eq!(checked_lcm(i64::MAX, 2), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli with common divisors
+        assert_eq!(crt_pair((3, 4), (5, 6)), Some((11, 12)));
+        assert_eq!(crt_pair((0, 4), (1, 6)), None);
+        assert_eq!(crt(&[(-1, 4)]), Some((3, 4)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        let large = 1_000_000_007;
+        assert_eq!(
+            crt(&[(1, large), (2, large + 2)]),
+            Some((500_000_007_500_000_029, large * (large + 2)))
+        );
+        assert_eq!(first_at_least((23, 105), 100), 128);
+        assert_eq!(first_at_least((23, 105), 23), 23);
+    }
+}
@@ -0,0 +1,109 @@
+//! Cache for recursive functions, so that counting solvers can recurse
+//! naturally instead of threading their own maps through every call
+//!
+//! A memoized function takes the [`Memo`] as its first argument and wraps its
+//! body in [`Memo::get`], which hands the memo back for the recursive calls.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Results of a function by its key, with statistics on how often they
+/// were reused
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Cached value for `key`, computed by `compute` if there is none yet
+    pub fn get<F>(&mut self, key: K, compute: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Forgets all values, e.g. before solving an unrelated instance, but
+    /// keeps the statistics
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Number of cached values
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Share of lookups answered from the cache, 0 before the first one
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Memo;
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get(n, |memo| fibonacci(memo, n - 1) + fibonacci(memo, n - 2))
+    }
+
+    #[test]
+    fn test_recursion() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.len(), 89);
+        assert_eq!(memo.misses(), 89);
+        assert_eq!(memo.hits(), 87);
+
+        assert_eq!(fibonacci(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.hits(), 88);
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(fibonacci(&mut memo, 10), 55);
+        assert_eq!(memo.misses(), 98);
+        assert!(memo.hit_rate() > 0.4 && memo.hit_rate() < 0.5);
+    }
+}
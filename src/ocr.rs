@@ -0,0 +1,194 @@
+//! Recognition of the block letters some puzzles draw as their answer
+//!
+//! Images are given as text with `#` for a lit pixel and any other
+//! character for a dark one. Two fonts are known: letters 6 pixels high and
+//! (mostly) 4 wide, and letters 10 pixels high and 6 wide. Letters are told
+//! apart by the dark columns between them.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::AdventError;
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Also prints every image passed to [`read_letters`] to stderr
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+const SMALL_FONT: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE_FONT: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Reads the letters drawn in `image`, ignoring blank lines above and below
+pub fn read_letters(image: &str) -> Result<String, AdventError> {
+    if VERBOSE.load(Ordering::Relaxed) {
+        eprintln!("{image}");
+    }
+    let rows: Vec<Vec<bool>> = image
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#').collect())
+        .skip_while(|row: &Vec<bool>| row.is_empty())
+        .collect();
+    let height = rows
+        .iter()
+        .rposition(|row| !row.is_empty())
+        .map_or(0, |y| y + 1);
+    let rows = &rows[..height];
+    let font = match height {
+        6 => &SMALL_FONT[..],
+        10 => &LARGE_FONT[..],
+        _ => {
+            return Err(AdventError::invalid(format!(
+                "image of {height} rows has no known font"
+            )))
+        }
+    };
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < width {
+        if (0..height).all(|y| !lit(x, y)) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && (0..height).any(|y| lit(x, y)) {
+            x += 1;
+        }
+        let glyph = (0..height)
+            .map(|y| {
+                (start..x)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let (letter, _) = font.iter().find(|(_, g)| *g == glyph).ok_or_else(|| {
+            AdventError::invalid(format!(
+                "unknown letter in columns {start} to {}:\n{glyph}",
+                x - 1
+            ))
+        })?;
+        letters.push(*letter);
+    }
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read_letters, LARGE_FONT, SMALL_FONT};
+
+    /// Draws `letters` side by side with a dark column between them
+    fn draw(font: &[(char, &str)], letters: &str) -> String {
+        let glyphs: Vec<Vec<&str>> = letters
+            .chars()
+            .map(|c| {
+                font.iter()
+                    .find(|(l, _)| *l == c)
+                    .unwrap()
+                    .1
+                    .lines()
+                    .collect()
+            })
+            .collect();
+        (0..glyphs[0].len())
+            .map(|y| glyphs.iter().map(|g| g[y]).collect::<Vec<_>>().join(" "))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_read_letters() {
+        let all: String = SMALL_FONT.iter().map(|(c, _)| c).collect();
+        assert_eq!(read_letters(&draw(&SMALL_FONT, &all)).unwrap(), all);
+        let all: String = LARGE_FONT.iter().map(|(c, _)| c).collect();
+        assert_eq!(read_letters(&draw(&LARGE_FONT, &all)).unwrap(), all);
+        let image = format!("\n{}\n\n", draw(&SMALL_FONT, "HI"));
+        assert_eq!(read_letters(&image).unwrap(), "HI");
+        assert!(read_letters("#\n#\n#\n#\n#\n#").is_err());
+        assert!(read_letters("##\n##").is_err());
+    }
+}
@@ -0,0 +1,76 @@
+//! Work on independent items, spread over a thread pool if the `parallel`
+//! feature is enabled and one after the other otherwise
+//!
+//! Both ways give the same results in the same order, so solutions can use
+//! these functions without caring which one is compiled in.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Results of `f` for every item, in the order of the items
+///
+/// Sequentially each result is computed when the iterator gets to it, in
+/// parallel all of them are computed before the first one is returned.
+#[cfg(not(feature = "parallel"))]
+pub fn map<'a, T, R, F>(items: &'a [T], f: F) -> impl Iterator<Item = R> + 'a
+where
+    T: Sync,
+    R: Send + 'a,
+    F: Fn(&T) -> R + Sync + Send + 'a,
+{
+    items.iter().map(f)
+}
+
+#[cfg(feature = "parallel")]
+pub fn map<'a, T, R, F>(items: &'a [T], f: F) -> impl Iterator<Item = R> + 'a
+where
+    T: Sync,
+    R: Send + 'a,
+    F: Fn(&T) -> R + Sync + Send + 'a,
+{
+    items.par_iter().map(f).collect::<Vec<_>>().into_iter()
+}
+
+/// Result of `f` for the first item for which it returns one
+///
+/// In parallel later items may be tried as well, but their results are only
+/// used if no earlier item has one.
+#[cfg(not(feature = "parallel"))]
+pub fn find_map_first<T, R, F>(items: &[T], f: F) -> Option<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Option<R> + Sync + Send,
+{
+    items.iter().find_map(f)
+}
+
+#[cfg(feature = "parallel")]
+pub fn find_map_first<T, R, F>(items: &[T], f: F) -> Option<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Option<R> + Sync + Send,
+{
+    items.par_iter().find_map_first(f)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_map_first, map};
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items: Vec<u64> = (0..1000).collect();
+        let squares: Vec<u64> = map(&items, |n| n * n).collect();
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_find_map_first() {
+        let items: Vec<u64> = (0..1000).collect();
+        let found = find_map_first(&items, |n| (n % 7 == 6).then_some(n * 10));
+        assert_eq!(found, Some(60));
+        assert_eq!(find_map_first(&items, |_| None::<u64>), None);
+    }
+}
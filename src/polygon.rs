@@ -0,0 +1,60 @@
+//! Areas of loops through grid points, for puzzles asking how many tiles a
+//! loop encloses
+//!
+//! A loop is given by its vertices in order, the edge from the last back to
+//! the first one is implied. Edges may have any direction, and vertices in
+//! the middle of a straight edge don't change the results.
+
+use crate::Position;
+use num::integer::gcd;
+
+fn edges(vertices: &[Position]) -> impl Iterator<Item = (&Position, &Position)> {
+    vertices.iter().zip(vertices.iter().cycle().skip(1))
+}
+
+/// Twice the area enclosed by the loop by the shoelace formula, positive if
+/// the loop runs clockwise on screen, with y growing downwards
+pub fn signed_double_area(vertices: &[Position]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64)
+        .sum()
+}
+
+/// Number of grid points on the loop
+pub fn boundary_points(vertices: &[Position]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| gcd(b.x as i64 - a.x as i64, b.y as i64 - a.y as i64))
+        .sum()
+}
+
+/// Number of grid points strictly inside the loop by Pick's theorem, which
+/// needs a loop that doesn't touch or cross itself
+pub fn interior_points(vertices: &[Position]) -> i64 {
+    (signed_double_area(vertices).abs() - boundary_points(vertices) + 2) / 2
+}
+
+#[cfg(test)]
+mod test {
+    use super::{boundary_points, interior_points, signed_double_area};
+    use crate::Position;
+
+    #[test]
+    fn test_square() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)].map(|(x, y)| Position::new(x, y));
+        assert_eq!(signed_double_area(&square), 32);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+        let mut reversed = square.clone();
+        reversed.reverse();
+        assert_eq!(signed_double_area(&reversed), -32);
+        assert_eq!(interior_points(&reversed), 9);
+    }
+
+    #[test]
+    fn test_diagonal_edges() {
+        let triangle = [(0, 0), (2, 2), (4, 4), (8, 0)].map(|(x, y)| Position::new(x, y));
+        assert_eq!(signed_double_area(&triangle), -32);
+        assert_eq!(boundary_points(&triangle), 16);
+        assert_eq!(interior_points(&triangle), 9);
+    }
+}
@@ -0,0 +1,175 @@
+//! Machine-readable results of runs and verifications, for scripts that
+//! shouldn't have to parse the text output
+
+use std::{fmt::Write, time::Duration};
+
+/// Outcome of running or verifying one puzzle part
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Solved, without a recorded answer to compare with
+    Ok,
+    /// The solver failed
+    Error,
+    Pass,
+    Fail,
+    /// Either the input or the recorded answer is missing
+    Missing,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Error => "error",
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Missing => "missing",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub duration: Option<Duration>,
+    /// Error message of a failed solver
+    pub message: Option<String>,
+}
+
+impl Record {
+    pub fn new(year: u32, day: u32, part: u32, status: Status) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            status,
+            answer: None,
+            expected: None,
+            duration: None,
+            message: None,
+        }
+    }
+
+    fn duration_ms(&self) -> Option<String> {
+        self.duration
+            .map(|d| format!("{:.3}", d.as_secs_f64() * 1000.0))
+    }
+}
+
+const COLUMNS: &str = "year,day,part,status,answer,expected,duration_ms,message";
+
+/// Formats the records as a JSON array of objects, one per line
+pub fn to_json(records: &[Record]) -> String {
+    let string = |s: &Option<String>| s.as_deref().map_or("null".to_string(), json_string);
+    let mut json = String::from("[\n");
+    for (idx, r) in records.iter().enumerate() {
+        let separator = if idx + 1 < records.len() { "," } else { "" };
+        writeln!(
+            json,
+            r#"  {{"year": {}, "day": {}, "part": {}, "status": "{}", "answer": {}, "expected": {}, "duration_ms": {}, "message": {}}}{separator}"#,
+            r.year,
+            r.day,
+            r.part,
+            r.status.as_str(),
+            string(&r.answer),
+            string(&r.expected),
+            r.duration_ms().unwrap_or_else(|| "null".to_string()),
+            string(&r.message),
+        )
+        .unwrap();
+    }
+    json.push_str("]\n");
+    json
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Formats the records as CSV with a header line, leaving absent values empty
+pub fn to_csv(records: &[Record]) -> String {
+    let field = |s: &Option<String>| s.as_deref().map_or(String::new(), csv_field);
+    let mut csv = format!("{COLUMNS}\n");
+    for r in records {
+        writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{}",
+            r.year,
+            r.day,
+            r.part,
+            r.status.as_str(),
+            field(&r.answer),
+            field(&r.expected),
+            r.duration_ms().unwrap_or_default(),
+            field(&r.message),
+        )
+        .unwrap();
+    }
+    csv
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{to_csv, to_json, Record, Status};
+    use std::time::Duration;
+
+    fn records() -> Vec<Record> {
+        let mut solved = Record::new(2023, 5, 1, Status::Pass);
+        solved.answer = Some("42".into());
+        solved.expected = Some("42".into());
+        solved.duration = Some(Duration::from_micros(1500));
+        let mut failed = Record::new(2023, 5, 2, Status::Error);
+        failed.message = Some("invalid input: \"x\", line 1".into());
+        vec![solved, failed]
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            to_json(&records()),
+            r#"[
+  {"year": 2023, "day": 5, "part": 1, "status": "pass", "answer": "42", "expected": "42", "duration_ms": 1.500, "message": null},
+  {"year": 2023, "day": 5, "part": 2, "status": "error", "answer": null, "expected": null, "duration_ms": null, "message": "invalid input: \"x\", line 1"}
+]
+"#
+        );
+        assert_eq!(to_json(&[]), "[\n]\n");
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            to_csv(&records()),
+            "year,day,part,status,answer,expected,duration_ms,message
+2023,5,1,pass,42,42,1.500,
+2023,5,2,error,,,,\"invalid input: \"\"x\"\", line 1\"
+"
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! Generation of the boilerplate for a new puzzle day
+//!
+//! A day is a module `src/year<year>/day<day>.rs` declared in the year's
+//! `mod.rs` and listed in its `SOLUTIONS`; a new year is in turn declared
+//! in `src/lib.rs` and added to [`crate::solutions`].
+
+use anyhow::{bail, Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn day_template(year: u32, day: u32, title: &str) -> String {
+    format!(
+        r#"use std::io::{{self, BufRead}};
+
+use anyhow::Result;
+
+solution!({year}, {day}, {title:?});
+
+pub fn riddle_1(_lines: io::Lines<impl BufRead>) -> Result<String> {{
+    Ok("not yet implemented".to_string())
+}}
+
+pub fn riddle_2(_lines: io::Lines<impl BufRead>) -> Result<String> {{
+    Ok("not yet implemented".to_string())
+}}
+
+#[cfg(test)]
+mod test {{
+    use super::{{riddle_1, riddle_2}};
+    use crate::open_input;
+    use std::io::BufRead;
+
+    const EXAMPLE: &str = "data/{year}/{day}_test.txt";
+
+    #[test]
+    #[ignore = "the example answers are still to be filled in"]
+    fn test_{year}_{day}_example() {{
+        assert_eq!(riddle_1(open_input(EXAMPLE).unwrap().lines()).unwrap(), "");
+        assert_eq!(riddle_2(open_input(EXAMPLE).unwrap().lines()).unwrap(), "");
+    }}
+}}
+"#
+    )
+}
+
+fn year_template(day: u32) -> String {
+    format!(
+        "use crate::Solution;\n\nmod day{day};\n\n{}",
+        solutions_list(&[day])
+    )
+}
+
+fn solutions_list(days: &[u32]) -> String {
+    let entries: String = days
+        .iter()
+        .map(|d| format!("    &day{d}::Puzzle,\n"))
+        .collect();
+    format!("pub static SOLUTIONS: &[&dyn Solution] = &[\n{entries}];\n")
+}
+
+/// Adds the solution of `day` to the `SOLUTIONS` of a year module, in
+/// whichever layout rustfmt gave them
+fn add_solution(content: &str, day: u32) -> Result<String> {
+    let start = content
+        .find("pub static SOLUTIONS")
+        .context("no SOLUTIONS in the year module")?;
+    let open = start
+        + content[start..]
+            .find("= &[")
+            .or_else(|| content[start..].find("=\n"))
+            .context("SOLUTIONS isn't a slice")?;
+    let open = open
+        + content[open..]
+            .find('[')
+            .context("SOLUTIONS isn't a slice")?
+        + 1;
+    let end = open
+        + content[open..]
+            .find("];")
+            .context("SOLUTIONS isn't terminated")?;
+    let mut days: Vec<u32> = content[open..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .map(|e| numbered(e, "&day", "::Puzzle").context("unexpected entry in SOLUTIONS"))
+        .collect::<Result<_>>()?;
+    if days.contains(&day) {
+        bail!("day {day} is in SOLUTIONS already");
+    }
+    days.push(day);
+    days.sort();
+    let rest = content[end + 2..].trim_start_matches('\n');
+    Ok(format!(
+        "{}{}{rest}",
+        &content[..start],
+        solutions_list(&days)
+    ))
+}
+
+/// Lets rustfmt lay out a generated file, if it is installed
+fn format(path: &Path) {
+    // a file that is valid but not formatted is no reason to fail
+    let _ = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .arg(path)
+        .status();
+}
+
+/// Inserts `line` among the lines for which `key` returns a key, keeping
+/// them ordered and indented like the others
+fn insert_sorted<K, F>(content: &str, line: &str, key: F) -> Result<String>
+where
+    K: Ord,
+    F: Fn(&str) -> Option<K>,
+{
+    let new_key = key(line.trim()).context("line to insert has no key")?;
+    let lines: Vec<&str> = content.lines().collect();
+    let items: Vec<(usize, K)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, l)| Some((idx, key(l.trim())?)))
+        .collect();
+    let Some(&(last, _)) = items.last() else {
+        bail!("nothing to insert '{}' next to", line.trim());
+    };
+    if items.iter().any(|(_, k)| *k == new_key) {
+        bail!("'{}' is there already", line.trim());
+    }
+    let pos = items
+        .iter()
+        .find(|(_, k)| *k > new_key)
+        .map_or(last + 1, |(idx, _)| *idx);
+    let reference = lines[items[0].0];
+    let indent = &reference[..reference.len() - reference.trim_start().len()];
+    let mut result: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    result.insert(pos, format!("{indent}{}", line.trim()));
+    Ok(result.join("\n") + "\n")
+}
+
+/// Number in a line `<prefix><number><suffix>`
+fn numbered(line: &str, prefix: &str, suffix: &str) -> Option<u32> {
+    line.strip_prefix(prefix)?
+        .strip_suffix(suffix)?
+        .parse()
+        .ok()
+}
+
+/// Creates the module of a new day below `root` from a template, registers
+/// it (and its year, if new) and creates an empty file for the example
+/// input, returning the paths written to
+///
+/// Nothing is written if the day exists already.
+pub fn new_day(root: &Path, year: u32, day: u32, title: &str) -> Result<Vec<PathBuf>> {
+    let year_dir = root.join(format!("src/year{year}"));
+    let module = year_dir.join(format!("day{day}.rs"));
+    let year_module = year_dir.join("mod.rs");
+    let example = root.join(format!("data/{year}/{day}_test.txt"));
+    for path in [&module, &example] {
+        if path.exists() {
+            bail!("{} exists already", path.display());
+        }
+    }
+
+    let mut written = Vec::new();
+    if year_module.exists() {
+        let content = fs::read_to_string(&year_module)?;
+        // rustfmt orders modules by name, the solutions are ordered by day
+        let content = insert_sorted(&content, &format!("mod day{day};"), |l| {
+            numbered(l, "mod day", ";").map(|day| format!("day{day}"))
+        })?;
+        let content = add_solution(&content, day)?;
+        fs::write(&year_module, content)?;
+    } else {
+        let lib = root.join("src/lib.rs");
+        let content = fs::read_to_string(&lib)?;
+        let content = insert_sorted(&content, &format!("mod year{year};"), |l| {
+            numbered(l, "mod year", ";")
+        })?;
+        let content = insert_sorted(&content, &format!("year{year}::SOLUTIONS,"), |l| {
+            numbered(l, "year", "::SOLUTIONS,")
+        })?;
+        fs::create_dir_all(&year_dir)?;
+        fs::write(&year_module, year_template(day))?;
+        fs::write(&lib, content)?;
+        written.push(lib);
+    }
+    fs::write(&module, day_template(year, day, title))?;
+    format(&year_module);
+    fs::create_dir_all(example.parent().unwrap())?;
+    fs::write(&example, "")?;
+    written.extend([year_module, module, example]);
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use super::new_day;
+    use std::{env, fs};
+
+    const LIB: &str = "mod year2015;
+mod year2024;
+
+pub fn solutions() -> impl Iterator<Item = &'static dyn Solution> {
+    [
+        year2015::SOLUTIONS,
+        year2024::SOLUTIONS,
+    ]
+    .into_iter()
+}
+";
+
+    #[test]
+    fn test_new_day() {
+        let root = env::temp_dir().join(format!("advent-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+
+        assert_eq!(new_day(&root, 2016, 2, "Two").unwrap().len(), 4);
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("mod year2015;\nmod year2016;\nmod year2024;\n"));
+        assert!(lib.contains("        year2015::SOLUTIONS,\n        year2016::SOLUTIONS,\n"));
+
+        assert_eq!(new_day(&root, 2016, 10, "Ten").unwrap().len(), 3);
+        new_day(&root, 2016, 1, "One").unwrap();
+        let year = fs::read_to_string(root.join("src/year2016/mod.rs")).unwrap();
+        assert!(year.contains("mod day1;\nmod day10;\nmod day2;\n"));
+        let entry = |day: u32| year.find(&format!("&day{day}::Puzzle")).unwrap();
+        assert!(entry(1) < entry(2) && entry(2) < entry(10));
+        let day = fs::read_to_string(root.join("src/year2016/day10.rs")).unwrap();
+        assert!(day.contains("solution!(2016, 10, \"Ten\");"));
+        assert!(root.join("data/2016/10_test.txt").exists());
+
+        assert!(new_day(&root, 2016, 10, "Ten").is_err());
+        assert_eq!(
+            year,
+            fs::read_to_string(root.join("src/year2016/mod.rs")).unwrap()
+        );
+
+        new_day(&root, 2016, 3, r#"Don't Panic "Here""#).unwrap();
+        let day = fs::read_to_string(root.join("src/year2016/day3.rs")).unwrap();
+        assert!(day.contains(r#"solution!(2016, 3, "Don't Panic \"Here\"");"#));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,255 @@
+//! Shortest path searches over an implicit graph, given by its start states,
+//! a function returning the neighbours of a state and a goal predicate
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// States reached by a search, with the state each was first reached from
+struct Visited<S> {
+    states: Vec<S>,
+    parents: Vec<Option<usize>>,
+    costs: Vec<usize>,
+    index: HashMap<S, usize>,
+}
+
+impl<S> Visited<S>
+where
+    S: Clone + Eq + Hash,
+{
+    fn new() -> Self {
+        Self {
+            states: Vec::new(),
+            parents: Vec::new(),
+            costs: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Records that `state` can be reached for `cost`, returning its index if
+    /// this is cheaper than any way found before
+    fn reach(&mut self, state: S, parent: Option<usize>, cost: usize) -> Option<usize> {
+        match self.index.entry(state) {
+            Entry::Occupied(e) => {
+                let idx = *e.get();
+                if cost >= self.costs[idx] {
+                    return None;
+                }
+                self.parents[idx] = parent;
+                self.costs[idx] = cost;
+                Some(idx)
+            }
+            Entry::Vacant(e) => {
+                let idx = self.states.len();
+                self.states.push(e.key().clone());
+                e.insert(idx);
+                self.parents.push(parent);
+                self.costs.push(cost);
+                Some(idx)
+            }
+        }
+    }
+
+    /// States from a start state to the one at `idx`
+    fn path(&self, mut idx: usize) -> Vec<S> {
+        let mut path = vec![self.states[idx].clone()];
+        while let Some(parent) = self.parents[idx] {
+            path.push(self.states[parent].clone());
+            idx = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+fn bfs_impl<S, I, N, G>(starts: I, mut neighbours: N, mut is_goal: G) -> Option<(usize, Visited<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> Vec<S>,
+    G: FnMut(&S) -> bool,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Some(idx) = visited.reach(start, None, 0) {
+            queue.push_back(idx);
+        }
+    }
+    while let Some(idx) = queue.pop_front() {
+        if is_goal(&visited.states[idx]) {
+            return Some((idx, visited));
+        }
+        let cost = visited.costs[idx] + 1;
+        for next in neighbours(&visited.states[idx]) {
+            if !visited.index.contains_key(&next) {
+                queue.extend(visited.reach(next, Some(idx), cost));
+            }
+        }
+    }
+    None
+}
+
+fn astar_impl<S, I, N, H, G>(
+    starts: I,
+    mut neighbours: N,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> Option<(usize, Visited<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+    N: FnMut(&S) -> Vec<(S, usize)>,
+    H: FnMut(&S) -> usize,
+    G: FnMut(&S) -> bool,
+{
+    let mut visited = Visited::new();
+    let mut queue = BinaryHeap::new();
+    for (start, cost) in starts {
+        if let Some(idx) = visited.reach(start, None, cost) {
+            queue.push(Reverse((cost + heuristic(&visited.states[idx]), cost, idx)));
+        }
+    }
+    while let Some(Reverse((_, cost, idx))) = queue.pop() {
+        if cost > visited.costs[idx] {
+            // superseded by a cheaper way to the same state
+            continue;
+        }
+        if is_goal(&visited.states[idx]) {
+            return Some((idx, visited));
+        }
+        for (next, step) in neighbours(&visited.states[idx]) {
+            let next_cost = cost + step;
+            if let Some(next_idx) = visited.reach(next, Some(idx), next_cost) {
+                let estimate = next_cost + heuristic(&visited.states[next_idx]);
+                queue.push(Reverse((estimate, next_cost, next_idx)));
+            }
+        }
+    }
+    None
+}
+
+/// Number of steps from the nearest start to a goal state, if any can be reached
+pub fn bfs<S, I, N, G>(starts: I, neighbours: N, is_goal: G) -> Option<usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> Vec<S>,
+    G: FnMut(&S) -> bool,
+{
+    bfs_impl(starts, neighbours, is_goal).map(|(idx, visited)| visited.costs[idx])
+}
+
+/// Like [`bfs`], returning the states of a shortest path from start to goal
+pub fn bfs_path<S, I, N, G>(starts: I, neighbours: N, is_goal: G) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> Vec<S>,
+    G: FnMut(&S) -> bool,
+{
+    bfs_impl(starts, neighbours, is_goal).map(|(idx, visited)| visited.path(idx))
+}
+
+/// Lowest cost to reach a goal state, where `neighbours` returns each next
+/// state with the cost of getting there and the start states come with
+/// their initial costs
+pub fn dijkstra<S, I, N, G>(starts: I, neighbours: N, is_goal: G) -> Option<usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+    N: FnMut(&S) -> Vec<(S, usize)>,
+    G: FnMut(&S) -> bool,
+{
+    astar(starts, neighbours, |_| 0, is_goal)
+}
+
+/// Like [`dijkstra`], also returning the states of a cheapest path
+pub fn dijkstra_path<S, I, N, G>(starts: I, neighbours: N, is_goal: G) -> Option<(usize, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+    N: FnMut(&S) -> Vec<(S, usize)>,
+    G: FnMut(&S) -> bool,
+{
+    astar_path(starts, neighbours, |_| 0, is_goal)
+}
+
+/// Like [`dijkstra`], exploring the states in order of their cost plus
+/// `heuristic`, which must never overestimate the remaining cost to a goal
+pub fn astar<S, I, N, H, G>(starts: I, neighbours: N, heuristic: H, is_goal: G) -> Option<usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+    N: FnMut(&S) -> Vec<(S, usize)>,
+    H: FnMut(&S) -> usize,
+    G: FnMut(&S) -> bool,
+{
+    astar_impl(starts, neighbours, heuristic, is_goal).map(|(idx, visited)| visited.costs[idx])
+}
+
+/// Like [`astar`], also returning the states of a cheapest path
+pub fn astar_path<S, I, N, H, G>(
+    starts: I,
+    neighbours: N,
+    heuristic: H,
+    is_goal: G,
+) -> Option<(usize, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+    N: FnMut(&S) -> Vec<(S, usize)>,
+    H: FnMut(&S) -> usize,
+    G: FnMut(&S) -> bool,
+{
+    astar_impl(starts, neighbours, heuristic, is_goal)
+        .map(|(idx, visited)| (visited.costs[idx], visited.path(idx)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{astar_path, bfs, bfs_path, dijkstra, dijkstra_path};
+
+    /// Steps along a line of weighted cells, from one end to the other
+    fn line(costs: &[usize]) -> impl FnMut(&usize) -> Vec<(usize, usize)> + '_ {
+        move |&i| {
+            [i.wrapping_sub(1), i + 1]
+                .into_iter()
+                .filter(|&j| j < costs.len())
+                .map(|j| (j, costs[j]))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let neighbours = |&i: &i32| vec![i + 1, i * 2];
+        assert_eq!(bfs([1], neighbours, |&i| i == 10), Some(4));
+        assert_eq!(
+            bfs_path([1], neighbours, |&i| i == 10),
+            Some(vec![1, 2, 4, 5, 10])
+        );
+        assert_eq!(bfs([3, 5], neighbours, |&i| i == 10), Some(1));
+        assert_eq!(bfs([1], |_| vec![], |&i| i == 10), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let costs = [0, 1, 9, 1, 1];
+        assert_eq!(dijkstra([(0, 0)], line(&costs), |&i| i == 4), Some(12));
+        assert_eq!(
+            dijkstra([(0, 0), (4, 3)], line(&costs), |&i| i == 3),
+            Some(4)
+        );
+        assert_eq!(
+            dijkstra_path([(4, 0)], line(&costs), |&i| i == 0),
+            Some((11, vec![4, 3, 2, 1, 0]))
+        );
+        assert_eq!(
+            astar_path([(0, 0)], line(&costs), |&i| 4 - i, |&i| i == 4),
+            Some((12, vec![0, 1, 2, 3, 4]))
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::Result;
+use std::io::BufRead;
+
+use crate::AdventError;
+
+/// A single Advent of Code puzzle, identified by year and day
+pub trait Solution: Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+    /// Number of parts the puzzle has, 1 for day 25 and 2 otherwise
+    fn parts(&self) -> u32 {
+        2
+    }
+    /// Only parses the input, for timing it apart from solving. `None` if the
+    /// day has no separate parsing step.
+    fn parse(&self, _input: &mut dyn BufRead) -> Option<Result<()>> {
+        None
+    }
+    /// The graph the puzzle is about in Graphviz DOT format. `None` if the
+    /// day has no graph to show.
+    fn dot(&self, _input: &mut dyn BufRead) -> Option<Result<String>> {
+        None
+    }
+    fn part_1(&self, input: &mut dyn BufRead) -> Result<String>;
+    /// Fails with [`AdventError::UnknownPart`] for puzzles without a second part (i.e. day 25)
+    fn part_2(&self, input: &mut dyn BufRead) -> Result<String>;
+
+    fn solve(&self, part: u32, input: &mut dyn BufRead) -> Result<String> {
+        match part {
+            1 => self.part_1(input),
+            2 => self.part_2(input),
+            _ => Err(AdventError::UnknownPart {
+                year: self.year(),
+                day: self.day(),
+                part,
+            }
+            .into()),
+        }
+    }
+}
+
+/// Implements [`Solution`] for the surrounding day module by forwarding
+/// to its `riddle_1` and `riddle_2` functions. `parse = f` names the
+/// function reading the input, which is called with the input lines, and
+/// `dot = f` the one turning the input into a [`crate::dot::Graph`].
+macro_rules! solution {
+    ($year:literal, $day:literal, $title:literal $(, parse = $parse:expr)? $(, dot = $dot:expr)?) => {
+        solution!(@impl $year, $day, $title, 2, [$($parse)?], [$($dot)?], |lines| riddle_2(lines));
+    };
+    ($year:literal, $day:literal, $title:literal $(, parse = $parse:expr)? $(, dot = $dot:expr)?, part_1_only) => {
+        solution!(@impl $year, $day, $title, 1, [$($parse)?], [$($dot)?], |_lines| Err($crate::AdventError::UnknownPart {
+            year: $year,
+            day: $day,
+            part: 2,
+        }
+        .into()));
+    };
+    (@impl $year:literal, $day:literal, $title:literal, $parts:literal, [$($parse:expr)?], [$($dot:expr)?], |$lines:ident| $part_2:expr) => {
+        pub struct Puzzle;
+
+        impl $crate::Solution for Puzzle {
+            fn year(&self) -> u32 {
+                $year
+            }
+
+            fn day(&self) -> u32 {
+                $day
+            }
+
+            fn title(&self) -> &'static str {
+                $title
+            }
+
+            fn parts(&self) -> u32 {
+                $parts
+            }
+
+            $(
+                fn parse(&self, input: &mut dyn std::io::BufRead) -> Option<anyhow::Result<()>> {
+                    Some(($parse)(std::io::BufRead::lines(input)).map(drop))
+                }
+            )?
+
+            $(
+                fn dot(&self, input: &mut dyn std::io::BufRead) -> Option<anyhow::Result<String>> {
+                    let graph: anyhow::Result<$crate::dot::Graph> = ($dot)(std::io::BufRead::lines(input));
+                    Some(graph.map(|graph| graph.to_string()))
+                }
+            )?
+
+            fn part_1(&self, input: &mut dyn std::io::BufRead) -> anyhow::Result<String> {
+                riddle_1(std::io::BufRead::lines(input))
+            }
+
+            fn part_2(&self, input: &mut dyn std::io::BufRead) -> anyhow::Result<String> {
+                let $lines = std::io::BufRead::lines(input);
+                $part_2
+            }
+        }
+    };
+}
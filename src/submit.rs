@@ -0,0 +1,395 @@
+//! Submission of answers to the Advent of Code website
+//!
+//! Every rejected answer is recorded in `data/<year>/rejected.txt`, so that
+//! neither it nor any answer beyond a bound it established is sent again,
+//! and so is the time until which the website asked to wait.
+
+use anyhow::{bail, Context, Result};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{fetch::Client, AdventError};
+
+/// How the website responded to an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// An answer was given too recently, with the time left as stated
+    Wait(String),
+    /// The part is solved already or not unlocked yet
+    WrongLevel,
+    /// Any other response, as its text
+    Unknown(String),
+}
+
+impl Outcome {
+    /// Name of a rejection as recorded in the history
+    fn rejection(&self) -> Option<&'static str> {
+        match self {
+            Outcome::Wrong => Some("wrong"),
+            Outcome::TooHigh => Some("too-high"),
+            Outcome::TooLow => Some("too-low"),
+            _ => None,
+        }
+    }
+}
+
+/// Seconds in a time left to wait as the website states it, e.g. `1m 30s`
+fn parse_wait(left: &str) -> Option<u64> {
+    let mut seconds = 0;
+    for part in left.split_whitespace() {
+        let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let factor = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3600,
+            _ => return None,
+        };
+        seconds += number.parse::<u64>().ok()? * factor;
+    }
+    Some(seconds)
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// Reads the outcome from the text of the page returned for a submission
+pub fn parse_response(page: &str) -> Outcome {
+    let text = page
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        let left = text
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map_or("", |(left, _)| left);
+        Outcome::Wait(left.to_string())
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown(strip_tags(text).trim().to_string())
+    }
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Location of the record of rejected answers of the given year
+///
+/// Each line holds day, part, the kind of rejection (`wrong`, `too-high` or
+/// `too-low`) and the answer, separated by single spaces. Lines of kind
+/// `wait` hold the Unix time before which no answer is sent instead.
+pub fn rejected_path(year: u32) -> PathBuf {
+    format!("data/{year}/rejected.txt").into()
+}
+
+/// Answers rejected before and pending waits, of one year
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<(u32, u32, String, String)>,
+}
+
+impl History {
+    /// Reads the history at `path`, which is empty if there is no such file
+    pub fn read(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content).with_context(|| format!("in {}", path.display()))
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let mut entries = Vec::new();
+        for (idx, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut fields = line.splitn(4, ' ');
+            let (Some(day), Some(part), Some(kind), Some(answer)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                bail!(AdventError::at_line(
+                    idx,
+                    "expected '<day> <part> <kind> <answer>'"
+                ));
+            };
+            let day = day
+                .parse()
+                .map_err(|_| AdventError::at_line(idx, "invalid day"))?;
+            let part = part
+                .parse()
+                .map_err(|_| AdventError::at_line(idx, "invalid part"))?;
+            entries.push((day, part, kind.to_string(), answer.to_string()));
+        }
+        Ok(Self { entries })
+    }
+
+    /// Why `answer` would be rejected again at `now`, if it is known to be
+    /// wrong or the website asked to wait until later
+    pub fn check(&self, day: u32, part: u32, answer: &str, now: SystemTime) -> Option<String> {
+        let value = answer.parse::<i64>().ok();
+        let now = unix_seconds(now) as i64;
+        self.entries
+            .iter()
+            .filter(|(d, p, _, _)| (*d, *p) == (day, part))
+            .find_map(|(_, _, kind, rejected)| {
+                let bound = rejected.parse::<i64>().ok();
+                match (kind.as_str(), value, bound) {
+                    ("wait", _, Some(until)) if now < until => Some(format!(
+                        "answered too recently, {}s left to wait",
+                        until - now
+                    )),
+                    ("wait", _, _) => None,
+                    _ if rejected == answer => Some(format!("{answer} was rejected as {kind}")),
+                    ("too-high", Some(v), Some(b)) if v >= b => Some(format!(
+                        "{answer} is not below {rejected}, which is too high"
+                    )),
+                    ("too-low", Some(v), Some(b)) if v <= b => Some(format!(
+                        "{answer} is not above {rejected}, which is too low"
+                    )),
+                    _ => None,
+                }
+            })
+    }
+
+    /// Appends a rejected answer, or the end of the wait the website asked
+    /// for at `now`, to the history here and at `path`
+    pub fn record(
+        &mut self,
+        path: &Path,
+        day: u32,
+        part: u32,
+        outcome: &Outcome,
+        answer: &str,
+        now: SystemTime,
+    ) -> Result<()> {
+        let (kind, value) = match outcome {
+            Outcome::Wait(left) => {
+                let Some(seconds) = parse_wait(left) else {
+                    return Ok(());
+                };
+                ("wait", (unix_seconds(now) + seconds).to_string())
+            }
+            _ => match outcome.rejection() {
+                Some(kind) => (kind, answer.to_string()),
+                None => return Ok(()),
+            },
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{day} {part} {kind} {value}")?;
+        self.entries.push((day, part, kind.to_string(), value));
+        Ok(())
+    }
+}
+
+pub struct Submitter<C> {
+    client: C,
+    base_url: String,
+    session: String,
+}
+
+impl<C: Client> Submitter<C> {
+    pub fn new(client: C, base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+        }
+    }
+
+    /// Posts `answer` for the given puzzle part
+    pub fn submit(&self, year: u32, day: u32, part: u32, answer: &str) -> Result<Outcome> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let level = part.to_string();
+        let page = self.client.post(
+            &url,
+            &self.session,
+            &[("level", &level), ("answer", answer)],
+        )?;
+        Ok(parse_response(&page))
+    }
+
+    /// Posts `answer` unless `history` knows it would be rejected, and
+    /// records the outcome in `history` and at `path`
+    pub fn submit_new(
+        &self,
+        history: &mut History,
+        path: &Path,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<Outcome> {
+        if let Some(reason) = history.check(day, part, answer, SystemTime::now()) {
+            bail!("not submitting, {reason}");
+        }
+        let outcome = self.submit(year, day, part, answer)?;
+        history.record(path, day, part, &outcome, answer, SystemTime::now())?;
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_response, parse_wait, History, Outcome, Submitter};
+    use crate::fetch::Client;
+    use anyhow::Result;
+    use std::{
+        cell::RefCell,
+        env, fs,
+        time::{Duration, SystemTime},
+    };
+
+    /// URL and form fields of a request
+    type Post = (String, Vec<(String, String)>);
+
+    /// Answers every post with the same page, recording URLs and form fields
+    struct Stub {
+        page: String,
+        posts: RefCell<Vec<Post>>,
+    }
+
+    impl Client for Stub {
+        fn get(&self, _url: &str, _session: &str) -> Result<String> {
+            unimplemented!()
+        }
+
+        fn post(&self, url: &str, _session: &str, fields: &[(&str, &str)]) -> Result<String> {
+            let fields = fields
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            self.posts.borrow_mut().push((url.to_string(), fields));
+            Ok(self.page.clone())
+        }
+    }
+
+    fn page(text: &str) -> String {
+        format!("<html><main>\n<article><p>{text}</p></article>\n</main></html>")
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let correct = "That's the right answer! You are <span>one gold star</span> closer.";
+        assert_eq!(parse_response(&page(correct)), Outcome::Correct);
+        let high = "That's not the right answer; your answer is too high.";
+        assert_eq!(parse_response(&page(high)), Outcome::TooHigh);
+        let low = "That's not the right answer; your answer is too low.";
+        assert_eq!(parse_response(&page(low)), Outcome::TooLow);
+        let wrong = "That's not the right answer. If you're stuck, ...";
+        assert_eq!(parse_response(&page(wrong)), Outcome::Wrong);
+        let wait = "You gave an answer too recently. You have 42s left to wait.";
+        assert_eq!(parse_response(&page(wait)), Outcome::Wait("42s".into()));
+        let level = "You don't seem to be solving the right level.";
+        assert_eq!(parse_response(&page(level)), Outcome::WrongLevel);
+        assert_eq!(
+            parse_response(&page("Something <em>new</em>")),
+            Outcome::Unknown("Something new".into())
+        );
+    }
+
+    #[test]
+    fn test_history_check() {
+        let history = History::parse("1 1 too-high 100\n1 1 too-low 10\n1 2 wrong abc\n").unwrap();
+        let now = SystemTime::now();
+        assert!(history.check(1, 1, "100", now).is_some());
+        assert!(history.check(1, 1, "150", now).is_some());
+        assert!(history.check(1, 1, "5", now).is_some());
+        assert_eq!(history.check(1, 1, "50", now), None);
+        assert!(history.check(1, 2, "abc", now).is_some());
+        assert_eq!(history.check(1, 2, "150", now), None);
+        assert_eq!(history.check(2, 1, "150", now), None);
+        assert!(History::parse("1 x wrong 5\n").is_err());
+        let spaced = History::parse("\n1 1 wrong 5\n  \n").unwrap();
+        assert!(spaced.check(1, 1, "5", now).is_some());
+        let error = History::parse("1 1 wrong 5\n\n1 x wrong 5\n").unwrap_err();
+        assert!(error.to_string().contains("line 3"), "{error}");
+    }
+
+    #[test]
+    fn test_history_wait() {
+        assert_eq!(parse_wait("42s"), Some(42));
+        assert_eq!(parse_wait("1m 30s"), Some(90));
+        assert_eq!(parse_wait("soon"), None);
+        let dir = env::temp_dir().join(format!("advent-wait-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("rejected.txt");
+        let mut history = History::default();
+        let now = SystemTime::now();
+        let wait = Outcome::Wait("1m 30s".into());
+        history.record(&path, 1, 1, &wait, "50", now).unwrap();
+        let later = |s| now + Duration::from_secs(s);
+        assert!(history.check(1, 1, "50", later(60)).is_some());
+        assert_eq!(history.check(1, 1, "50", later(100)), None);
+        let reread = History::read(&path).unwrap();
+        assert!(reread.check(1, 1, "60", later(60)).is_some());
+        assert_eq!(reread.check(1, 1, "60", later(100)), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_submitter_posts_once() {
+        let dir = env::temp_dir().join(format!("advent-submit-{}", std::process::id()));
+        let path = dir.join("2023/rejected.txt");
+        let stub = Stub {
+            page: page("That's not the right answer; your answer is too high."),
+            posts: RefCell::new(Vec::new()),
+        };
+        let submitter = Submitter::new(stub, "http://stub/", "secret");
+        let mut history = History::default();
+        let outcome = submitter
+            .submit_new(&mut history, &path, 2023, 5, 2, "4711")
+            .unwrap();
+        assert_eq!(outcome, Outcome::TooHigh);
+        assert!(submitter
+            .submit_new(&mut history, &path, 2023, 5, 2, "4711")
+            .is_err());
+        assert!(submitter
+            .submit_new(&mut history, &path, 2023, 5, 2, "5000")
+            .is_err());
+        let field = |k: &str, v: &str| (k.to_string(), v.to_string());
+        assert_eq!(
+            *submitter.client.posts.borrow(),
+            vec![(
+                "http://stub/2023/day/5/answer".to_string(),
+                vec![field("level", "2"), field("answer", "4711")]
+            )]
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "5 2 too-high 4711\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,258 @@
+//! Points and vectors in space, the 3D counterpart of [`crate::Position`]
+//! and [`crate::Direction`]
+
+use num::{One, Signed, Zero};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Point or vector with coordinates of type `T`, ordered by x, then y, then z
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Copy> Vec3<T> {
+    /// Coordinates in the order x, y, z
+    pub fn to_array(self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    pub fn from_array([x, y, z]: [T; 3]) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Vec3<T> {
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl<T: Copy + Sub<Output = T> + Mul<Output = T>> Vec3<T> {
+    pub fn cross(self, other: Self) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+}
+
+impl<T: Copy + Signed> Vec3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Vec3<T> {
+    pub fn rotate(self, rotation: &Rotation) -> Self {
+        let coords = self.to_array();
+        Self::from_array(rotation.0.map(|row| {
+            let axis = row.iter().position(|&c| c != 0).unwrap();
+            if row[axis] > 0 {
+                coords[axis]
+            } else {
+                -coords[axis]
+            }
+        }))
+    }
+}
+
+impl<T: Copy + PartialEq + Zero + One + Neg<Output = T> + Add<Output = T>> Vec3<T> {
+    /// The six points sharing a face with this one
+    pub fn neighbours6(self) -> impl Iterator<Item = Self> {
+        let (o, l) = (T::zero(), T::one());
+        [
+            Self::new(l, o, o),
+            Self::new(-l, o, o),
+            Self::new(o, l, o),
+            Self::new(o, -l, o),
+            Self::new(o, o, l),
+            Self::new(o, o, -l),
+        ]
+        .into_iter()
+        .map(move |d| self + d)
+    }
+
+    /// The 26 points sharing a face, an edge or a corner with this one
+    pub fn neighbours26(self) -> impl Iterator<Item = Self> {
+        let steps = [-T::one(), T::zero(), T::one()];
+        let origin = Self::new(T::zero(), T::zero(), T::zero());
+        steps
+            .into_iter()
+            .flat_map(move |x| {
+                steps
+                    .into_iter()
+                    .flat_map(move |y| steps.into_iter().map(move |z| Self::new(x, y, z)))
+            })
+            .filter(move |&d| d != origin)
+            .map(move |d| self + d)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vec3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+/// Rotation by multiples of 90° about the axes, as a matrix holding a single
+/// 1 or -1 in every row and column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation(pub [[i32; 3]; 3]);
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    /// The 24 orientations of a cube, starting with the identity
+    pub fn all() -> Vec<Rotation> {
+        let permutations = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        let mut rotations = Vec::new();
+        for axes in permutations {
+            for signs in 0..8 {
+                let mut matrix = [[0; 3]; 3];
+                for (row, &axis) in axes.iter().enumerate() {
+                    matrix[row][axis] = if signs & (1 << row) == 0 { 1 } else { -1 };
+                }
+                // the others are reflections
+                if Rotation(matrix).determinant() == 1 {
+                    rotations.push(Rotation(matrix));
+                }
+            }
+        }
+        rotations
+    }
+
+    fn determinant(&self) -> i32 {
+        let m = &self.0;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// Rotation by `self` followed by `other`
+    pub fn then(&self, other: &Rotation) -> Rotation {
+        let mut matrix = [[0; 3]; 3];
+        for (row, entries) in matrix.iter_mut().enumerate() {
+            for (col, entry) in entries.iter_mut().enumerate() {
+                *entry = (0..3).map(|k| other.0[row][k] * self.0[k][col]).sum();
+            }
+        }
+        Rotation(matrix)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Rotation, Vec3};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Vec3::new(1, 2, 3);
+        let b = Vec3::new(-2, 0, 5);
+        assert_eq!(a + b, Vec3::new(-1, 2, 8));
+        assert_eq!(a - b, Vec3::new(3, 2, -2));
+        assert_eq!(-a * 2, Vec3::new(-2, -4, -6));
+        assert_eq!(a.dot(b), 13);
+        assert_eq!(a.cross(b), Vec3::new(10, -11, 4));
+        assert_eq!(a.manhattan(b), 7);
+    }
+
+    #[test]
+    fn test_rotations() {
+        let rotations = Rotation::all();
+        assert_eq!(rotations.len(), 24);
+        assert_eq!(rotations[0], Rotation::IDENTITY);
+        let v = Vec3::new(1, 2, 3);
+        let images: HashSet<_> = rotations.iter().map(|r| v.rotate(r)).collect();
+        assert_eq!(images.len(), 24);
+        for r in &rotations {
+            for s in &rotations {
+                assert_eq!(v.rotate(r).rotate(s), v.rotate(&r.then(s)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let v = Vec3::new(0, 0, 0);
+        assert_eq!(v.neighbours6().count(), 6);
+        assert!(v.neighbours6().all(|n| n.manhattan(v) == 1));
+        let all: HashSet<_> = v.neighbours26().collect();
+        assert_eq!(all.len(), 26);
+        assert!(!all.contains(&v));
+    }
+}
@@ -0,0 +1,257 @@
+//! Frames of grid simulations written to image files, for watching what a
+//! solution does instead of printing its grid to the terminal
+//!
+//! Recording is off until [`start`] is called, which `run --visualize <dir>`
+//! does. Simulations pass every step to [`record`], which only renders the
+//! frame while recording. Each simulation names its frames, so that they end
+//! up as `<dir>/<name>-<step>.ppm`, or as `<dir>/<name>.gif` for animations.
+//! PNG and GIF need the `visualize` feature.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, MutexGuard,
+    },
+};
+
+use anyhow::{bail, Result};
+
+/// Colour of a pixel as red, green and blue
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const GREY: Rgb = [128, 128, 128];
+pub const SAND: Rgb = [230, 190, 90];
+pub const WATER: Rgb = [40, 110, 230];
+pub const LAVA: Rgb = [250, 120, 20];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameFormat {
+    Ppm,
+    Png,
+    Gif,
+}
+
+impl FrameFormat {
+    fn is_supported(self) -> bool {
+        self == FrameFormat::Ppm || cfg!(feature = "visualize")
+    }
+}
+
+/// Image of a grid with one square of `scale` pixels per cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Frame {
+    /// Renders rows of cells, which must all have the same length, with the
+    /// colours `palette` gives them
+    pub fn from_rows<T, R>(
+        rows: impl IntoIterator<Item = R>,
+        scale: usize,
+        palette: impl Fn(&T) -> Rgb,
+    ) -> Self
+    where
+        R: AsRef<[T]>,
+    {
+        let mut width = 0;
+        let mut height = 0;
+        let mut pixels = Vec::new();
+        for row in rows {
+            let line: Vec<Rgb> = row
+                .as_ref()
+                .iter()
+                .flat_map(|cell| std::iter::repeat_n(palette(cell), scale))
+                .collect();
+            width = line.len();
+            for _ in 0..scale {
+                pixels.extend(&line);
+            }
+            height += scale;
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    /// The frame as a binary PPM image
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.bytes());
+        ppm
+    }
+
+    fn save(&self, path: &Path, format: FrameFormat) -> Result<()> {
+        match format {
+            FrameFormat::Ppm => fs::write(path, self.to_ppm())?,
+            #[cfg(feature = "visualize")]
+            FrameFormat::Png => {
+                let file = std::io::BufWriter::new(fs::File::create(path)?);
+                let mut encoder = png::Encoder::new(file, self.width as u32, self.height as u32);
+                encoder.set_color(png::ColorType::Rgb);
+                encoder.set_depth(png::BitDepth::Eight);
+                encoder.write_header()?.write_image_data(&self.bytes())?;
+            }
+            _ => bail!("{format:?} frames need the visualize feature"),
+        }
+        Ok(())
+    }
+}
+
+/// Writes the frames of one simulation into an animated GIF
+#[cfg(feature = "visualize")]
+fn save_gif(path: &Path, frames: &[Frame]) -> Result<()> {
+    let Some(first) = frames.first() else {
+        return Ok(());
+    };
+    let (width, height) = (first.width as u16, first.height as u16);
+    let file = std::io::BufWriter::new(fs::File::create(path)?);
+    let mut encoder = gif::Encoder::new(file, width, height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    for frame in frames {
+        if frame.width != first.width || frame.height != first.height {
+            bail!("frames of {} differ in size", path.display());
+        }
+        let mut image = gif::Frame::from_rgb(width, height, &frame.bytes());
+        image.delay = 5;
+        encoder.write_frame(&image)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "visualize"))]
+fn save_gif(_path: &Path, _frames: &[Frame]) -> Result<()> {
+    bail!("GIF animations need the visualize feature")
+}
+
+struct Recorder {
+    dir: PathBuf,
+    format: FrameFormat,
+    scale: usize,
+    steps: HashMap<String, usize>,
+    animations: HashMap<String, Vec<Frame>>,
+    /// The first error while saving, which [`finish`] reports
+    error: Option<anyhow::Error>,
+}
+
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+/// Whether [`RECORDER`] is set, so that simulations running concurrently
+/// don't contend for its lock while nothing is recorded
+static RECORDING: AtomicBool = AtomicBool::new(false);
+
+fn recorder() -> MutexGuard<'static, Option<Recorder>> {
+    RECORDER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Starts recording frames into `dir`, which is created if necessary, with
+/// every grid cell drawn as a square of `scale` pixels
+pub fn start(dir: &Path, format: FrameFormat, scale: usize) -> Result<()> {
+    if !format.is_supported() {
+        bail!("{format:?} frames need the visualize feature");
+    }
+    fs::create_dir_all(dir)?;
+    *recorder() = Some(Recorder {
+        dir: dir.to_path_buf(),
+        format,
+        scale: scale.max(1),
+        steps: HashMap::new(),
+        animations: HashMap::new(),
+        error: None,
+    });
+    RECORDING.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Records the next frame of the simulation `name`, rendering the grid given
+/// by its rows only while recording
+pub fn record<T, R>(name: &str, rows: impl IntoIterator<Item = R>, palette: impl Fn(&T) -> Rgb)
+where
+    R: AsRef<[T]>,
+{
+    if !RECORDING.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut guard = recorder();
+    let Some(recorder) = guard.as_mut() else {
+        return;
+    };
+    let frame = Frame::from_rows(rows, recorder.scale, palette);
+    let step = recorder.steps.entry(name.to_string()).or_default();
+    let path = recorder.dir.join(format!("{name}-{step:05}"));
+    *step += 1;
+    let saved = match recorder.format {
+        FrameFormat::Gif => {
+            recorder
+                .animations
+                .entry(name.to_string())
+                .or_default()
+                .push(frame);
+            Ok(())
+        }
+        FrameFormat::Ppm => frame.save(&path.with_extension("ppm"), recorder.format),
+        FrameFormat::Png => frame.save(&path.with_extension("png"), recorder.format),
+    };
+    if let Err(err) = saved {
+        recorder.error.get_or_insert(err);
+    }
+}
+
+/// Stops recording and writes the animations, returning the number of
+/// frames recorded or the first error while saving them
+pub fn finish() -> Result<usize> {
+    RECORDING.store(false, Ordering::Relaxed);
+    let Some(recorder) = recorder().take() else {
+        return Ok(0);
+    };
+    if let Some(err) = recorder.error {
+        return Err(err);
+    }
+    for (name, frames) in &recorder.animations {
+        save_gif(&recorder.dir.join(format!("{name}.gif")), frames)?;
+    }
+    Ok(recorder.steps.values().sum())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Frame, BLACK, WHITE};
+
+    #[test]
+    fn test_frame() {
+        let rows = ["#.", ".#"].map(|r| r.as_bytes().to_vec());
+        let palette = |c: &u8| if *c == b'#' { WHITE } else { BLACK };
+        let frame = Frame::from_rows(&rows, 2, palette);
+        assert_eq!((frame.width(), frame.height()), (4, 4));
+        let ppm = frame.to_ppm();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        let pixels = &ppm[11..];
+        assert_eq!(pixels.len(), 4 * 4 * 3);
+        assert_eq!(&pixels[..6], &[255; 6]);
+        assert_eq!(&pixels[6..12], &[0; 6]);
+        assert_eq!(&pixels[36..42], &[0; 6]);
+    }
+}
@@ -1,16 +1,13 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::next_line;
+use anyhow::Result;
+
+solution!(2015, 1, "Not Quite Lisp");
 
-pub fn riddle_1(mut lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_1(mut lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut floor = 0;
-    let line = lines.next().unwrap().unwrap().to_string();
+    let line = next_line(&mut lines)?;
     for l in line.as_bytes() {
         match l {
             b'(' => floor += 1,
@@ -18,12 +15,12 @@ pub fn riddle_1(mut lines: io::Lines<io::BufReader<File>>) -> String {
             _ => {}
         }
     }
-    format!("{floor}")
+    Ok(format!("{floor}"))
 }
 
-pub fn riddle_2(mut lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_2(mut lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut floor = 0;
-    let line = lines.next().unwrap().unwrap().to_string();
+    let line = next_line(&mut lines)?;
     let mut count = 1;
     for l in line.as_bytes() {
         match l {
@@ -36,25 +33,20 @@ pub fn riddle_2(mut lines: io::Lines<io::BufReader<File>>) -> String {
         }
         count += 1;
     }
-    format!("{count}")
+    Ok(format!("{count}"))
 }
 
 #[cfg(test)]
 mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2015_1_1() {
-        let lines = read_lines("data/2015/1.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "232");
-    }
+    use super::{riddle_1, riddle_2};
+    use std::io::BufRead;
 
     #[test]
-    fn test_2015_1_2() {
-        let lines = read_lines("data/2015/1.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1783");
+    fn test_2015_1_example() {
+        assert_eq!(riddle_1("(())".as_bytes().lines()).unwrap(), "0");
+        assert_eq!(riddle_1("))(((((".as_bytes().lines()).unwrap(), "3");
+        assert_eq!(riddle_1(")())())".as_bytes().lines()).unwrap(), "-3");
+        assert_eq!(riddle_2(")".as_bytes().lines()).unwrap(), "1");
+        assert_eq!(riddle_2("()())".as_bytes().lines()).unwrap(), "5");
     }
 }
@@ -1,62 +1,35 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
+use crate::parse_lines;
+use anyhow::{anyhow, Result};
+
+solution!(2015, 2, "I Was Told There Would Be No Math");
+
+fn parse_dims(line: &str) -> Result<[u64; 3]> {
+    let dims: Vec<u64> = line.split('x').map(str::parse).collect::<Result<_, _>>()?;
+    dims.try_into()
+        .map_err(|_| anyhow!("expected three dimensions"))
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut sum = 0;
-    for l in lines {
-        let dims: Vec<u64> = l
-            .unwrap()
-            .split('x')
-            .map(|s| str::parse(s).unwrap())
-            .collect();
+    for dims in parse_lines(lines, parse_dims)? {
         let a = dims[0] * dims[1];
         let b = dims[1] * dims[2];
         let c = dims[2] * dims[0];
         sum += 2 * (a + b + c) + a.min(b).min(c);
     }
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut sum = 0;
-    for l in lines {
-        let dims: Vec<u64> = l
-            .unwrap()
-            .split('x')
-            .map(|s| str::parse(s).unwrap())
-            .collect();
+    for dims in parse_lines(lines, parse_dims)? {
         let v = dims[0] * dims[1] * dims[2];
         let b = (dims[0] + dims[1])
             .min(dims[1] + dims[2])
             .min(dims[2] + dims[0]);
         sum += 2 * b + v;
     }
-    format!("{sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2015_2_1() {
-        let lines = read_lines("data/2015/2.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "1588178");
-    }
-
-    #[test]
-    fn test_2015_2_2() {
-        let lines = read_lines("data/2015/2.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "3783758");
-    }
+    Ok(format!("{sum}"))
 }
@@ -1,13 +1,10 @@
 use std::collections::HashSet;
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{next_line, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2016, 1, "No Time for a Taxicab");
 
 #[derive(Debug)]
 enum Direction {
@@ -73,8 +70,8 @@ impl Position {
 
 use Direction::*;
 
-pub fn riddle_1(mut lines: io::Lines<io::BufReader<File>>) -> String {
-    let line = lines.next().unwrap().unwrap().to_string();
+pub fn riddle_1(mut lines: io::Lines<impl BufRead>) -> Result<String> {
+    let line = next_line(&mut lines)?;
     let directions: Vec<&[u8]> = line.split(", ").map(|s| s.as_bytes()).collect();
     let mut pos = Position::new();
     for mov in directions {
@@ -85,16 +82,19 @@ pub fn riddle_1(mut lines: io::Lines<io::BufReader<File>>) -> String {
             b'L' => {
                 pos.dir.turn_left();
             }
-            _ => panic!("Invalid move"),
+            _ => bail!(AdventError::invalid(format!(
+                "invalid move '{}'",
+                String::from_utf8_lossy(mov)
+            ))),
         }
-        let steps = String::from_utf8_lossy(&mov[1..]).parse().unwrap();
+        let steps = String::from_utf8_lossy(&mov[1..]).parse()?;
         pos.go(steps);
     }
-    format!("{}", pos.x.abs() + pos.y.abs())
+    Ok(format!("{}", pos.x.abs() + pos.y.abs()))
 }
 
-pub fn riddle_2(mut lines: io::Lines<io::BufReader<File>>) -> String {
-    let line = lines.next().unwrap().unwrap().to_string();
+pub fn riddle_2(mut lines: io::Lines<impl BufRead>) -> Result<String> {
+    let line = next_line(&mut lines)?;
     let directions: Vec<&[u8]> = line.split(", ").map(|s| s.as_bytes()).collect();
     let mut pos = Position::new();
     let mut past_positions = HashSet::new();
@@ -107,36 +107,19 @@ pub fn riddle_2(mut lines: io::Lines<io::BufReader<File>>) -> String {
             b'L' => {
                 pos.dir.turn_left();
             }
-            _ => panic!("Invalid move"),
+            _ => bail!(AdventError::invalid(format!(
+                "invalid move '{}'",
+                String::from_utf8_lossy(mov)
+            ))),
         }
-        let steps = String::from_utf8_lossy(&mov[1..]).parse().unwrap();
+        let steps = String::from_utf8_lossy(&mov[1..]).parse()?;
         for _ in 0..steps {
             pos.go(1);
             if past_positions.contains(&(pos.x, pos.y)) {
-                return format!("{}", pos.x.abs() + pos.y.abs());
+                return Ok(format!("{}", pos.x.abs() + pos.y.abs()));
             }
             past_positions.insert((pos.x, pos.y));
         }
     }
-    "no solution found".to_string()
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2016_1_1() {
-        let lines = read_lines("data/2016/1.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "271");
-    }
-
-    #[test]
-    fn test_2016_1_2() {
-        let lines = read_lines("data/2016/1.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "153");
-    }
+    Err(AdventError::NoSolution.into())
 }
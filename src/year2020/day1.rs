@@ -1,65 +1,34 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{parse_lines, AdventError};
+use anyhow::Result;
+
+solution!(2020, 1, "Report Repair");
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let numbers: Vec<i32> = lines
-        .into_iter()
-        .map_while(Result::ok)
-        .filter_map(|s| s.parse::<i32>().ok())
-        .collect();
+fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let numbers: Vec<i32> = parse_lines(lines, |s| Ok(s.parse()?))?;
 
     for (idx, i) in numbers.iter().enumerate() {
         for j in numbers[idx + 1..].iter() {
             if i + j == 2020 {
-                return format!("{}", i * j);
+                return Ok(format!("{}", i * j));
             }
         }
     }
-    "No solution found".to_string()
+    Err(AdventError::NoSolution.into())
 }
 
-fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let numbers: Vec<i32> = lines
-        .into_iter()
-        .map_while(Result::ok)
-        .filter_map(|s| s.parse::<i32>().ok())
-        .collect();
+fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let numbers: Vec<i32> = parse_lines(lines, |s| Ok(s.parse()?))?;
 
     for (idx, i) in numbers.iter().enumerate() {
         for (jdx, j) in numbers[idx + 1..].iter().enumerate() {
             for k in numbers[idx + jdx + 1..].iter() {
                 if i + j + k == 2020 {
-                    return format!("{}", i * j * k);
+                    return Ok(format!("{}", i * j * k));
                 }
             }
         }
     }
-    "No solution found".to_string()
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2020_1_1() {
-        let lines = read_lines("data/2020/1.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "889779");
-    }
-
-    #[test]
-    fn test_2020_1_2() {
-        let lines = read_lines("data/2020/1.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "76110336");
-    }
+    Err(AdventError::NoSolution.into())
 }
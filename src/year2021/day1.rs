@@ -1,67 +1,21 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
-
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let numbers: Vec<i32> = lines
-        .into_iter()
-        .filter_map(|s| s.ok())
-        .filter_map(|s| s.parse::<i32>().ok())
-        .collect();
+use crate::parse_lines;
+use anyhow::Result;
 
-    let mut last = numbers[0];
-    let count = numbers
-        .iter()
-        .filter(|x| {
-            let res = **x > last;
-            last = **x;
-            res
-        })
-        .count();
-    format!("{count}")
-}
+solution!(2021, 1, "Sonar Sweep");
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let numbers: Vec<i32> = lines
-        .into_iter()
-        .filter_map(|s| s.ok())
-        .filter_map(|s| s.parse::<i32>().ok())
-        .collect();
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let numbers: Vec<i32> = parse_lines(lines, |s| Ok(s.parse()?))?;
 
-    let mut sum = numbers[0] + numbers[1] + numbers[2];
-    let mut count = 0;
-    for i in 1..numbers.len() - 2 {
-        let new_sum = sum + numbers[i + 2] - numbers[i - 1];
-        if new_sum > sum {
-            count += 1;
-        }
-        sum = new_sum;
-    }
-    format!("{count}")
+    let count = numbers.windows(2).filter(|w| w[1] > w[0]).count();
+    Ok(format!("{count}"))
 }
 
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_1_1() {
-        let lines = read_lines("data/2021/1.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "1400");
-    }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let numbers: Vec<i32> = parse_lines(lines, |s| Ok(s.parse()?))?;
 
-    #[test]
-    fn test_2021_1_2() {
-        let lines = read_lines("data/2021/1.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1429");
-    }
+    // consecutive sums share two numbers, so only the outer ones decide
+    let count = numbers.windows(4).filter(|w| w[3] > w[0]).count();
+    Ok(format!("{count}"))
 }
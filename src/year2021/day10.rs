@@ -1,12 +1,9 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::parse_lines;
+use anyhow::Result;
+
+solution!(2021, 10, "Syntax Scoring");
 
 fn score_incorrect(s: &str) -> (usize, Vec<char>) {
     let mut stack = Vec::new();
@@ -53,14 +50,12 @@ fn score_incorrect(s: &str) -> (usize, Vec<char>) {
     (0, stack)
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let score: usize = lines
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let score: usize = parse_lines(lines, |s| Ok(score_incorrect(s).0))?
         .into_iter()
-        .filter_map(|l| l.ok())
-        .map(|s| score_incorrect(&s).0)
         .sum();
 
-    format!("{score}")
+    Ok(format!("{score}"))
 }
 
 pub fn score_stack(s: &[char]) -> usize {
@@ -78,40 +73,19 @@ pub fn score_stack(s: &[char]) -> usize {
     score
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut scores: Vec<usize> = lines
-        .into_iter()
-        .filter_map(|l| l.ok())
-        .filter_map(|s| {
-            let (score, stack) = score_incorrect(&s);
-            match score {
-                0 => Some(score_stack(&stack)),
-                _ => None,
-            }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut scores: Vec<usize> = parse_lines(lines, |s| {
+        let (score, stack) = score_incorrect(s);
+        Ok(match score {
+            0 => Some(score_stack(&stack)),
+            _ => None,
         })
-        .collect();
+    })?
+    .into_iter()
+    .flatten()
+    .collect();
 
     scores.sort();
     let mid = scores.len() / 2;
-    format!("{}", scores[mid])
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_10_1() {
-        let lines = read_lines("data/2021/10.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "462693");
-    }
-
-    #[test]
-    fn test_2021_10_2() {
-        let lines = read_lines("data/2021/10.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "3094671161");
-    }
+    Ok(format!("{}", scores[mid]))
 }
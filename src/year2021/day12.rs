@@ -1,26 +1,46 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
 use std::collections::{HashMap, HashSet};
 
-fn read_pairs(lines: io::Lines<io::BufReader<File>>) -> Vec<(String, String)> {
-    lines
-        .into_iter()
-        .filter_map(|l| l.ok())
-        .map(|s| {
-            s.split("-")
-                .into_iter()
-                .map(|s| s.to_owned())
-                .collect::<Vec<String>>()
-        })
-        .map(|s| (s[0].clone(), s[1].clone()))
-        .collect()
+use crate::{dot::Graph, parse_lines};
+use anyhow::{Context, Result};
+
+solution!(
+    2021,
+    12,
+    "Passage Pathing",
+    parse = read_pairs,
+    dot = cave_graph
+);
+
+fn read_pairs(lines: io::Lines<impl BufRead>) -> Result<Vec<(String, String)>> {
+    parse_lines(lines, |s| {
+        let (from, to) = s
+            .split_once("-")
+            .context("expected connection as from-to")?;
+        Ok((from.to_owned(), to.to_owned()))
+    })
+}
+
+/// The cave system, with big caves drawn as boxes
+fn cave_graph(lines: io::Lines<impl BufRead>) -> Result<Graph> {
+    let pairs = read_pairs(lines)?;
+    let mut graph = Graph::undirected("2021-12");
+    let mut names: Vec<&String> = pairs.iter().flat_map(|(a, b)| [a, b]).collect();
+    names.sort();
+    names.dedup();
+    for name in names {
+        let (kind, shape) = match name.as_str() {
+            "start" | "end" => (name.as_str(), "doublecircle"),
+            _ if is_lower_case(name) => ("small", "ellipse"),
+            _ => ("big", "box"),
+        };
+        graph.node(name, &[("kind", &kind), ("shape", &shape)]);
+    }
+    for (from, to) in &pairs {
+        graph.edge(from, to, &[]);
+    }
+    Ok(graph)
 }
 
 #[derive(Debug)]
@@ -31,12 +51,7 @@ struct Node {
 }
 
 fn is_lower_case(s: &str) -> bool {
-    let zed = "Z".bytes().next().unwrap();
-    if s.bytes().next().unwrap() > zed {
-        true
-    } else {
-        false
-    }
+    s.starts_with(|c: char| c.is_ascii_lowercase())
 }
 
 fn prepare_nodes(pairs: Vec<(String, String)>) -> (usize, Vec<Node>) {
@@ -94,7 +109,7 @@ fn find_all_paths(
     let mut double_chance = double_chance;
     // exclude small caves already visited
     if visited.contains(&idx) {
-        if double_chance == true && nodes[idx].name != "start" {
+        if double_chance && nodes[idx].name != "start" {
             double_chance = false;
         } else {
             return;
@@ -114,8 +129,8 @@ fn find_all_paths(
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let pairs = read_pairs(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let pairs = read_pairs(lines)?;
     let (start, nodes) = prepare_nodes(pairs);
 
     let mut paths = Vec::new();
@@ -123,11 +138,11 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
     let start_path = vec![start];
     find_all_paths(start_path, false, &nodes, visited, &mut paths);
 
-    format!("{:?}", paths.len())
+    Ok(format!("{:?}", paths.len()))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let pairs = read_pairs(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let pairs = read_pairs(lines)?;
     let (start, nodes) = prepare_nodes(pairs);
 
     let mut paths = Vec::new();
@@ -135,25 +150,5 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
     let start_path = vec![start];
     find_all_paths(start_path, true, &nodes, visited, &mut paths);
 
-    format!("{}", paths.len())
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_12_1() {
-        let lines = read_lines("data/2021/12.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "3497");
-    }
-
-    #[test]
-    fn test_2021_12_2() {
-        let lines = read_lines("data/2021/12.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "93686");
-    }
+    Ok(format!("{}", paths.len()))
 }
@@ -1,14 +1,12 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
 use std::collections::HashSet;
 
+use crate::{ocr::read_letters, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2021, 13, "Transparent Origami", parse = read_instructions);
+
 #[derive(Debug)]
 enum Fold {
     X(i32),
@@ -21,7 +19,7 @@ struct Instructions {
     folds: Vec<Fold>,
 }
 
-fn fold(points: &Vec<(i32, i32)>, fold: &Fold) -> Vec<(i32, i32)> {
+fn fold(points: &[(i32, i32)], fold: &Fold) -> Vec<(i32, i32)> {
     let new_points: HashSet<(i32, i32)> = points
         .iter()
         .map(|xy| match fold {
@@ -44,43 +42,48 @@ fn fold(points: &Vec<(i32, i32)>, fold: &Fold) -> Vec<(i32, i32)> {
     new_points.into_iter().collect()
 }
 
-fn read_instructions(lines: io::Lines<io::BufReader<File>>) -> Instructions {
+fn read_instructions(lines: io::Lines<impl BufRead>) -> Result<Instructions> {
     let mut instructions = Instructions {
         points: Vec::new(),
         folds: Vec::new(),
     };
     let mut read_points = true;
-    for line in lines.into_iter().filter_map(|x| x.ok()) {
+    for (idx, line) in lines.enumerate() {
+        let line = line?;
         if line.is_empty() {
             read_points = false;
             continue;
         }
         if read_points {
-            let points: Vec<i32> = line
-                .split(",")
-                .into_iter()
-                .filter_map(|s| s.parse::<i32>().ok())
-                .collect();
-            instructions.points.push((points[0], points[1]));
+            let (x, y) = line
+                .split_once(",")
+                .ok_or_else(|| AdventError::at_line(idx, "expected point as x,y"))?;
+            instructions.points.push((x.parse()?, y.parse()?));
         } else {
-            let fold_num: i32 = line[13..].parse().unwrap();
-            if &line[11..12] == "x" {
-                instructions.folds.push(Fold::X(fold_num));
-            } else {
-                instructions.folds.push(Fold::Y(fold_num));
+            match line
+                .strip_prefix("fold along ")
+                .and_then(|f| f.split_once("="))
+            {
+                Some(("x", num)) => instructions.folds.push(Fold::X(num.parse()?)),
+                Some(("y", num)) => instructions.folds.push(Fold::Y(num.parse()?)),
+                _ => bail!(AdventError::at_line(idx, "invalid fold instruction")),
             }
         }
     }
-    instructions
+    Ok(instructions)
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let instructions = read_instructions(lines);
-    let points = fold(&instructions.points, &instructions.folds[0]);
-    format!("{}", points.len())
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let instructions = read_instructions(lines)?;
+    let first = instructions
+        .folds
+        .first()
+        .ok_or(AdventError::invalid("no fold instructions"))?;
+    let points = fold(&instructions.points, first);
+    Ok(format!("{}", points.len()))
 }
 
-fn print_points(points: &Vec<(i32, i32)>) -> String {
+fn print_points(points: &[(i32, i32)]) -> String {
     let mut xmax = 0;
     let mut ymax = 0;
     points.iter().for_each(|xy| {
@@ -103,40 +106,11 @@ fn print_points(points: &Vec<(i32, i32)>) -> String {
     result
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let instructions = read_instructions(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let instructions = read_instructions(lines)?;
     let mut points = instructions.points.clone();
     for f in instructions.folds {
         points = fold(&points, &f);
     }
-    print_points(&points)
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_13_1() {
-        let lines = read_lines("data/2021/13.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "695");
-    }
-
-    #[test]
-    fn test_2021_13_2() {
-        let lines = read_lines("data/2021/13.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(
-            result,
-            r" ##    ## ####  ##  #    #  # ###    ##
-#  #    #    # #  # #    #  # #  #    #
-#       #   #  #    #    #  # #  #    #
-# ##    #  #   # ## #    #  # ###     #
-#  # #  # #    #  # #    #  # #    #  #
- ###  ##  ####  ### ####  ##  #     ## 
-"
-        );
-    }
+    Ok(read_letters(&print_points(&points))?)
 }
@@ -1,88 +1,100 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
+use std::collections::HashMap;
+
+use crate::{next_line, parse_lines_after, AdventError};
+use anyhow::{anyhow, bail, Result};
+
+solution!(2021, 14, "Extended Polymerization", parse = read_input);
+
+fn parse_rule(line: &str) -> Result<(&str, &str)> {
+    line.split_once(" -> ")
+        .ok_or_else(|| anyhow!("expected '<pair> -> <element>'"))
+}
+
+fn read_polymere(lines: &mut io::Lines<impl BufRead>) -> Result<String> {
+    let polymere = next_line(lines)?;
+    if polymere.len() < 2 {
+        bail!(AdventError::at_line(
+            0,
+            "polymer needs at least two elements"
+        ));
     }
+    Ok(polymere)
+}
+
+fn missing_rule(a: u8, b: u8) -> AdventError {
+    AdventError::invalid(format!("no rule for pair {}{}", a as char, b as char))
 }
-use std::collections::HashMap;
 
-fn read_input(lines: io::Lines<io::BufReader<File>>) -> (String, HashMap<String, String>) {
-    let mut lines = lines.into_iter().filter_map(|x| x.ok());
-    let polymere = lines.next().unwrap();
-    let rules: HashMap<String, String> = lines
-        .skip(1)
-        .map(|x| {
-            let s: Vec<String> = x.split(" -> ").into_iter().map(|x| x.to_owned()).collect();
-            (s[0].clone(), s[1].clone())
-        })
-        .collect();
-    (polymere, rules)
+fn read_input(mut lines: io::Lines<impl BufRead>) -> Result<(String, HashMap<String, String>)> {
+    let polymere = read_polymere(&mut lines)?;
+    next_line(&mut lines)?;
+    let rules = parse_lines_after(lines, 2, |line| {
+        let (pair, element) = parse_rule(line)?;
+        Ok((pair.to_owned(), element.to_owned()))
+    })?;
+    Ok((polymere, rules.into_iter().collect()))
 }
 
-fn calc_new_polymere(polymere: String, rules: &HashMap<String, String>) -> String {
+fn calc_new_polymere(polymere: String, rules: &HashMap<String, String>) -> Result<String> {
     let mut new_polymere = Vec::new();
-    let elements: Vec<u8> = polymere.bytes().into_iter().collect();
-    for i in 0..elements.len() - 1 {
-        new_polymere.push(elements[i]);
-        let insertion = rules[&String::from_utf8(elements[i..=i + 1].to_vec()).unwrap()]
-            .bytes()
-            .next()
-            .unwrap();
+    let elements: Vec<u8> = polymere.bytes().collect();
+    for pair in elements.windows(2) {
+        new_polymere.push(pair[0]);
+        let insertion = rules
+            .get(&String::from_utf8_lossy(pair).into_owned())
+            .and_then(|element| element.bytes().next())
+            .ok_or_else(|| missing_rule(pair[0], pair[1]))?;
         new_polymere.push(insertion);
     }
-    new_polymere.push(*elements.last().unwrap());
-    String::from_utf8(new_polymere).unwrap()
+    new_polymere.extend(elements.last());
+    Ok(String::from_utf8_lossy(&new_polymere).into_owned())
 }
 
 fn diff_most_least(polymere: &str) -> usize {
     let mut histo = HashMap::new();
-    polymere.bytes().into_iter().for_each(|x| {
-        if histo.contains_key(&x) {
-            *histo.get_mut(&x).unwrap() += 1;
+    polymere.bytes().for_each(|x| {
+        if let std::collections::hash_map::Entry::Vacant(e) = histo.entry(x) {
+            e.insert(1);
         } else {
-            histo.insert(x, 1);
+            *histo.get_mut(&x).unwrap() += 1;
         }
     });
     histo.values().max().unwrap() - histo.values().min().unwrap()
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let (mut polymere, rules) = read_input(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (mut polymere, rules) = read_input(lines)?;
     for _ in 0..10 {
-        polymere = calc_new_polymere(polymere, &rules);
+        polymere = calc_new_polymere(polymere, &rules)?;
     }
     let solution = diff_most_least(&polymere);
-    format!("{solution}")
+    Ok(format!("{solution}"))
 }
 
-fn read_as_vec(lines: io::Lines<io::BufReader<File>>) -> (Vec<u8>, HashMap<(u8, u8), u8>) {
-    let mut lines = lines.into_iter().filter_map(|x| x.ok());
-    let polymere = lines.next().unwrap().bytes().collect();
-    let rules: HashMap<(u8, u8), u8> = lines
-        .skip(1)
-        .map(|x| {
-            let s: Vec<String> = x.split(" -> ").into_iter().map(|x| x.to_owned()).collect();
-            let mut b0 = s[0].bytes();
-            (
-                (b0.next().unwrap(), b0.next().unwrap()),
-                s[1].bytes().next().unwrap(),
-            )
-        })
-        .collect();
-    (polymere, rules)
+type Rules = HashMap<(u8, u8), u8>;
+
+fn read_as_vec(mut lines: io::Lines<impl BufRead>) -> Result<(Vec<u8>, Rules)> {
+    let polymere = read_polymere(&mut lines)?.bytes().collect();
+    next_line(&mut lines)?;
+    let rules = parse_lines_after(lines, 2, |line| {
+        match parse_rule(line).map(|(p, e)| (p.as_bytes(), e.as_bytes()))? {
+            (&[a, b], &[c]) => Ok(((a, b), c)),
+            _ => bail!("rules need a pair and a single element"),
+        }
+    })?;
+    Ok((polymere, rules.into_iter().collect()))
 }
 
 pub fn init_score(polymere: &[u8]) -> HashMap<(u8, u8), usize> {
     let mut score = HashMap::new();
     for i in 0..polymere.len() - 1 {
         let key = (polymere[i], polymere[i + 1]);
-        if score.contains_key(&key) {
-            *score.get_mut(&key).unwrap() += 1;
+        if let std::collections::hash_map::Entry::Vacant(e) = score.entry(key) {
+            e.insert(1);
         } else {
-            score.insert(key, 1);
+            *score.get_mut(&key).unwrap() += 1;
         }
     }
     score
@@ -98,16 +110,15 @@ fn update_val(score: &mut HashMap<(u8, u8), usize>, key: (u8, u8), val: usize) {
 
 fn update_score(
     score: &HashMap<(u8, u8), usize>,
-    rules: &HashMap<(u8, u8), u8>,
-) -> HashMap<(u8, u8), usize> {
+    rules: &Rules,
+) -> Result<HashMap<(u8, u8), usize>> {
     let mut new_score = HashMap::new();
     for (key, val) in score {
-        let key1 = (key.0, rules[key]);
-        update_val(&mut new_score, key1, *val);
-        let key2 = (rules[key], key.1);
-        update_val(&mut new_score, key2, *val);
+        let insertion = *rules.get(key).ok_or_else(|| missing_rule(key.0, key.1))?;
+        update_val(&mut new_score, (key.0, insertion), *val);
+        update_val(&mut new_score, (insertion, key.1), *val);
     }
-    new_score
+    Ok(new_score)
 }
 
 fn update_count(count: &mut HashMap<u8, usize>, key: u8, val: usize) {
@@ -125,37 +136,17 @@ fn score_polymere(polymere: &[u8], score: &HashMap<(u8, u8), usize>) -> usize {
         update_count(&mut counts, key.1, *val);
     }
     *counts.get_mut(&polymere[0]).unwrap() += 1;
-    *counts.get_mut(&polymere.last().unwrap()).unwrap() += 1;
+    *counts.get_mut(polymere.last().unwrap()).unwrap() += 1;
 
     (counts.values().max().unwrap() - counts.values().min().unwrap()) / 2
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let (polymere, rules) = read_as_vec(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (polymere, rules) = read_as_vec(lines)?;
     let mut score = init_score(&polymere);
     for _ in 0..40 {
-        score = update_score(&score, &rules);
+        score = update_score(&score, &rules)?;
     }
     let solution = score_polymere(&polymere, &score);
-    format!("{solution}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_14_1() {
-        let lines = read_lines("data/2021/14.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "5656");
-    }
-
-    #[test]
-    fn test_2021_14_2() {
-        let lines = read_lines("data/2021/14.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "12271437788530");
-    }
+    Ok(format!("{solution}"))
 }
@@ -1,143 +1,58 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-struct Node {
-    dist: usize,
-    val: u8,
-}
-
-fn read_grid(lines: io::Lines<io::BufReader<File>>) -> Vec<Vec<Node>> {
-    let zero = "0".bytes().next().unwrap();
-
-    lines
-        .into_iter()
-        .filter_map(|x| x.ok())
-        .map(|x| {
-            x.bytes()
-                .map(|x| Node {
-                    dist: usize::MAX,
-                    val: x - zero,
-                })
-                .collect::<Vec<Node>>()
-        })
-        .collect()
-}
-
-fn update_cell(grid: &mut Vec<Vec<Node>>, x: usize, y: usize) -> bool {
-    let mut has_changed = false;
-    let my_dist = grid[y][x].dist;
-    if x > 0 {
-        if grid[y][x - 1].dist > grid[y][x - 1].val as usize + my_dist {
-            grid[y][x - 1].dist = grid[y][x - 1].val as usize + my_dist;
-            has_changed = true
-        }
-    }
-    if x + 1 < grid[0].len() {
-        if grid[y][x + 1].dist > grid[y][x + 1].val as usize + my_dist {
-            grid[y][x + 1].dist = grid[y][x + 1].val as usize + my_dist;
-            has_changed = true
-        }
-    }
-    if y > 0 {
-        if grid[y - 1][x].dist > grid[y - 1][x].val as usize + my_dist {
-            grid[y - 1][x].dist = grid[y - 1][x].val as usize + my_dist;
-            has_changed = true
-        }
-    }
-    if y + 1 < grid.len() {
-        if grid[y + 1][x].dist > grid[y + 1][x].val as usize + my_dist {
-            grid[y + 1][x].dist = grid[y + 1][x].val as usize + my_dist;
-            has_changed = true
-        }
-    }
-    has_changed
-}
+use crate::{search::dijkstra, AdventError, Grid, Position};
+use anyhow::{bail, Result};
 
-fn low_risk_path(grid: &mut Vec<Vec<Node>>) {
-    let xlen = grid[0].len();
+solution!(2021, 15, "Chiton", parse = read_grid);
 
-    grid[0][0].dist = 0;
-    let mut has_changed = true;
-    while has_changed {
-        has_changed = false;
-        for y in 0..grid.len() {
-            for x in 0..xlen {
-                has_changed |= update_cell(grid, x, y);
-            }
-        }
+fn read_grid(lines: io::Lines<impl BufRead>) -> Result<Grid<u8>> {
+    let grid = Grid::parse_digits(lines)?;
+    if grid.width() == 0 || grid.height() == 0 {
+        bail!(AdventError::invalid("empty grid"));
     }
+    Ok(grid)
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut grid = read_grid(lines);
-    low_risk_path(&mut grid);
-    format!("{}", grid.last().unwrap().last().unwrap().dist)
-}
-
-fn increas_val(grid: &mut Vec<Vec<Node>>) {
-    for v in grid.iter_mut() {
-        for n in v.iter_mut() {
-            if n.val == 9 {
-                n.val = 1;
-            } else {
-                n.val += 1;
-            }
-        }
-    }
-}
-
-fn enlarge_grid(grid: &mut Vec<Vec<Node>>) {
-    let mut tmp_grid = grid.clone();
-    let ylen = tmp_grid.len();
-    for d in 1..=8 {
-        increas_val(&mut tmp_grid);
-        let xxmin = if d > 4 { d - 4 } else { 0 };
-        for xx in xxmin..=d.min(4) {
-            if xx == 0 {
-                for v in tmp_grid.iter() {
-                    grid.push(v.clone())
-                }
-            } else {
-                let yy = (d - xx) * ylen;
-                for y in 0..tmp_grid.len() {
-                    grid[y + yy].extend(tmp_grid[y].iter())
-                }
-            }
-        }
-    }
-}
-
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut grid = read_grid(lines);
-    enlarge_grid(&mut grid);
-    low_risk_path(&mut grid);
-    format!("{}", grid.last().unwrap().last().unwrap().dist)
+fn low_risk_path(grid: &Grid<u8>) -> Result<usize> {
+    let end = Position::new(grid.width() as i32 - 1, grid.height() as i32 - 1);
+    let risk = dijkstra(
+        [(Position::new(0, 0), 0)],
+        |pos| {
+            grid.neighbours4(pos)
+                .map(|n| {
+                    let risk = grid[&n] as usize;
+                    (n, risk)
+                })
+                .collect()
+        },
+        |pos| *pos == end,
+    );
+    Ok(risk.ok_or(AdventError::NoSolution)?)
+}
+
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let grid = read_grid(lines)?;
+    Ok(format!("{}", low_risk_path(&grid)?))
+}
+
+/// The full map, made of 5x5 tiles of the input, with risks increased by
+/// the tile's distance to the top left one and wrapping around from 9 to 1
+fn enlarge_grid(grid: &Grid<u8>) -> Result<Grid<u8>> {
+    let rows = (0..5 * grid.height())
+        .map(|y| {
+            (0..5 * grid.width())
+                .map(|x| {
+                    let tile = x / grid.width() + y / grid.height();
+                    let pos = Position::new((x % grid.width()) as i32, (y % grid.height()) as i32);
+                    (grid[&pos] + tile as u8 - 1) % 9 + 1
+                })
+                .collect()
+        })
+        .collect();
+    Ok(Grid::from_rows(rows)?)
 }
 
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_15_1() {
-        let lines = read_lines("data/2021/15.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "441");
-    }
-
-    #[test]
-    fn test_2021_15_2() {
-        let lines = read_lines("data/2021/15.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "2849");
-    }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let grid = enlarge_grid(&read_grid(lines)?)?;
+    Ok(format!("{}", low_risk_path(&grid)?))
 }
@@ -1,20 +1,22 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
 use bit_range::BitRange;
 
-fn read_message(lines: io::Lines<io::BufReader<File>>) -> Vec<u8> {
-    let message = lines.into_iter().filter_map(|x| x.ok()).next().unwrap();
+use crate::{next_line, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2021, 16, "Packet Decoder", parse = read_message);
+
+fn read_message(mut lines: io::Lines<impl BufRead>) -> Result<Vec<u8>> {
+    let message = next_line(&mut lines)?;
     let message: Vec<char> = message.chars().collect();
     message
         .chunks(2)
-        .map(|x| u8::from_str_radix(&x.iter().collect::<String>(), 16).unwrap())
+        .enumerate()
+        .map(|(i, x)| {
+            u8::from_str_radix(&x.iter().collect::<String>(), 16)
+                .map_err(|_| AdventError::parse(1, 2 * i + 1, "expected hexadecimal digits").into())
+        })
         .collect()
 }
 
@@ -31,65 +33,68 @@ struct Package {
     value: PackageValue,
 }
 
-fn read_bits(msg: &[u8], p: &mut u32, bits: u32) -> u32 {
+fn read_bits(msg: &[u8], p: &mut u32, bits: u32) -> Result<u32> {
+    if (*p + bits) as usize > msg.len() * 8 {
+        bail!(AdventError::invalid("message ends within a package"));
+    }
     let val = msg.get_bit_range(*p..*p + bits);
     *p += bits;
-    val
+    Ok(val)
 }
 
-fn read_literal(msg: &[u8], p: &mut u32) -> usize {
+fn read_literal(msg: &[u8], p: &mut u32) -> Result<usize> {
     let mut num = 0;
     loop {
-        let last_bits = read_bits(msg, p, 1) == 0;
+        let last_bits = read_bits(msg, p, 1)? == 0;
         num <<= 4;
-        num += read_bits(msg, p, 4) as usize;
+        num += read_bits(msg, p, 4)? as usize;
         if last_bits {
             break;
         }
     }
-    num
+    Ok(num)
 }
 
-fn read_package_list(msg: &[u8], p: &mut u32) -> Vec<Package> {
-    let is_bit_size = read_bits(msg, p, 1) == 0;
+fn read_package_list(msg: &[u8], p: &mut u32) -> Result<Vec<Package>> {
+    let is_bit_size = read_bits(msg, p, 1)? == 0;
     let mut bit_size = 0;
     let mut num_packages = 0;
     if is_bit_size {
-        bit_size = read_bits(msg, p, 15) as u32;
+        bit_size = read_bits(msg, p, 15)?;
     } else {
-        num_packages = read_bits(msg, p, 11) as usize;
+        num_packages = read_bits(msg, p, 11)? as usize;
     }
 
     let mut packages = Vec::new();
     let start_pointer = *p;
     loop {
-        packages.push(parse_package(msg, p));
+        packages.push(parse_package(msg, p)?);
         if (is_bit_size && *p - start_pointer == bit_size)
             || (!is_bit_size && packages.len() == num_packages)
         {
             break;
         }
     }
-    packages
+    Ok(packages)
 }
 
-fn parse_package(msg: &[u8], p: &mut u32) -> Package {
-    let version = read_bits(msg, p, 3) as u8;
-    let operation = read_bits(msg, p, 3) as u8;
+fn parse_package(msg: &[u8], p: &mut u32) -> Result<Package> {
+    let version = read_bits(msg, p, 3)? as u8;
+    let operation = read_bits(msg, p, 3)? as u8;
     if operation == 4 {
-        let value = PackageValue::Number(read_literal(msg, p));
-        Package {
+        let value = PackageValue::Number(read_literal(msg, p)?);
+        Ok(Package {
             version,
             operation,
             value,
-        }
+        })
     } else {
-        let value = PackageValue::PackageList(read_package_list(msg, p));
-        Package {
+        let value = PackageValue::PackageList(read_package_list(msg, p)?);
+        Ok(Package {
             version,
             operation,
             value,
-        }
+        })
     }
 }
 
@@ -99,125 +104,53 @@ fn sum_versions(package: &Package) -> usize {
         PackageValue::Number(_) => version,
         PackageValue::PackageList(v) => {
             for p in v {
-                version += sum_versions(&p);
+                version += sum_versions(p);
             }
             version
         }
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let message = read_message(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let message = read_message(lines)?;
     let mut p = 0;
-    let package = parse_package(&message, &mut p);
+    let package = parse_package(&message, &mut p)?;
     let version_sum = sum_versions(&package);
-    format!("{version_sum}")
+    Ok(format!("{version_sum}"))
 }
 
-fn calc_value(package: &Package) -> usize {
-    match package.operation {
-        4 => match package.value {
-            PackageValue::Number(x) => x,
-            _ => panic!("Value expected!"),
-        },
-        0 => match &package.value {
-            PackageValue::Number(_) => panic!("Package list expected!"),
-            PackageValue::PackageList(v) => {
-                let mut value = 0;
-                for p in v {
-                    value += calc_value(&p);
-                }
-                value
-            }
-        },
-        1 => match &package.value {
-            PackageValue::Number(_) => panic!("Package list expected!"),
-            PackageValue::PackageList(v) => {
-                let mut value = 1;
-                for p in v {
-                    value *= calc_value(&p);
-                }
-                value
-            }
-        },
-        2 => match &package.value {
-            PackageValue::Number(_) => panic!("Package list expected!"),
-            PackageValue::PackageList(v) => {
-                let mut value = usize::MAX;
-                for p in v {
-                    value = value.min(calc_value(&p));
-                }
-                value
-            }
-        },
-        3 => match &package.value {
-            PackageValue::Number(_) => panic!("Package list expected!"),
-            PackageValue::PackageList(v) => {
-                let mut value = 0;
-                for p in v {
-                    value = value.max(calc_value(&p));
-                }
-                value
+fn calc_value(package: &Package) -> Result<usize> {
+    let v = match &package.value {
+        PackageValue::Number(x) => return Ok(*x),
+        PackageValue::PackageList(v) => v,
+    };
+    let values = v.iter().map(calc_value).collect::<Result<Vec<usize>>>()?;
+    let value = match package.operation {
+        0 => values.iter().sum(),
+        1 => values.iter().product(),
+        2 => values.iter().copied().min().unwrap_or(usize::MAX),
+        3 => values.iter().copied().max().unwrap_or(0),
+        _ => {
+            if values.len() != 2 {
+                bail!(AdventError::invalid(format!(
+                    "comparison needs 2 packages, found {}",
+                    values.len()
+                )));
             }
-        },
-        5 => match &package.value {
-            PackageValue::Number(_) => panic!("Package list expected!"),
-            PackageValue::PackageList(v) => {
-                if calc_value(&v[0]) > calc_value(&v[1]) {
-                    1
-                } else {
-                    0
-                }
+            match package.operation {
+                5 => (values[0] > values[1]) as usize,
+                6 => (values[0] < values[1]) as usize,
+                _ => (values[0] == values[1]) as usize,
             }
-        },
-        6 => match &package.value {
-            PackageValue::Number(_) => panic!("Package list expected!"),
-            PackageValue::PackageList(v) => {
-                if calc_value(&v[0]) < calc_value(&v[1]) {
-                    1
-                } else {
-                    0
-                }
-            }
-        },
-        7 => match &package.value {
-            PackageValue::Number(_) => panic!("Package list expected!"),
-            PackageValue::PackageList(v) => {
-                if calc_value(&v[0]) == calc_value(&v[1]) {
-                    1
-                } else {
-                    0
-                }
-            }
-        },
-        _ => panic!("Invalid opeation"),
-    }
+        }
+    };
+    Ok(value)
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let message = read_message(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let message = read_message(lines)?;
     let mut p = 0;
-    let package = parse_package(&message, &mut p);
-    let value = calc_value(&package);
-    format!("{value}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_16_1() {
-        let lines = read_lines("data/2021/16.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "945");
-    }
-
-    #[test]
-    fn test_2021_16_2() {
-        let lines = read_lines("data/2021/16.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "10637009915279");
-    }
+    let package = parse_package(&message, &mut p)?;
+    let value = calc_value(&package)?;
+    Ok(format!("{value}"))
 }
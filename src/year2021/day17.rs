@@ -1,26 +1,25 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
 use regex::Regex;
 
-fn read_parameter(lines: io::Lines<io::BufReader<File>>) -> ((i32, i32), (i32, i32)) {
-    let line = lines.into_iter().filter_map(|x| x.ok()).next().unwrap();
+use crate::{next_line, AdventError};
+use anyhow::Result;
+
+solution!(2021, 17, "Trick Shot", parse = read_parameter);
+
+fn read_parameter(mut lines: io::Lines<impl BufRead>) -> Result<((i32, i32), (i32, i32))> {
+    let line = next_line(&mut lines)?;
     let re = Regex::new(r"target area: x=([-0-9]*)..([-0-9]*), y=([-0-9]*)..([-0-9]*)").unwrap();
-    let numbers: Vec<i32> = re
-        .captures_iter(&line)
-        .next()
-        .unwrap()
+    let numbers = re
+        .captures(&line)
+        .ok_or_else(|| AdventError::at_line(0, "expected 'target area: x=..., y=...'"))?
         .iter()
         .skip(1)
-        .map(|x| x.unwrap().as_str().parse::<i32>().unwrap())
-        .collect();
-    ((numbers[0], numbers[1]), (numbers[2], numbers[3]))
+        .flatten()
+        .map(|x| x.as_str().parse::<i32>())
+        .collect::<Result<Vec<i32>, _>>()
+        .map_err(|err| AdventError::at_line(0, err.to_string()))?;
+    Ok(((numbers[0], numbers[1]), (numbers[2], numbers[3])))
 }
 
 fn simulate(start_vx: i32, start_vy: i32, target: ((i32, i32), (i32, i32))) -> i32 {
@@ -44,8 +43,8 @@ fn simulate(start_vx: i32, start_vy: i32, target: ((i32, i32), (i32, i32))) -> i
     target.1 .0 - 1
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let target = read_parameter(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let target = read_parameter(lines)?;
     let min_vx = (-0.5 + (2.0 * (target.0 .0 as f64) + 0.25)).sqrt().floor() as i32;
     let max_vx = target.0 .1;
     let mut height = 0;
@@ -54,11 +53,11 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
             height = height.max(simulate(start_vx, start_vy, target));
         }
     }
-    format!("{height}")
+    Ok(format!("{height}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let target = read_parameter(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let target = read_parameter(lines)?;
     let min_vx = (-0.5 + (2.0 * (target.0 .0 as f64) + 0.25)).sqrt().floor() as i32;
     let max_vx = target.0 .1;
     let mut count = 0;
@@ -69,25 +68,5 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
             }
         }
     }
-    format!("{count}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_17_1() {
-        let lines = read_lines("data/2021/17.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "5253");
-    }
-
-    #[test]
-    fn test_2021_17_2() {
-        let lines = read_lines("data/2021/17.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1770");
-    }
+    Ok(format!("{count}"))
 }
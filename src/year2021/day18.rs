@@ -1,29 +1,25 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
 use std::fmt;
 
-fn read_sfnumbers(lines: io::Lines<io::BufReader<File>>) -> Vec<SnailFish> {
-    let mut nums = Vec::new();
-    lines
-        .into_iter()
-        .filter_map(|x| x.ok())
-        .into_iter()
-        .for_each(|x| {
-            let mut num = Vec::new();
-            let (_, sf_idx) = parse_sfnumber(&(x.chars().collect::<Vec<char>>()), 0, &mut num);
-            nums.push(SnailFish {
-                v: num,
-                idx: sf_idx,
-            });
-        });
-    nums
+use crate::{parse_lines, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2021, 18, "Snailfish", parse = read_sfnumbers);
+
+fn read_sfnumbers(lines: io::Lines<impl BufRead>) -> Result<Vec<SnailFish>> {
+    let nums = parse_lines(lines, |x| {
+        let mut num = Vec::new();
+        let (_, sf_idx) = parse_sfnumber(&(x.chars().collect::<Vec<char>>()), 0, &mut num)?;
+        Ok(SnailFish {
+            v: num,
+            idx: sf_idx,
+        })
+    })?;
+    if nums.is_empty() {
+        bail!(AdventError::invalid("no snail fish numbers"));
+    }
+    Ok(nums)
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -231,7 +227,7 @@ impl SnailFish {
     }
 }
 
-fn parse_number(s: &[char], idx: usize, num: &mut Vec<SFNumber>) -> (usize, usize) {
+fn parse_number(s: &[char], idx: usize, num: &mut Vec<SFNumber>) -> Result<(usize, usize)> {
     let mut last = idx;
     for i in idx..s.len() {
         last = i;
@@ -243,40 +239,39 @@ fn parse_number(s: &[char], idx: usize, num: &mut Vec<SFNumber>) -> (usize, usiz
         .iter()
         .collect::<String>()
         .parse::<i32>()
-        .unwrap();
+        .map_err(|_| AdventError::parse(0, idx + 1, "expected number"))?;
     num.push(SFNumber::Number(inum));
-    (last, num.len() - 1)
+    Ok((last, num.len() - 1))
 }
 
-fn parse_pair(s: &[char], idx: usize, num: &mut Vec<SFNumber>) -> (usize, usize) {
-    let (idx, sf_idx1) = parse_sfnumber(s, idx, num);
-    let (idx, sf_idx2) = parse_sfnumber(s, idx, num);
+fn parse_pair(s: &[char], idx: usize, num: &mut Vec<SFNumber>) -> Result<(usize, usize)> {
+    let (idx, sf_idx1) = parse_sfnumber(s, idx, num)?;
+    let (idx, sf_idx2) = parse_sfnumber(s, idx, num)?;
     num.push(SFNumber::Pair(sf_idx1, sf_idx2));
-    (idx, num.len() - 1)
+    Ok((idx, num.len() - 1))
 }
-fn parse_sfnumber(s: &[char], idx: usize, num: &mut Vec<SFNumber>) -> (usize, usize) {
-    let (idx, sf_idx) = match s[idx] {
-        '[' => parse_pair(s, idx + 1, num),
-        '0'..='9' => parse_number(s, idx, num),
-        _ => {
-            panic!("Invalid snail fish number!")
-        }
+fn parse_sfnumber(s: &[char], idx: usize, num: &mut Vec<SFNumber>) -> Result<(usize, usize)> {
+    let (idx, sf_idx) = match s.get(idx) {
+        Some('[') => parse_pair(s, idx + 1, num)?,
+        Some('0'..='9') => parse_number(s, idx, num)?,
+        Some(c) => bail!(AdventError::parse(0, idx + 1, format!("unexpected '{c}'"))),
+        None => bail!(AdventError::parse(0, idx + 1, "unexpected end of line")),
     };
-    (idx + 1, sf_idx)
+    Ok((idx + 1, sf_idx))
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let nums = read_sfnumbers(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let nums = read_sfnumbers(lines)?;
     let mut num = nums[0].clone();
     for n in nums.into_iter().skip(1) {
         num.add(&n);
         num.reduce();
     }
-    format!("{}", num.magnitude(num.idx))
+    Ok(format!("{}", num.magnitude(num.idx)))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let nums = read_sfnumbers(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let nums = read_sfnumbers(lines)?;
     let mut max_magnitude = 0;
     let n = nums.len();
     for i in 0..n {
@@ -291,25 +286,5 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
             max_magnitude = max_magnitude.max(num2.magnitude(num2.idx));
         }
     }
-    format!("{max_magnitude}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_18_1() {
-        let lines = read_lines("data/2021/18.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "3981");
-    }
-
-    #[test]
-    fn test_2021_18_2() {
-        let lines = read_lines("data/2021/18.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "4687");
-    }
+    Ok(format!("{max_magnitude}"))
 }
@@ -1,13 +1,14 @@
 use anyhow::{anyhow, Result};
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::parse_lines;
+
+solution!(
+    2021,
+    2,
+    "Dive!",
+    parse = |lines| parse_lines(lines, Move::parse)
+);
 
 #[derive(Debug)]
 struct Position {
@@ -49,12 +50,8 @@ impl Move {
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let moves: Vec<Move> = lines
-        .into_iter()
-        .filter_map(|s| s.ok())
-        .filter_map(|s| Move::parse(&s).ok())
-        .collect();
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let moves = parse_lines(lines, Move::parse)?;
 
     let mut pos = Position {
         horizontal: 0,
@@ -68,15 +65,11 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
             Direction::Down => pos.depth += m.value,
         }
     }
-    format!("{}", pos.horizontal * pos.depth)
+    Ok(format!("{}", pos.horizontal * pos.depth))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let moves: Vec<Move> = lines
-        .into_iter()
-        .filter_map(|s| s.ok())
-        .filter_map(|s| Move::parse(&s).ok())
-        .collect();
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let moves = parse_lines(lines, Move::parse)?;
 
     let mut pos = Position {
         horizontal: 0,
@@ -93,25 +86,5 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
             Direction::Down => pos.aim += m.value,
         }
     }
-    format!("{}", pos.horizontal * pos.depth)
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_2_1() {
-        let lines = read_lines("data/2021/2.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "1855814");
-    }
-
-    #[test]
-    fn test_2021_2_2() {
-        let lines = read_lines("data/2021/2.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1845455714");
-    }
+    Ok(format!("{}", pos.horizontal * pos.depth))
 }
@@ -1,20 +1,24 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
 use std::collections::VecDeque;
 
-fn read_algo_and_image(lines: io::Lines<io::BufReader<File>>) -> (String, VecDeque<String>) {
-    let mut line_iter = lines.into_iter().filter_map(|x| x.ok());
-    let algo_string = line_iter.next().unwrap();
-    let image: VecDeque<String> = line_iter.skip(1).collect();
+use crate::{next_line, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2021, 20, "Trench Map", parse = read_algo_and_image);
 
-    (algo_string, image)
+fn read_algo_and_image(mut lines: io::Lines<impl BufRead>) -> Result<(String, VecDeque<String>)> {
+    let algo_string = next_line(&mut lines)?;
+    if algo_string.len() != 512 {
+        bail!(AdventError::at_line(
+            0,
+            "enhancement algorithm needs 512 pixels"
+        ));
+    }
+    next_line(&mut lines)?;
+    let image: VecDeque<String> = lines.collect::<Result<_, _>>()?;
+
+    Ok((algo_string, image))
 }
 
 fn extend_image(image: &mut VecDeque<String>, outer_space: char) {
@@ -34,7 +38,7 @@ fn enhance_image(image: &VecDeque<String>, algo: &str) -> VecDeque<String> {
         &algo[511..=511]
     };
     let n = image[0].len();
-    let outer_row: String = (0..n).into_iter().map(|_| outer_space).collect();
+    let outer_row: String = (0..n).map(|_| outer_space).collect();
     new_image.push_back(outer_row.clone());
     for i in 1..image.len() - 1 {
         let mut row = outer_space.to_string();
@@ -62,51 +66,30 @@ fn enhance_image(image: &VecDeque<String>, algo: &str) -> VecDeque<String> {
 pub fn count_lit_pixels(image: &VecDeque<String>) -> usize {
     image
         .iter()
-        .map(|x| x.chars())
-        .flatten()
+        .flat_map(|x| x.chars())
         .filter(|x| *x == '#')
         .count()
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let (algo, mut image) = read_algo_and_image(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (algo, mut image) = read_algo_and_image(lines)?;
     extend_image(&mut image, '.');
     extend_image(&mut image, '.');
     extend_image(&mut image, '.');
-    image = enhance_image(&mut image, &algo);
-    image = enhance_image(&mut image, &algo);
-    format!("{}", count_lit_pixels(&image))
+    image = enhance_image(&image, &algo);
+    image = enhance_image(&image, &algo);
+    Ok(format!("{}", count_lit_pixels(&image)))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let (algo, mut image) = read_algo_and_image(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (algo, mut image) = read_algo_and_image(lines)?;
     let mut outer = '.';
     extend_image(&mut image, outer);
     extend_image(&mut image, outer);
     for _ in 0..50 {
         extend_image(&mut image, outer);
-        image = enhance_image(&mut image, &algo);
+        image = enhance_image(&image, &algo);
         outer = image[0][0..=0].chars().next().unwrap();
     }
-    format!("{}", count_lit_pixels(&image))
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_20_1() {
-        let lines = read_lines("data/2021/20.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "5361");
-    }
-
-    #[test]
-    fn test_2021_20_2() {
-        let lines = read_lines("data/2021/20.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "16826");
-    }
+    Ok(format!("{}", count_lit_pixels(&image)))
 }
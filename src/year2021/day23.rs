@@ -1,55 +1,43 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
-use std::cmp::Ordering;
-use std::collections::HashMap;
+use crate::{memo::Memo, parse_lines, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2021, 23, "Amphipod", parse = |lines| read_game(lines, 2));
 
-fn read_game(lines: io::Lines<io::BufReader<File>>, n: usize) -> Vec<(u8, u8)> {
-    let map: Vec<Vec<char>> = lines
-        .into_iter()
-        .filter_map(|x| x.ok())
-        .skip(1)
-        .map(|x| x.chars().collect::<Vec<char>>())
-        .collect();
+/// Rows inserted into the diagram for the second part
+const UNFOLDED: [&str; 2] = ["  #D#C#B#A#", "  #D#B#A#C#"];
+
+fn read_game(lines: io::Lines<impl BufRead>, n: usize) -> Result<Vec<(u8, u8)>> {
+    let mut map = parse_lines(lines, |x| Ok(x.chars().collect::<Vec<char>>()))?;
+    // the second part unfolds the diagram, unless that is done already
+    if n == 4 && map.len() == 5 {
+        map.splice(3..3, UNFOLDED.iter().map(|row| row.chars().collect()));
+    }
     let mut positions = vec![(0, 0); 4 * n];
-    let mut counta = 0;
-    let mut countb = 0;
-    let mut countc = 0;
-    let mut countd = 0;
+    let mut counts = [0; 4];
     for i in 1..=n {
         for j in 0..4 {
             let col = j * 2 + 3;
-            match map[i][col] {
-                'A' => {
-                    positions[counta] = (col as u8, i as u8);
-                    counta += 1;
-                }
-                'B' => {
-                    positions[n + countb] = (col as u8, i as u8);
-                    countb += 1;
-                }
-                'C' => {
-                    positions[2 * n + countc] = (col as u8, i as u8);
-                    countc += 1;
-                }
-                'D' => {
-                    positions[3 * n + countd] = (col as u8, i as u8);
-                    countd += 1;
-                }
-                _ => panic!("Unexpected kind of Amphipod"),
+            let kind = match map.get(i + 1).and_then(|row| row.get(col)) {
+                Some(c @ 'A'..='D') => (*c as u8 - b'A') as usize,
+                _ => bail!(AdventError::parse(i + 2, col + 1, "expected amphipod")),
             };
+            if counts[kind] == n {
+                bail!(AdventError::parse(
+                    i + 2,
+                    col + 1,
+                    "too many amphipods of one kind"
+                ));
+            }
+            positions[kind * n + counts[kind]] = (col as u8, i as u8);
+            counts[kind] += 1;
         }
     }
-    positions
+    Ok(positions)
 }
 
-#[derive(Debug, Clone, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct Game {
     positions: Vec<(u8, u8)>,
 }
@@ -60,50 +48,9 @@ impl Game {
     }
 }
 
-impl PartialOrd for Game {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl PartialEq for Game {
-    fn eq(&self, other: &Self) -> bool {
-        for (i, p) in (&self.positions).iter().enumerate() {
-            if *p != other.positions[i] {
-                return false;
-            }
-        }
-        true
-    }
-}
-
-fn pair_cmp(i: (u8, u8), j: (u8, u8)) -> Ordering {
-    if i.0 == j.0 {
-        i.1.cmp(&j.1)
-    } else {
-        i.0.cmp(&j.0)
-    }
-}
-
-fn slice_cmp(first: &[(u8, u8)], second: &[(u8, u8)]) -> Ordering {
-    if first.len() != second.len() {
-        panic!("unequal length in slice compare");
-    } else if first.len() == 1 {
-        pair_cmp(first[0], second[0])
-    } else {
-        slice_cmp(&first[1..], &second[1..])
-    }
-}
-
-impl Ord for Game {
-    fn cmp(&self, other: &Self) -> Ordering {
-        slice_cmp(&self.positions, &other.positions)
-    }
-}
-
 fn is_finished(game: &Game) -> bool {
     let mut finished = true;
-    for (i, p) in (&game.positions).iter().enumerate() {
+    for (i, p) in game.positions.iter().enumerate() {
         if p.0 != get_target(i, game.n()) {
             finished = false;
             break;
@@ -113,7 +60,7 @@ fn is_finished(game: &Game) -> bool {
 }
 
 fn who_is_at(x: u8, y: u8, game: &Game) -> Option<usize> {
-    for (i, p) in (&game.positions).iter().enumerate() {
+    for (i, p) in game.positions.iter().enumerate() {
         if *p == (x, y) {
             return Some(i);
         }
@@ -123,7 +70,7 @@ fn who_is_at(x: u8, y: u8, game: &Game) -> Option<usize> {
 
 fn is_right(x: u8, y: u8, game: &Game) -> bool {
     let n = game.n();
-    let max_y = n as u8;
+    let max_y = n;
     if y > max_y {
         return true;
     }
@@ -131,11 +78,7 @@ fn is_right(x: u8, y: u8, game: &Game) -> bool {
         return false;
     }
     if let Some(i) = who_is_at(x, y, game) {
-        if game.positions[i].0 == get_target(i, n) && is_right(x, y + 1, game) {
-            true
-        } else {
-            false
-        }
+        game.positions[i].0 == get_target(i, n) && is_right(x, y + 1, game)
     } else {
         false
     }
@@ -172,12 +115,7 @@ fn move_out(i: usize, game: &Game) -> Vec<(Game, i32)> {
             } else {
                 energy(i, n) * (x - ax + ay) as i32
             };
-            moves.push((
-                Game {
-                    positions: positions,
-                },
-                move_energy,
-            ));
+            moves.push((Game { positions }, move_energy));
         }
     }
     moves
@@ -200,7 +138,7 @@ fn move_in(i: usize, game: &Game) -> Option<(Game, i32)> {
         (target..ax).rev().collect::<Vec<u8>>()
     };
     for x in walk {
-        if !who_is_at(x, 0, game).is_none() {
+        if who_is_at(x, 0, game).is_some() {
             return None;
         }
     }
@@ -216,13 +154,7 @@ fn move_in(i: usize, game: &Game) -> Option<(Game, i32)> {
         positions[i] = (target, new_y);
         return Some((
             Game { positions },
-            energy(i, n)
-                * (if target > ax {
-                    target - ax
-                } else {
-                    ax - target
-                } + new_y
-                    + ay) as i32,
+            energy(i, n) * (target.abs_diff(ax) + new_y + ay) as i32,
         ));
     }
     None
@@ -234,7 +166,7 @@ fn energy(i: usize, n: u8) -> i32 {
 
 fn get_list_of_moves(game: &Game) -> Vec<(Game, i32)> {
     let mut moves = Vec::new();
-    for (i, p) in (&game.positions).iter().enumerate() {
+    for (i, p) in game.positions.iter().enumerate() {
         let (ax, ay) = *p;
         if is_right(ax, ay, game) {
             continue;
@@ -257,92 +189,32 @@ fn get_list_of_moves(game: &Game) -> Vec<(Game, i32)> {
     moves
 }
 
-fn solve(game: &Game, total_energy: i32, solutions: &mut HashMap<Game, i32>) -> i32 {
+/// Least energy needed to organize the amphipods from `game` on, if they
+/// don't get stuck
+fn solve(memo: &mut Memo<Game, Option<i32>>, game: &Game) -> Option<i32> {
     if is_finished(game) {
-        solutions.insert(game.clone(), total_energy);
-        return total_energy;
-    }
-    if solutions.contains_key(game) {
-        return solutions[game];
-    }
-    let moves = get_list_of_moves(&game);
-    let mut min_energy = i32::MAX;
-    for m in &moves {
-        let curr_energy = solve(&m.0, m.1 + total_energy, solutions);
-        solutions.insert(m.0.clone(), curr_energy);
-        min_energy = min_energy.min(curr_energy);
-    }
-    min_energy
-}
-
-fn print_moves(moves: &Vec<(Game, i32)>) {
-    for m in moves {
-        let n = m.0.n();
-        for y in 0..=n {
-            for x in 0..13 {
-                if let Some(i) = who_is_at(x, y, &m.0) {
-                    if i < n as usize {
-                        print!("A");
-                    } else if i < 2 * n as usize {
-                        print!("B");
-                    } else if i < 3 * n as usize {
-                        print!("C");
-                    } else {
-                        print!("D");
-                    }
-                } else {
-                    if y > 0 && (x % 2) == 0 && x > 1 && x < 11 {
-                        print!("#")
-                    } else if y > 1 && (x < 2 || x > 10) {
-                        print!(" ");
-                    } else if y == 1 && (x < 2 || x > 10) {
-                        print!("#");
-                    } else if y == 0 && (x == 0 || x == 12) {
-                        print!("#");
-                    } else {
-                        print!(".");
-                    }
-                }
-            }
-            println!();
-        }
-        println!("  #########\n");
+        return Some(0);
     }
+    memo.get(game.clone(), |memo| {
+        get_list_of_moves(game)
+            .iter()
+            .filter_map(|(next, energy)| Some(energy + solve(memo, next)?))
+            .min()
+    })
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
     let game = Game {
-        positions: read_game(lines, 2),
+        positions: read_game(lines, 2)?,
     };
-    let mut solutions = HashMap::new();
-    format!("{}", solve(&game, 0, &mut solutions))
+    let energy = solve(&mut Memo::new(), &game).ok_or(AdventError::NoSolution)?;
+    Ok(format!("{energy}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
     let game = Game {
-        positions: read_game(lines, 4),
+        positions: read_game(lines, 4)?,
     };
-    print_moves(&vec![(game.clone(), 0)]);
-    let mut solutions = HashMap::new();
-    format!("{}", solve(&game, 0, &mut solutions))
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_23_1() {
-        let lines = read_lines("data/2021/23.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "13495");
-    }
-
-    #[test]
-    fn test_2021_23_2() {
-        let lines = read_lines("data/2021/23.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "53767");
-    }
+    let energy = solve(&mut Memo::new(), &game).ok_or(AdventError::NoSolution)?;
+    Ok(format!("{energy}"))
 }
@@ -1,20 +1,12 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
-use std::collections::HashMap;
-
-fn read_instructions(lines: io::Lines<io::BufReader<File>>) -> Vec<Instruction> {
-    lines
-        .into_iter()
-        .filter_map(|x| x.ok())
-        .map(|x| Instruction::from_str(&x))
-        .collect()
+use crate::{parse_lines, AdventError};
+use anyhow::{anyhow, bail, Result};
+
+solution!(2021, 24, "Arithmetic Logic Unit", parse = read_instructions);
+
+fn read_instructions(lines: io::Lines<impl BufRead>) -> Result<Vec<Instruction>> {
+    parse_lines(lines, Instruction::from_str)
 }
 
 #[derive(Debug)]
@@ -24,14 +16,14 @@ enum Arg {
 }
 
 impl Arg {
-    fn from_str(s: &str) -> Arg {
-        match s {
+    fn from_str(s: Option<&str>) -> Result<Arg> {
+        Ok(match s.ok_or_else(|| anyhow!("missing argument"))? {
             "w" => Arg::Register(0),
             "x" => Arg::Register(1),
             "y" => Arg::Register(2),
             "z" => Arg::Register(3),
-            _ => Arg::Number(s.parse::<i64>().unwrap()),
-        }
+            s => Arg::Number(s.parse::<i64>()?),
+        })
     }
 
     fn get_number(&self, registers: &[i64]) -> i64 {
@@ -53,24 +45,24 @@ enum Instruction {
 }
 
 impl Instruction {
-    fn from_str(s: &str) -> Instruction {
-        let tokens: Vec<String> = s.split(" ").map(|x| x.to_owned()).collect();
-        match tokens[0].as_str() {
-            "inp" => Instruction::Inp(Arg::from_str(&tokens[1])),
-            "add" => Instruction::Add(Arg::from_str(&tokens[1]), Arg::from_str(&tokens[2])),
-            "mul" => Instruction::Mul(Arg::from_str(&tokens[1]), Arg::from_str(&tokens[2])),
-            "div" => Instruction::Div(Arg::from_str(&tokens[1]), Arg::from_str(&tokens[2])),
-            "mod" => Instruction::Mod(Arg::from_str(&tokens[1]), Arg::from_str(&tokens[2])),
-            "eql" => Instruction::Eql(Arg::from_str(&tokens[1]), Arg::from_str(&tokens[2])),
-            _ => panic!("Unknown command found!"),
-        }
+    fn from_str(s: &str) -> Result<Instruction> {
+        let tokens: Vec<&str> = s.split(" ").collect();
+        let arg = |i: usize| Arg::from_str(tokens.get(i).copied());
+        Ok(match tokens[0] {
+            "inp" => Instruction::Inp(arg(1)?),
+            "add" => Instruction::Add(arg(1)?, arg(2)?),
+            "mul" => Instruction::Mul(arg(1)?, arg(2)?),
+            "div" => Instruction::Div(arg(1)?, arg(2)?),
+            "mod" => Instruction::Mod(arg(1)?, arg(2)?),
+            "eql" => Instruction::Eql(arg(1)?, arg(2)?),
+            cmd => bail!("unknown command '{cmd}'"),
+        })
     }
 }
 
-fn process_instructions(input: &[i64], z: i64, instructions: &[Instruction]) -> i64 {
+fn process_instructions(input: &[i64], instructions: &[Instruction]) -> i64 {
     let mut input_counter = 0;
     let mut registers = vec![0; 4];
-    registers[3] = z;
     for instruction in instructions {
         match instruction {
             Instruction::Inp(a) => {
@@ -116,99 +108,268 @@ fn process_instructions(input: &[i64], z: i64, instructions: &[Instruction]) ->
             }
         }
     }
-    return registers[3];
+    registers[3]
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let instructions = read_instructions(lines);
-    let mut start_indices = Vec::new();
-    for (idx, ins) in instructions.iter().enumerate() {
-        if let Instruction::Inp(_) = ins {
-            start_indices.push(idx);
-        }
-    }
-    start_indices.push(instructions.len());
-    let mut allowed_z = HashMap::new();
-    allowed_z.insert(0, vec![]);
-    for i in (0..start_indices.len() - 1).rev() {
-        let mut new_allowed_z = HashMap::new();
-        for z in 0..250000 {
-            for w in (1..=9).rev() {
-                let result = process_instructions(
-                    &[w],
-                    z,
-                    &instructions[start_indices[i]..start_indices[i + 1]],
-                );
-                if allowed_z.contains_key(&result) {
-                    let mut new_input = allowed_z[&result].clone();
-                    new_input.push(w);
-                    new_allowed_z.insert(z, new_input);
-                    break;
-                }
-            }
+/// Symbolic value of a register while interpreting the program for any
+/// model number
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Number(i64),
+    /// The input digit with the given index plus an offset
+    Digit(usize, i64),
+    /// A number in base 26, least significant digit last, each of them a
+    /// `Number` or `Digit` below 26
+    Stack(Vec<Value>),
+}
+
+impl Value {
+    /// Range of the values it can take, if it is a single number or digit
+    fn range(&self) -> Option<(i64, i64)> {
+        match self {
+            Value::Number(n) => Some((*n, *n)),
+            Value::Digit(_, k) => Some((1 + k, 9 + k)),
+            Value::Stack(_) => None,
         }
-        allowed_z = new_allowed_z;
     }
-    let mut solution = 0;
-    for d in allowed_z[&0].iter().rev() {
-        solution = solution * 10 + *d;
+
+    fn is_base_26_digit(&self) -> bool {
+        self.range().is_some_and(|(lo, hi)| lo >= 0 && hi < 26)
     }
-    format!("{}", solution)
-}
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let instructions = read_instructions(lines);
-    let mut start_indices = Vec::new();
-    for (idx, ins) in instructions.iter().enumerate() {
-        if let Instruction::Inp(_) = ins {
-            start_indices.push(idx);
+    fn is_zero(&self) -> bool {
+        match self {
+            Value::Number(n) => *n == 0,
+            Value::Digit(_, _) => false,
+            Value::Stack(s) => s.iter().all(Value::is_zero),
         }
     }
-    start_indices.push(instructions.len());
-    let mut allowed_z = HashMap::new();
-    allowed_z.insert(0, vec![]);
-    for i in (0..start_indices.len() - 1).rev() {
-        let mut new_allowed_z = HashMap::new();
-        for z in 0..160000 {
-            for w in 1..=9 {
-                let result = process_instructions(
-                    &[w],
-                    z,
-                    &instructions[start_indices[i]..start_indices[i + 1]],
-                );
-                if allowed_z.contains_key(&result) {
-                    let mut new_input = allowed_z[&result].clone();
-                    new_input.push(w);
-                    new_allowed_z.insert(z, new_input);
-                    break;
+}
+
+/// `w_right = w_left + diff` for the digits of a model number
+#[derive(Debug, PartialEq)]
+struct Constraint {
+    left: usize,
+    right: usize,
+    diff: i64,
+}
+
+/// Interprets the program on symbolic input digits, with `z` as a stack
+/// that blocks push a digit onto (`mul z 26` then `add z y`) or pop from
+/// (`div z 26`). Comparisons that can hold are assumed to, recording a
+/// constraint between the digits: otherwise the popping block would push
+/// again, and `z` only ends at 0 if every push is matched by a pop, which
+/// is checked at the end. Fails for programs not following this pattern.
+fn analyze(instructions: &[Instruction]) -> Result<(usize, Vec<Constraint>)> {
+    let mut registers = [
+        Value::Number(0),
+        Value::Number(0),
+        Value::Number(0),
+        Value::Stack(Vec::new()),
+    ];
+    let mut digits = 0;
+    let mut constraints = Vec::new();
+    for (idx, instruction) in instructions.iter().enumerate() {
+        let unsupported = || {
+            AdventError::at_line(
+                idx,
+                format!("{instruction:?} does not follow the stack pattern"),
+            )
+        };
+        let (a, b) = match instruction {
+            Instruction::Inp(Arg::Register(a)) => {
+                registers[*a as usize] = Value::Digit(digits, 0);
+                digits += 1;
+                continue;
+            }
+            Instruction::Add(Arg::Register(a), b)
+            | Instruction::Mul(Arg::Register(a), b)
+            | Instruction::Div(Arg::Register(a), b)
+            | Instruction::Mod(Arg::Register(a), b)
+            | Instruction::Eql(Arg::Register(a), b) => {
+                let b = match b {
+                    Arg::Number(n) => Value::Number(*n),
+                    Arg::Register(r) => registers[*r as usize].clone(),
+                };
+                (*a as usize, b)
+            }
+            _ => bail!(unsupported()),
+        };
+        let value = match (instruction, &registers[a], b) {
+            (Instruction::Add(..), Value::Number(x), Value::Number(y)) => Value::Number(x + y),
+            (Instruction::Add(..), Value::Number(0), y) => y,
+            (Instruction::Add(..), x, Value::Number(0)) => x.clone(),
+            (Instruction::Add(..), Value::Digit(i, k), Value::Number(y)) => Value::Digit(*i, k + y),
+            (Instruction::Add(..), Value::Stack(s), y) if y.is_base_26_digit() => {
+                let mut s = s.clone();
+                match s.last_mut() {
+                    Some(top) if top.is_zero() => *top = y,
+                    None => s.push(y),
+                    _ => bail!(unsupported()),
+                }
+                Value::Stack(s)
+            }
+            (Instruction::Mul(..), Value::Number(x), Value::Number(y)) => Value::Number(x * y),
+            (Instruction::Mul(..), _, Value::Number(0)) => Value::Number(0),
+            (Instruction::Mul(..), x, Value::Number(1)) => x.clone(),
+            (Instruction::Mul(..), Value::Stack(s), Value::Number(26)) => {
+                let mut s = s.clone();
+                s.push(Value::Number(0));
+                Value::Stack(s)
+            }
+            (Instruction::Div(..), x, Value::Number(1)) => x.clone(),
+            (Instruction::Div(..), Value::Number(x), Value::Number(y)) if y != 0 => {
+                Value::Number(x / y)
+            }
+            (Instruction::Div(..), Value::Stack(s), Value::Number(26)) => {
+                let mut s = s.clone();
+                s.pop();
+                Value::Stack(s)
+            }
+            (Instruction::Mod(..), Value::Number(x), Value::Number(y)) if *x >= 0 && y > 0 => {
+                Value::Number(x % y)
+            }
+            (Instruction::Mod(..), x, Value::Number(26)) if x.is_base_26_digit() => x.clone(),
+            (Instruction::Mod(..), Value::Stack(s), Value::Number(26)) => {
+                s.last().cloned().unwrap_or(Value::Number(0))
+            }
+            (Instruction::Eql(..), x, y) => {
+                let ((x_lo, x_hi), (y_lo, y_hi)) = match (x.range(), y.range()) {
+                    (Some(x), Some(y)) => (x, y),
+                    _ => bail!(unsupported()),
+                };
+                match (x, &y) {
+                    _ if x_hi < y_lo || y_hi < x_lo => Value::Number(0),
+                    (Value::Number(x), Value::Number(y)) => Value::Number((*x == *y) as i64),
+                    (Value::Digit(i, k), Value::Digit(j, m)) if i == j => {
+                        Value::Number((k == m) as i64)
+                    }
+                    (Value::Digit(i, k), Value::Digit(j, m)) => {
+                        let (left, right) = (*i.min(j), *i.max(j));
+                        let diff = if *i == left { k - m } else { m - k };
+                        constraints.push(Constraint { left, right, diff });
+                        Value::Number(1)
+                    }
+                    _ => bail!(unsupported()),
                 }
             }
+            _ => bail!(unsupported()),
+        };
+        registers[a] = value;
+    }
+    if !registers[3].is_zero() {
+        bail!(AdventError::invalid(
+            "program pushes more digits onto z than it pops"
+        ));
+    }
+    Ok((digits, constraints))
+}
+
+/// The largest or smallest model number satisfying the constraints of the
+/// program, checked by running it
+fn model_number(instructions: &[Instruction], largest: bool) -> Result<String> {
+    let (digits, constraints) = analyze(instructions)?;
+    let free = if largest { 9 } else { 1 };
+    let mut number = vec![None; digits];
+    for Constraint { left, right, diff } in constraints {
+        if let Some(twice) = [left, right].into_iter().find(|&d| number[d].is_some()) {
+            bail!(AdventError::invalid(format!(
+                "digit {} is compared more than once",
+                twice + 1
+            )));
         }
-        allowed_z = new_allowed_z;
+        // w_right = w_left + diff, with both in 1..=9
+        let w_left = if largest {
+            free.min(free - diff)
+        } else {
+            free.max(free - diff)
+        };
+        number[left] = Some(w_left);
+        number[right] = Some(w_left + diff);
     }
-    let mut solution = 0;
-    for d in allowed_z[&0].iter().rev() {
-        solution = solution * 10 + *d;
+    let number: Vec<i64> = number.into_iter().map(|d| d.unwrap_or(free)).collect();
+    if process_instructions(&number, instructions) != 0 {
+        bail!(AdventError::NoSolution);
     }
-    format!("{}", solution)
+    Ok(number.iter().map(|d| d.to_string()).collect())
+}
+
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let instructions = read_instructions(lines)?;
+    model_number(&instructions, true)
+}
+
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let instructions = read_instructions(lines)?;
+    model_number(&instructions, false)
 }
 
 #[cfg(test)]
 mod test {
-    use super::execute;
-    use crate::read_lines;
+    use super::{analyze, read_instructions, riddle_1, riddle_2, Constraint};
+    use std::io::BufRead;
+
+    /// Pushes the first digit plus 3, pops it and compares it with the
+    /// second digit plus 1, like the blocks of the puzzle input
+    const PUSH_POP: &str = "inp w
+mul x 0
+add x z
+mod x 26
+div z 1
+add x 12
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y 3
+mul y x
+add z y
+inp w
+mul x 0
+add x z
+mod x 26
+div z 26
+add x -2
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y 8
+mul y x
+add z y
+";
 
     #[test]
-    fn test_2021_24_1() {
-        let lines = read_lines("data/2021/24.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "98491959997994");
+    fn test_2021_24_push_pop() {
+        let instructions = read_instructions(PUSH_POP.as_bytes().lines()).unwrap();
+        let (digits, constraints) = analyze(&instructions).unwrap();
+        assert_eq!(digits, 2);
+        assert_eq!(
+            constraints,
+            vec![Constraint {
+                left: 0,
+                right: 1,
+                diff: 1
+            }]
+        );
+        assert_eq!(riddle_1(PUSH_POP.as_bytes().lines()).unwrap(), "89");
+        assert_eq!(riddle_2(PUSH_POP.as_bytes().lines()).unwrap(), "12");
     }
 
     #[test]
-    fn test_2021_24_2() {
-        let lines = read_lines("data/2021/24.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "61191516111321");
+    fn test_2021_24_not_a_stack() {
+        let program = "inp w\nadd z w\nmod z 2\ndiv w 2\n";
+        let error = riddle_1(program.as_bytes().lines()).unwrap_err();
+        assert!(error.to_string().contains("stack pattern"), "{error}");
     }
 }
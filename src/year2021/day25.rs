@@ -1,33 +1,32 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{
+    parse_lines,
+    visualize::{self, Rgb, BLACK, LAVA, WATER},
+};
+use anyhow::Result;
 
-fn read_map(lines: io::Lines<io::BufReader<File>>) -> Vec<Vec<u8>> {
-    lines
-        .into_iter()
-        .filter_map(|x| x.ok())
-        .map(|x| x.bytes().collect::<Vec<u8>>())
-        .collect()
+solution!(2021, 25, "Sea Cucumber", parse = read_map, part_1_only);
+
+fn read_map(lines: io::Lines<impl BufRead>) -> Result<Vec<Vec<u8>>> {
+    parse_lines(lines, |x| Ok(x.bytes().collect::<Vec<u8>>()))
 }
 
-fn print_map(map: &Vec<Vec<u8>>) {
-    for row in map {
-        println!("{}", String::from_utf8((*row).clone()).unwrap());
+fn colour(cell: &u8) -> Rgb {
+    match cell {
+        b'>' => LAVA,
+        b'v' => WATER,
+        _ => BLACK,
     }
 }
 
 // 'v' = 118
 // '>' = 62
 // '.' = 46
-fn one_step(map: &Vec<Vec<u8>>) -> (bool, Vec<Vec<u8>>) {
+fn one_step(map: &[Vec<u8>]) -> (bool, Vec<Vec<u8>>) {
     let rows = map.len();
     let cols = map[0].len();
-    let mut new_map = map.clone();
+    let mut new_map = map.to_owned();
     let mut has_changed = false;
     // Move '>'
     for i in 0..rows {
@@ -59,10 +58,11 @@ fn one_step(map: &Vec<Vec<u8>>) -> (bool, Vec<Vec<u8>>) {
     (has_changed, final_map)
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut map = read_map(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut map = read_map(lines)?;
     let mut count = 1;
     loop {
+        visualize::record("2021-25-1", map.iter(), colour);
         let (has_changed, new_map) = one_step(&map);
         if !has_changed {
             break;
@@ -70,19 +70,5 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
         count += 1;
         map = new_map;
     }
-    print_map(&map);
-    format!("{}", count)
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_25_1() {
-        let lines = read_lines("data/2021/25.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "295");
-    }
+    Ok(format!("{}", count))
 }
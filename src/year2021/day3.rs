@@ -1,22 +1,14 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{parse_lines, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2021, 3, "Binary Diagnostic", parse = read_numbers);
 
 const DIGITS: usize = 12;
 
-fn read_numbers(lines: io::Lines<io::BufReader<File>>) -> Vec<i32> {
-    let numbers: Vec<i32> = lines
-        .into_iter()
-        .filter_map(|s| s.ok())
-        .filter_map(|s| i32::from_str_radix(&s, 2).ok())
-        .collect();
-    numbers
+fn read_numbers(lines: io::Lines<impl BufRead>) -> Result<Vec<i32>> {
+    parse_lines(lines, |s| Ok(i32::from_str_radix(s, 2)?))
 }
 
 fn calc_counts(numbers: &[i32]) -> Vec<usize> {
@@ -33,8 +25,8 @@ fn calc_counts(numbers: &[i32]) -> Vec<usize> {
     counts
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let numbers = read_numbers(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let numbers = read_numbers(lines)?;
 
     let counts = calc_counts(&numbers);
 
@@ -51,25 +43,23 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
         } else if counts[DIGITS - b - 1] < half {
             epsilon += 1;
         } else {
-            panic!("Warning: undetermined!");
+            bail!("undetermined bit {}", DIGITS - b - 1);
         }
     }
-    format!("{}", gamma * epsilon)
+    Ok(format!("{}", gamma * epsilon))
 }
 
-fn filter_nums(numbers: &Vec<i32>, bit_criteria: bool) -> Option<i32> {
-    let mut numbers = numbers.clone();
+fn filter_nums(numbers: &[i32], bit_criteria: bool) -> Option<i32> {
+    let mut numbers = numbers.to_owned();
     for b in (0..DIGITS).rev() {
-        let bit = (1 << b) as i32;
+        let bit = 1 << b;
         let counts = calc_counts(&numbers);
         let half = (numbers.len() as f64) / 2.0;
-        let valid_bit = (if bit_criteria && (counts[b] as f64) >= half {
-            bit
-        } else if !bit_criteria && (counts[b] as f64) < half {
+        let valid_bit = if bit_criteria == ((counts[b] as f64) >= half) {
             bit
         } else {
             0
-        }) as i32;
+        };
         let new_numbers: Vec<i32> = numbers
             .into_iter()
             .filter(|x| x & bit == valid_bit)
@@ -82,30 +72,10 @@ fn filter_nums(numbers: &Vec<i32>, bit_criteria: bool) -> Option<i32> {
     None
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let numbers = read_numbers(lines);
-
-    let oxygen = filter_nums(&numbers, true).unwrap();
-    let carbon = filter_nums(&numbers, false).unwrap();
-    format!("{}", oxygen * carbon)
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let numbers = read_numbers(lines)?;
 
-    #[test]
-    fn test_2021_3_1() {
-        let lines = read_lines("data/2021/3.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "852500");
-    }
-
-    #[test]
-    fn test_2021_3_2() {
-        let lines = read_lines("data/2021/3.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1007985");
-    }
+    let oxygen = filter_nums(&numbers, true).ok_or(AdventError::NoSolution)?;
+    let carbon = filter_nums(&numbers, false).ok_or(AdventError::NoSolution)?;
+    Ok(format!("{}", oxygen * carbon))
 }
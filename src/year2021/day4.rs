@@ -1,18 +1,15 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
 use std::collections::HashSet;
 
+use crate::{next_line, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2021, 4, "Giant Squid", parse = read_bingo);
+
 fn split_in_numbers(s: String, delim: &str) -> Vec<i32> {
     let nums: Vec<i32> = s
         .split(delim)
-        .into_iter()
         .filter_map(|x| x.parse::<i32>().ok())
         .collect();
     nums
@@ -44,18 +41,22 @@ fn score_board(board: &[(i32, bool)]) -> i32 {
     board.iter().map(|x| if !x.1 { x.0 } else { 0 }).sum()
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut lines_iter = lines.into_iter();
-    let draws_string = lines_iter.next().unwrap().unwrap();
-    let draws: Vec<i32> = split_in_numbers(draws_string, ",");
-
-    let mut numbers: Vec<(i32, bool)> = lines_iter
-        .filter_map(|x| x.ok())
-        .filter(|s| !s.is_empty())
-        .map(|s| split_in_numbers(s, " ").into_iter())
-        .flatten()
-        .map(|s| (s, false))
-        .collect();
+type Boards = Vec<(i32, bool)>;
+
+fn read_bingo(mut lines: io::Lines<impl BufRead>) -> Result<(Vec<i32>, Boards)> {
+    let draws: Vec<i32> = split_in_numbers(next_line(&mut lines)?, ",");
+    let mut numbers = Vec::new();
+    for line in lines {
+        numbers.extend(split_in_numbers(line?, " ").into_iter().map(|s| (s, false)));
+    }
+    if !numbers.len().is_multiple_of(25) {
+        bail!(AdventError::invalid("boards must have 5x5 numbers"));
+    }
+    Ok((draws, numbers))
+}
+
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (draws, mut numbers) = read_bingo(lines)?;
 
     let mut solutions = Vec::new();
     let num_boards = numbers.len() / 25;
@@ -76,21 +77,12 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
         }
     }
 
-    format!("{:?}", solutions.last().unwrap().1)
+    let (_, score) = solutions.last().ok_or(AdventError::NoSolution)?;
+    Ok(format!("{score}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut lines_iter = lines.into_iter();
-    let draws_string = lines_iter.next().unwrap().unwrap();
-    let draws: Vec<i32> = split_in_numbers(draws_string, ",");
-
-    let mut numbers: Vec<(i32, bool)> = lines_iter
-        .filter_map(|x| x.ok())
-        .filter(|s| !s.is_empty())
-        .map(|s| split_in_numbers(s, " ").into_iter())
-        .flatten()
-        .map(|s| (s, false))
-        .collect();
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (draws, mut numbers) = read_bingo(lines)?;
 
     let mut solutions = Vec::new();
     let num_boards = numbers.len() / 25;
@@ -113,25 +105,6 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
         }
     }
 
-    format!("{:?}", solutions.last().unwrap().2)
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_4_1() {
-        let lines = read_lines("data/2021/4.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "5685");
-    }
-
-    #[test]
-    fn test_2021_4_2() {
-        let lines = read_lines("data/2021/4.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "21070");
-    }
+    let (_, _, score) = solutions.last().ok_or(AdventError::NoSolution)?;
+    Ok(format!("{score}"))
 }
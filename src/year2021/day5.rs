@@ -1,43 +1,28 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
 use std::cmp::{max, min};
 
+use crate::parse_lines;
+use anyhow::{Context, Result};
+
+solution!(2021, 5, "Hydrothermal Venture", parse = read_data);
+
 type Point = (i32, i32);
 type Pair = (Point, Point);
 
-fn get_point(s: &str) -> Point {
-    let mut points = s.split(",");
-    (
-        points.next().unwrap().parse::<i32>().unwrap(),
-        points.next().unwrap().parse::<i32>().unwrap(),
-    )
+fn get_point(s: &str) -> Result<Point> {
+    let (x, y) = s.split_once(",").context("expected point as x,y")?;
+    Ok((x.parse()?, y.parse()?))
 }
 
-fn read_data(lines: io::Lines<io::BufReader<File>>) -> Vec<Pair> {
-    let vent_lines: Vec<((i32, i32), (i32, i32))> = lines
-        .into_iter()
-        .filter_map(|x| x.ok())
-        .filter(|x| !x.is_empty())
-        .map(|s| {
-            let mut p_list = s.split(" -> ");
-            (
-                get_point(&p_list.next().unwrap()),
-                get_point(&p_list.next().unwrap()),
-            )
-        })
-        .collect();
-
-    vent_lines
+fn read_data(lines: io::Lines<impl BufRead>) -> Result<Vec<Pair>> {
+    parse_lines(lines, |s| {
+        let (from, to) = s.split_once(" -> ").context("expected two points")?;
+        Ok((get_point(from)?, get_point(to)?))
+    })
 }
 
-fn calc_max_xy(vents: &Vec<Pair>) -> (usize, usize) {
+fn calc_max_xy(vents: &[Pair]) -> (usize, usize) {
     let mut max_x = 0;
     let mut max_y = 0;
     vents.iter().for_each(|x| {
@@ -47,7 +32,7 @@ fn calc_max_xy(vents: &Vec<Pair>) -> (usize, usize) {
     ((max_x + 1) as usize, (max_y + 1) as usize)
 }
 
-fn mark_grid(grid: &mut Vec<i32>, pair: &Pair, max_x: usize) {
+fn mark_grid(grid: &mut [i32], pair: &Pair, max_x: usize) {
     if pair.0 .0 == pair.1 .0 {
         let start = min(pair.0 .1, pair.1 .1);
         let end = max(pair.0 .1, pair.1 .1);
@@ -78,21 +63,8 @@ fn mark_grid(grid: &mut Vec<i32>, pair: &Pair, max_x: usize) {
     }
 }
 
-fn print_grid(grid: &Vec<i32>, max_x: usize) {
-    let max_y = grid.len() / max_x;
-    for y in 0..max_y {
-        for x in 0..max_x {
-            match grid[(y as usize) * max_x + x as usize] {
-                0 => print!("."),
-                x => print!("{}", x),
-            }
-        }
-        println!();
-    }
-}
-
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let vent_lines = read_data(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let vent_lines = read_data(lines)?;
     let (max_x, max_y) = calc_max_xy(&vent_lines);
     let mut grid = vec![0; max_x * max_y];
     vent_lines
@@ -106,11 +78,11 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
             sum += 1
         }
     });
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let vent_lines = read_data(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let vent_lines = read_data(lines)?;
     let (max_x, max_y) = calc_max_xy(&vent_lines);
     let mut grid = vec![0; max_x * max_y];
     vent_lines
@@ -123,26 +95,5 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
             sum += 1
         }
     });
-    print_grid(&grid, max_x);
-    format!("{sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_5_1() {
-        let lines = read_lines("data/2021/5.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "6283");
-    }
-
-    #[test]
-    fn test_2021_5_2() {
-        let lines = read_lines("data/2021/5.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "18864");
-    }
+    Ok(format!("{sum}"))
 }
@@ -1,31 +1,31 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{next_line, AdventError};
+use anyhow::Result;
+
+solution!(2021, 6, "Lanternfish", parse = read_numbers);
 
-fn read_numbers(lines: io::Lines<io::BufReader<File>>) -> Vec<i32> {
-    let number_str = lines.into_iter().next().unwrap().unwrap();
-    number_str
+fn read_numbers(mut lines: io::Lines<impl BufRead>) -> Result<Vec<i32>> {
+    let number_str = next_line(&mut lines)?;
+    Ok(number_str
         .split(",")
-        .into_iter()
-        .filter_map(|s| s.parse::<i32>().ok())
-        .collect()
+        .map(|s| s.parse::<i32>())
+        .collect::<Result<_, _>>()?)
 }
 
-fn collect_in_histo(numbers: &[i32]) -> Vec<usize> {
+fn collect_in_histo(numbers: &[i32]) -> Result<Vec<usize>> {
     let mut histo: Vec<usize> = vec![0; 9];
-    numbers.into_iter().for_each(|n| histo[*n as usize] += 1);
-    histo
+    for n in numbers {
+        *histo
+            .get_mut(*n as usize)
+            .ok_or_else(|| AdventError::invalid(format!("invalid timer {n}")))? += 1;
+    }
+    Ok(histo)
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let numbers = read_numbers(lines);
-    let mut hist = collect_in_histo(&numbers);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let numbers = read_numbers(lines)?;
+    let mut hist = collect_in_histo(&numbers)?;
     for _ in 0..80 {
         let new_fish = hist[0];
         for j in 1..9 {
@@ -35,12 +35,12 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
         hist[6] += new_fish;
     }
     let total_sum: usize = hist.into_iter().sum();
-    format!("{total_sum}")
+    Ok(format!("{total_sum}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let numbers = read_numbers(lines);
-    let mut hist = collect_in_histo(&numbers);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let numbers = read_numbers(lines)?;
+    let mut hist = collect_in_histo(&numbers)?;
     for _ in 0..256 {
         let new_fish = hist[0];
         for j in 1..9 {
@@ -50,25 +50,5 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
         hist[6] += new_fish;
     }
     let total_sum: usize = hist.into_iter().sum();
-    format!("{total_sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_6_1() {
-        let lines = read_lines("data/2021/6.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "362639");
-    }
-
-    #[test]
-    fn test_2021_6_2() {
-        let lines = read_lines("data/2021/6.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1639854996917");
-    }
+    Ok(format!("{total_sum}"))
 }
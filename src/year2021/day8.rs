@@ -1,49 +1,32 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
 use std::collections::{HashMap, HashSet};
 
-fn read_notes(lines: io::Lines<io::BufReader<File>>) -> Vec<(Vec<String>, Vec<String>)> {
-    lines
-        .into_iter()
-        .filter_map(|l| l.ok())
-        .map(|l| {
-            l.split(" | ")
-                .into_iter()
-                .map(|x| x.to_owned())
-                .collect::<Vec<String>>()
-        })
-        .filter(|x| x.len() == 2)
-        .map(|x| {
-            (
-                x[0].split(" ")
-                    .into_iter()
-                    .map(|x| x.to_owned())
-                    .collect::<Vec<String>>(),
-                x[1].split(" ")
-                    .into_iter()
-                    .map(|x| x.to_owned())
-                    .collect::<Vec<String>>(),
-            )
-        })
-        .collect()
+use crate::parse_lines;
+use anyhow::{bail, Context, Result};
+
+solution!(2021, 8, "Seven Segment Search", parse = read_notes);
+
+type Note = (Vec<String>, Vec<String>);
+
+fn read_notes(lines: io::Lines<impl BufRead>) -> Result<Vec<Note>> {
+    parse_lines(lines, |l| {
+        let (patterns, output) = l.split_once(" | ").context("missing separator '|'")?;
+        Ok((
+            patterns.split(" ").map(|x| x.to_owned()).collect(),
+            output.split(" ").map(|x| x.to_owned()).collect(),
+        ))
+    })
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let notes = read_notes(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let notes = read_notes(lines)?;
     let count = notes
         .into_iter()
-        .map(|x| x.1.into_iter())
-        .flatten()
+        .flat_map(|x| x.1.into_iter())
         .filter(|x| x.len() == 2 || x.len() == 3 || x.len() == 4 || x.len() == 7)
         .count();
-    format!("{count}")
+    Ok(format!("{count}"))
 }
 
 fn map_phase2(map: &mut HashMap<String, i32>, x: &str) {
@@ -55,17 +38,13 @@ fn map_phase2(map: &mut HashMap<String, i32>, x: &str) {
     let mut new_map = HashMap::new();
     if x.len() == 6 {
         let mut found = false;
-        if rev_map.contains_key(&4) {
-            if contains_x(&rev_map[&4], x) == 4 {
-                new_map.insert(x.to_owned(), 9);
-                found = true;
-            }
+        if rev_map.contains_key(&4) && contains_x(&rev_map[&4], x) == 4 {
+            new_map.insert(x.to_owned(), 9);
+            found = true;
         }
-        if !found && rev_map.contains_key(&1) {
-            if contains_x(&rev_map[&1], x) == 2 {
-                new_map.insert(x.to_owned(), 0);
-                found = true;
-            }
+        if !found && rev_map.contains_key(&1) && contains_x(&rev_map[&1], x) == 2 {
+            new_map.insert(x.to_owned(), 0);
+            found = true;
         }
         if !found {
             new_map.insert(x.to_owned(), 6);
@@ -73,24 +52,20 @@ fn map_phase2(map: &mut HashMap<String, i32>, x: &str) {
     }
     if x.len() == 5 {
         let mut found = false;
-        if rev_map.contains_key(&1) {
-            if contains_x(&rev_map[&1], x) == 2 {
-                new_map.insert(x.to_owned(), 3);
-                found = true;
-            }
+        if rev_map.contains_key(&1) && contains_x(&rev_map[&1], x) == 2 {
+            new_map.insert(x.to_owned(), 3);
+            found = true;
         }
-        if rev_map.contains_key(&4) {
-            if contains_x(&rev_map[&4], x) == 2 {
-                new_map.insert(x.to_owned(), 2);
-                found = true;
-            }
+        if rev_map.contains_key(&4) && contains_x(&rev_map[&4], x) == 2 {
+            new_map.insert(x.to_owned(), 2);
+            found = true;
         }
         if !found {
             new_map.insert(x.to_owned(), 5);
         }
     }
 
-    map.extend(new_map.into_iter().map(|(k, v)| (k.clone(), v.clone())));
+    map.extend(new_map.into_iter().map(|(k, v)| (k.clone(), v)));
 }
 
 fn contains_x(x: &str, y: &str) -> i32 {
@@ -105,11 +80,11 @@ fn contains_x(x: &str, y: &str) -> i32 {
 
 fn sorted_string(s: String) -> String {
     let mut chars: Vec<char> = s.chars().collect();
-    chars.sort_by(|a, b| a.cmp(b));
+    chars.sort();
     String::from_iter(chars)
 }
 
-fn find_mapping(note: &(Vec<String>, Vec<String>)) -> HashMap<String, i32> {
+fn find_mapping(note: &Note) -> Result<HashMap<String, i32>> {
     let strings: HashSet<String> = note
         .0
         .iter()
@@ -130,41 +105,23 @@ fn find_mapping(note: &(Vec<String>, Vec<String>)) -> HashMap<String, i32> {
 
     strings.iter().for_each(|x| map_phase2(&mut mapping, x));
 
-    assert_eq!(mapping.len(), 10);
-    mapping
+    if mapping.len() != 10 {
+        bail!("found {} instead of 10 distinct digits", mapping.len());
+    }
+    Ok(mapping)
 }
 
-fn analyze_note(note: (Vec<String>, Vec<String>)) -> i32 {
-    let mapping = find_mapping(&note);
+fn analyze_note(note: Note) -> Result<i32> {
+    let mapping = find_mapping(&note)?;
     let mut num = 0;
     for s in note.1 {
         num = 10 * num + mapping[&sorted_string(s)];
     }
-    num
+    Ok(num)
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let notes = read_notes(lines);
-    let sum: i32 = notes.into_iter().map(|x| analyze_note(x)).sum();
-    format!("{sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_8_1() {
-        let lines = read_lines("data/2021/8.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "532");
-    }
-
-    #[test]
-    fn test_2021_8_2() {
-        let lines = read_lines("data/2021/8.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1011284");
-    }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let notes = read_notes(lines)?;
+    let sum = notes.into_iter().map(analyze_note).sum::<Result<i32>>()?;
+    Ok(format!("{sum}"))
 }
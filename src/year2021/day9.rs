@@ -1,107 +1,47 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{Grid, Position};
+use anyhow::Result;
 
-fn read_map(lines: io::Lines<io::BufReader<File>>) -> Vec<Vec<u8>> {
-    lines
-        .into_iter()
-        .filter_map(|l| l.ok())
-        .map(|l| {
-            l.bytes()
-                .into_iter()
-                .map(|b| b - { "0".bytes().into_iter().next() }.unwrap())
-                .collect::<Vec<u8>>()
-        })
-        .collect()
+solution!(2021, 9, "Smoke Basin", parse = read_map);
+
+fn read_map(lines: io::Lines<impl BufRead>) -> Result<Grid<u8>> {
+    Grid::parse_digits(lines)
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let map = read_map(lines);
-    let nx = map.len();
-    let ny = map[0].len();
-    let mut sum = 0;
-    for x in 0..nx {
-        for y in 0..ny {
-            let height = map[x][y];
-            if x > 0 && map[x - 1][y] <= height {
-                continue;
-            }
-            if y > 0 && map[x][y - 1] <= height {
-                continue;
-            }
-            if x + 1 < nx && map[x + 1][y] <= height {
-                continue;
-            }
-            if y + 1 < ny && map[x][y + 1] <= height {
-                continue;
-            }
-            sum += height as i32 + 1;
-        }
-    }
-    format!("{sum}")
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let map = read_map(lines)?;
+    let sum: i32 = map
+        .iter()
+        .filter(|(pos, height)| map.neighbours4(pos).all(|n| map[&n] > **height))
+        .map(|(_, height)| *height as i32 + 1)
+        .sum();
+    Ok(format!("{sum}"))
 }
 
-fn calc_bassin(map: &mut Vec<Vec<u8>>, x: usize, y: usize) -> usize {
-    if map[x][y] == 9 {
+fn calc_bassin(map: &mut Grid<u8>, pos: &Position) -> usize {
+    if map[pos] == 9 {
         return 0;
     }
     let mut count = 1;
-    map[x][y] = 9;
-    if x > 0 {
-        count += calc_bassin(map, x - 1, y)
-    }
-    if y > 0 {
-        count += calc_bassin(map, x, y - 1)
-    }
-    if x + 1 < map.len() {
-        count += calc_bassin(map, x + 1, y)
-    }
-    if y + 1 < map[0].len() {
-        count += calc_bassin(map, x, y + 1)
+    map[pos] = 9;
+    let neighbours: Vec<Position> = map.neighbours4(pos).collect();
+    for n in neighbours {
+        count += calc_bassin(map, &n);
     }
     count
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut map = read_map(lines);
-    let nx = map.len();
-    let ny = map[0].len();
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut map = read_map(lines)?;
     let mut bassins = Vec::new();
-    for x in 0..nx {
-        for y in 0..ny {
-            let bassin = calc_bassin(&mut map, x, y);
-            if bassin > 0 {
-                bassins.push(bassin);
-            }
+    for pos in map.positions().collect::<Vec<_>>() {
+        let bassin = calc_bassin(&mut map, &pos);
+        if bassin > 0 {
+            bassins.push(bassin);
         }
     }
 
     bassins.sort_by(|a, b| b.cmp(a));
-    format!("{}", bassins[0] * bassins[1] * bassins[2])
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2021_9_1() {
-        let lines = read_lines("data/2021/9.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "591");
-    }
-
-    #[test]
-    fn test_2021_9_2() {
-        let lines = read_lines("data/2021/9.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1113424");
-    }
+    Ok(format!("{}", bassins[0] * bassins[1] * bassins[2]))
 }
@@ -1,19 +1,20 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
+use crate::parse_lines;
+use anyhow::Result;
+
+solution!(2022, 1, "Calorie Counting", parse = read_calories);
+
+/// Reads the calories of all items, an empty line separating two elves is read as 0
+fn read_calories(lines: io::Lines<impl BufRead>) -> Result<Vec<i32>> {
+    parse_lines(lines, |s| match s {
+        "" => Ok(0),
+        _ => Ok(s.parse::<i32>()?),
+    })
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let numbers: Vec<i32> = lines
-        .into_iter()
-        .filter_map(|s| s.ok())
-        .map(|s| s.parse::<i32>().unwrap_or(0))
-        .collect();
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let numbers = read_calories(lines)?;
 
     let mut max = 0;
     let mut current = 0;
@@ -25,15 +26,11 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
             current += n;
         }
     }
-    format!("{max}")
+    Ok(format!("{max}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let numbers: Vec<i32> = lines
-        .into_iter()
-        .filter_map(|s| s.ok())
-        .map(|s| s.parse::<i32>().unwrap_or(0))
-        .collect();
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let numbers = read_calories(lines)?;
 
     let mut first = 0;
     let mut second = 0;
@@ -58,25 +55,5 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
             current += n;
         }
     }
-    format!("{}", first + second + third)
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_1_1() {
-        let lines = read_lines("data/2022/1.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "69912");
-    }
-
-    #[test]
-    fn test_2022_1_2() {
-        let lines = read_lines("data/2022/1.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "208180");
-    }
+    Ok(format!("{}", first + second + third))
 }
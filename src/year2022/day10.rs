@@ -1,12 +1,9 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{ocr::read_letters, parse_lines};
+use anyhow::{bail, Result};
+
+solution!(2022, 10, "Cathode-Ray Tube", parse = parse_commands);
 
 #[derive(Debug)]
 enum Command {
@@ -15,12 +12,11 @@ enum Command {
 }
 
 impl Command {
-    fn parse(s: String) -> Self {
-        let mut parts = s.split(" ");
-        if parts.next().unwrap() == "noop" {
-            Command::Noop
-        } else {
-            Command::Addx(parts.next().unwrap().parse().unwrap())
+    fn parse(s: &str) -> Result<Self> {
+        match s.split_once(" ") {
+            None if s == "noop" => Ok(Command::Noop),
+            Some(("addx", value)) => Ok(Command::Addx(value.parse()?)),
+            _ => bail!("unknown command '{s}'"),
         }
     }
 
@@ -91,52 +87,18 @@ fn draw_output(vc: &Vec<Command>) -> String {
     output
 }
 
-fn parse_commands(lines: io::Lines<io::BufReader<File>>) -> Vec<Command> {
-    lines
-        .into_iter()
-        .filter_map(|s| s.ok())
-        .map(Command::parse)
-        .collect()
+fn parse_commands(lines: io::Lines<impl BufRead>) -> Result<Vec<Command>> {
+    parse_lines(lines, Command::parse)
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let commands = parse_commands(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let commands = parse_commands(lines)?;
     let signal = process(&commands, vec![20, 60, 100, 140, 180, 220]);
-    format!("{signal}")
+    Ok(format!("{signal}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let commands = parse_commands(lines);
-    let output = draw_output(&commands);
-    output
-}
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let commands = parse_commands(lines)?;
 
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_10_1() {
-        let lines = read_lines("data/2022/10.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "13440");
-    }
-
-    #[test]
-    fn test_2022_10_2() {
-        let lines = read_lines("data/2022/10.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(
-            result,
-            r#"
-###..###..####..##..###...##..####..##..
-#..#.#..#....#.#..#.#..#.#..#....#.#..#.
-#..#.###....#..#....#..#.#..#...#..#..#.
-###..#..#..#...#.##.###..####..#...####.
-#....#..#.#....#..#.#.#..#..#.#....#..#.
-#....###..####..###.#..#.#..#.####.#..#.
-"#
-        );
-    }
+    Ok(read_letters(&draw_output(&commands))?)
 }
@@ -1,12 +1,8 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use anyhow::Result;
+
+solution!(2022, 11, "Monkey in the Middle");
 
 #[derive(Debug)]
 struct Monkey {
@@ -113,7 +109,7 @@ fn process_monkeys(m: &mut Vec<Monkey>) -> usize {
             let iffalse = m[i].false_monkey;
             for item in m[i].items.clone() {
                 let op = ((m[i].a2 * item + m[i].a1) * item + m[i].a0) / 3;
-                if op % m[i].divisible == 0 {
+                if op.is_multiple_of(m[i].divisible) {
                     m[iftrue].items.push(op);
                 } else {
                     m[iffalse].items.push(op);
@@ -147,7 +143,7 @@ fn process_monkeys_rule2(m: &mut Vec<Monkey>) -> usize {
             let iffalse = m[i].false_monkey;
             for item in m[i].items.clone() {
                 let op = ((m[i].a2 * item + m[i].a1) * item + m[i].a0) % total_divisibility;
-                if op % m[i].divisible == 0 {
+                if op.is_multiple_of(m[i].divisible) {
                     m[iftrue].items.push(op);
                 } else {
                     m[iffalse].items.push(op);
@@ -170,34 +166,14 @@ fn process_monkeys_rule2(m: &mut Vec<Monkey>) -> usize {
     max * max2
 }
 
-pub fn riddle_1(_lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_1(_lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut monkeys = init_monkeys();
     let score = process_monkeys(&mut monkeys);
-    format!("{score}")
+    Ok(format!("{score}"))
 }
 
-pub fn riddle_2(_lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_2(_lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut monkeys = init_monkeys();
     let score = process_monkeys_rule2(&mut monkeys);
-    format!("{score}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_11_1() {
-        let lines = read_lines("data/2022/11.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "78678");
-    }
-
-    #[test]
-    fn test_2022_11_2() {
-        let lines = read_lines("data/2022/11.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "15333249714");
-    }
+    Ok(format!("{score}"))
 }
@@ -1,17 +1,14 @@
 use crate::year2022::list::List;
 use std::cmp::Ordering;
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
 use lrlex::lrlex_mod;
 use lrpar::lrpar_mod;
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::parse_lines;
+use anyhow::{bail, Result};
+
+solution!(2022, 13, "Distress Signal", parse = parse_list);
 
 lrlex_mod!("year2022/list.l");
 lrpar_mod!("year2022/list.y");
@@ -19,24 +16,24 @@ lrpar_mod!("year2022/list.y");
 // I know, this is approach is far to complex for this little riddle,
 // but I thought it would be fun to experiment with some advanced
 // rust parser crate.. And I was right!
-fn parse_list(lines: io::Lines<io::BufReader<File>>) -> Vec<List> {
+fn parse_list(lines: io::Lines<impl BufRead>) -> Result<Vec<List>> {
     let lexerdef = list_l::lexerdef();
-    lines
-        .into_iter()
-        .map(|s| s.unwrap().trim().to_owned())
-        .filter(|s| !s.is_empty())
-        .map(|s| {
-            let lexer = lexerdef.lexer(&s);
-            let (res, errs) = list_y::parse(&lexer);
-            for e in errs {
-                println!("{}", e.pp(&lexer, &list_y::token_epp));
-            }
-            match res {
-                Some(Ok(r)) => r,
-                _ => List::Empty,
-            }
-        })
-        .collect()
+    let lists = parse_lines(lines, |s| {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(None);
+        }
+        let lexer = lexerdef.lexer(s);
+        let (res, errs) = list_y::parse(&lexer);
+        if let Some(e) = errs.first() {
+            bail!("{}", e.pp(&lexer, &list_y::token_epp));
+        }
+        match res {
+            Some(Ok(r)) => Ok(Some(r)),
+            _ => bail!("invalid list"),
+        }
+    })?;
+    Ok(lists.into_iter().flatten().collect())
 }
 
 enum Order {
@@ -62,18 +59,18 @@ fn is_ordered(a: &List, b: &List) -> Order {
                     Order::Wrong
                 }
             }
-            List::Array(_) => is_ordered(&List::Array(vec![Box::new(List::Num(*a))]), b),
+            List::Array(_) => is_ordered(&List::Array(vec![List::Num(*a)]), b),
         },
         List::Array(va) => match b {
             List::Empty => Order::Wrong,
-            List::Num(b) => is_ordered(a, &List::Array(vec![Box::new(List::Num(*b))])),
+            List::Num(b) => is_ordered(a, &List::Array(vec![List::Num(*b)])),
             List::Array(vb) => {
                 let adim = va.len();
                 for i in 0..adim {
                     if i >= vb.len() {
                         return Order::Wrong;
                     }
-                    match is_ordered(&(*va[i]), &(*vb[i])) {
+                    match is_ordered(&va[i], &vb[i]) {
                         Order::Wrong => {
                             return Order::Wrong;
                         }
@@ -93,23 +90,20 @@ fn is_ordered(a: &List, b: &List) -> Order {
     }
 }
 
-fn count_orderd(lists: &Vec<List>) -> usize {
+fn count_orderd(lists: &[List]) -> usize {
     let mut sum = 0;
     for i in 0..lists.len() / 2 {
-        match is_ordered(&lists[i * 2], &lists[i * 2 + 1]) {
-            Order::Right => {
-                sum += i + 1;
-            }
-            _ => {}
+        if let Order::Right = is_ordered(&lists[i * 2], &lists[i * 2 + 1]) {
+            sum += i + 1;
         }
     }
     sum
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let lists = parse_list(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let lists = parse_list(lines)?;
     let sum = count_orderd(&lists);
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
 fn ordering(a: &List, b: &List) -> Ordering {
@@ -120,55 +114,30 @@ fn ordering(a: &List, b: &List) -> Ordering {
     }
 }
 
-fn find_marker(m: u8, lists: &Vec<List>) -> usize {
+fn find_marker(m: u8, lists: &[List]) -> usize {
     for (i, item) in lists.iter().enumerate() {
-        match &item {
-            List::Array(va) => match &(*(va[0])) {
-                List::Array(vaa) => match *(vaa[0]) {
-                    List::Num(x) => {
-                        if m == x {
-                            return i + 1;
-                        }
+        if let List::Array(va) = &item {
+            if let List::Array(vaa) = &va[0] {
+                if let List::Num(x) = vaa[0] {
+                    if m == x {
+                        return i + 1;
                     }
-                    _ => {}
-                },
-                _ => {}
-            },
-            _ => {}
+                }
+            }
         }
     }
     0
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut lists = parse_list(lines);
-    let marker2 = List::Array(vec![Box::new(List::Num(2))]);
-    lists.push(List::Array(vec![Box::new(marker2)]));
-    let marker6 = List::Array(vec![Box::new(List::Num(6))]);
-    lists.push(List::Array(vec![Box::new(marker6)]));
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut lists = parse_list(lines)?;
+    let marker2 = List::Array(vec![List::Num(2)]);
+    lists.push(List::Array(vec![marker2]));
+    let marker6 = List::Array(vec![List::Num(6)]);
+    lists.push(List::Array(vec![marker6]));
 
-    lists.sort_by(|a, b| ordering(a, b));
+    lists.sort_by(ordering);
 
     let decoder_key = find_marker(2, &lists) * find_marker(6, &lists);
-    format!("{decoder_key}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_13_1() {
-        let lines = read_lines("data/2022/13.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "6070");
-    }
-
-    #[test]
-    fn test_2022_13_2() {
-        let lines = read_lines("data/2022/13.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "20758");
-    }
+    Ok(format!("{decoder_key}"))
 }
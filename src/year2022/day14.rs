@@ -1,36 +1,40 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
+use crate::{
+    parse_lines,
+    visualize::{self, Rgb, BLACK, GREY, SAND, WHITE},
+    AdventError,
+};
+use anyhow::{anyhow, bail, Result};
+
+solution!(
+    2022,
+    14,
+    "Regolith Reservoir",
+    parse = |lines| read_map(lines, false)
+);
+
+fn parse_point(s: &str) -> Result<(u16, u16)> {
+    let (x, y) = s
+        .split_once(",")
+        .ok_or_else(|| anyhow!("expected point, found '{s}'"))?;
+    Ok((x.parse()?, y.parse()?))
 }
 
-pub fn read_map(
-    mut lines: io::Lines<io::BufReader<File>>,
-    variant2: bool,
-) -> (Vec<Vec<u8>>, usize) {
-    let mut rocks = Vec::new();
-    let mut minx = u16::MAX;
-    let mut maxx = 0;
+pub fn read_map(lines: io::Lines<impl BufRead>, variant2: bool) -> Result<(Vec<Vec<u8>>, usize)> {
+    let rocks = parse_lines(lines, |s| {
+        s.split(" -> ").map(parse_point).collect::<Result<Vec<_>>>()
+    })?;
+    let mut minx = 500;
+    let mut maxx = 500;
     let mut maxy = 0;
-    while let Some(Ok(s)) = lines.next() {
-        let mut parts = s.split(" -> ");
-        let mut points = Vec::new();
-        while let Some(sub) = parts.next() {
-            let mut coords = sub.split(",");
-            let point = (
-                coords.next().unwrap().parse::<u16>().unwrap(),
-                coords.next().unwrap().parse::<u16>().unwrap(),
-            );
-            minx = minx.min(point.0);
-            maxx = maxx.max(point.0);
-            maxy = maxy.max(point.1);
-            points.push(point)
-        }
-        rocks.push(points);
+    for point in rocks.iter().flatten() {
+        minx = minx.min(point.0);
+        maxx = maxx.max(point.0);
+        maxy = maxy.max(point.1);
+    }
+    if variant2 && maxx + maxy >= 998 {
+        bail!(AdventError::invalid("rocks reach too far to the right"));
     }
     let offset = if variant2 { 0 } else { minx - 1 };
     let sand = (500 - offset) as usize;
@@ -62,24 +66,21 @@ pub fn read_map(
     }
     map[0][sand] = 1;
 
-    (map, sand)
+    Ok((map, sand))
 }
 
-fn print_map(m: &Vec<Vec<u8>>) {
-    for line in m {
-        for p in line {
-            match p {
-                8 => print!("#"),
-                1 => print!("+"),
-                2 => print!("o"),
-                _ => print!("."),
-            }
-        }
-        println!();
+fn colour(cell: &u8) -> Rgb {
+    match cell {
+        8 => GREY,
+        1 => WHITE,
+        2 => SAND,
+        _ => BLACK,
     }
 }
 
-fn simulate_sand(map: &mut Vec<Vec<u8>>, sand: usize) -> i32 {
+/// Drops sand until it flows off the map or blocks the source, recording a
+/// frame whenever `frame_every` more units came to rest
+fn simulate_sand(map: &mut [Vec<u8>], sand: usize, name: &str, frame_every: i32) -> i32 {
     let mut sand_point = (sand, 0);
     let maxy = map.len() - 1;
     let mut count = 0;
@@ -98,45 +99,27 @@ fn simulate_sand(map: &mut Vec<Vec<u8>>, sand: usize) -> i32 {
                     map[sand_point.1][sand_point.0] = 2;
                     sand_point = (sand, 0);
                     count += 1;
+                    if count % frame_every == 0 {
+                        visualize::record(name, map.iter(), colour);
+                    }
                 }
             }
             _ => {}
         }
     }
     map[sand_point.1][sand_point.0] = 2;
+    visualize::record(name, map.iter(), colour);
     count
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let (mut map, sand) = read_map(lines, false);
-    let count = simulate_sand(&mut map, sand);
-    print_map(&map);
-    format!("{count}")
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (mut map, sand) = read_map(lines, false)?;
+    let count = simulate_sand(&mut map, sand, "2022-14-1", 1);
+    Ok(format!("{count}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let (mut map, sand) = read_map(lines, true);
-    let count = simulate_sand(&mut map, sand);
-    print_map(&map);
-    format!("{count}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_14_1() {
-        let lines = read_lines("data/2022/14.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "888");
-    }
-
-    #[test]
-    fn test_2022_14_2() {
-        let lines = read_lines("data/2022/14.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "26461");
-    }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (mut map, sand) = read_map(lines, true)?;
+    let count = simulate_sand(&mut map, sand, "2022-14-2", 250);
+    Ok(format!("{count}"))
 }
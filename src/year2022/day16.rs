@@ -1,16 +1,19 @@
-use std::{fs::File, io};
-
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use std::io::{self, BufRead};
 
 use bitfield::{bitfield_bitrange, Bit, BitMut};
 use regex::Regex;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::{dot::Graph, parse_lines, AdventError};
+use anyhow::{anyhow, bail, Result};
+
+solution!(
+    2022,
+    16,
+    "Proboscidea Volcanium",
+    parse = read_valves,
+    dot = valve_graph
+);
 
 #[derive(Debug, Clone)]
 struct Valve {
@@ -19,29 +22,67 @@ struct Valve {
     pub next: HashMap<String, i32>,
 }
 
-fn read_valves(lines: io::Lines<io::BufReader<File>>) -> HashMap<String, Valve> {
+fn read_valves(lines: io::Lines<impl BufRead>) -> Result<HashMap<String, Valve>> {
     let re = Regex::new(
         r"Valve ([A-Z]{2}) has flow rate=([0-9]*); tunnel[s]? lead[s]? to valve[s]? ([,A-Z ]*)",
     )
     .unwrap();
     let mut id = 0;
-    lines
-        .into_iter()
-        .filter_map(|x| x.ok())
-        .map(|x| {
-            let r = re.captures_iter(&x).into_iter().next().unwrap();
-            let name = r.get(1).unwrap().as_str().to_owned();
-            let rate = r.get(2).unwrap().as_str().parse::<i32>().unwrap();
-            let next_str = r.get(3).unwrap().as_str();
-            let valve = Valve {
-                id,
-                rate,
-                next: next_str.split(", ").map(|s| (s.to_owned(), 1)).collect(),
-            };
-            id += 1;
-            (name, valve)
-        })
-        .collect()
+    let valves = parse_lines(lines, |x| {
+        let r = re
+            .captures(x)
+            .ok_or_else(|| anyhow!("expected valve description"))?;
+        let name = r[1].to_owned();
+        let rate = r[2].parse::<i32>()?;
+        let valve = Valve {
+            id,
+            rate,
+            next: r[3].split(", ").map(|s| (s.to_owned(), 1)).collect(),
+        };
+        id += 1;
+        Ok((name, valve))
+    })?;
+    let valves: HashMap<String, Valve> = valves.into_iter().collect();
+    if !valves.contains_key("AA") {
+        bail!(AdventError::invalid("no valve AA to start from"));
+    }
+    for valve in valves.values() {
+        if let Some(next) = valve.next.keys().find(|next| !valves.contains_key(*next)) {
+            bail!(AdventError::invalid(format!(
+                "tunnel to unknown valve {next}"
+            )));
+        }
+    }
+    Ok(valves)
+}
+
+/// The tunnels between the valves, labelled with their flow rates
+fn valve_graph(lines: io::Lines<impl BufRead>) -> Result<Graph> {
+    let valves: BTreeMap<String, Valve> = read_valves(lines)?.into_iter().collect();
+    let mut graph = Graph::undirected("2022-16");
+    for (name, valve) in &valves {
+        let shape = if name == "AA" {
+            "doublecircle"
+        } else if valve.rate > 0 {
+            "box"
+        } else {
+            "ellipse"
+        };
+        let label = format!("{name} {}", valve.rate);
+        graph.node(
+            name,
+            &[("label", &label), ("rate", &valve.rate), ("shape", &shape)],
+        );
+    }
+    // a tunnel may be listed by one of its valves only
+    let tunnels: BTreeSet<(&String, &String)> = valves
+        .iter()
+        .flat_map(|(name, valve)| valve.next.keys().map(move |n| (name.min(n), name.max(n))))
+        .collect();
+    for (a, b) in tunnels {
+        graph.edge(a, b, &[]);
+    }
+    Ok(graph)
 }
 
 #[derive(Debug, Clone)]
@@ -124,8 +165,8 @@ fn max_pressure(valves: &HashMap<String, Valve>, state: State) -> i32 {
     max_released
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let valves = read_valves(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let valves = read_valves(lines)?;
     let valves = calc_distances(&valves);
     let state = State {
         open: OpenStat(0),
@@ -133,7 +174,7 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
         node: "AA".to_owned(),
     };
     let max_released = max_pressure(&valves, state);
-    format!("{max_released}")
+    Ok(format!("{max_released}"))
 }
 
 #[derive(Debug, Clone)]
@@ -223,8 +264,8 @@ fn max_pressure2(valves: &HashMap<String, Valve>, state: State2) -> i32 {
     max_released
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let valves = read_valves(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let valves = read_valves(lines)?;
     let valves = calc_distances(&valves);
     let state = State2 {
         open: OpenStat(0),
@@ -232,25 +273,30 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
         node: (Some("AA".to_owned()), Some("AA".to_owned())),
     };
     let max_released = max_pressure2(&valves, state);
-    format!("{max_released}")
+    Ok(format!("{max_released}"))
 }
 
 #[cfg(test)]
 mod test {
-    use super::execute;
-    use crate::read_lines;
+    use super::valve_graph;
+    use std::io::BufRead;
 
     #[test]
-    fn test_2022_16_1() {
-        let lines = read_lines("data/2022/16.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "1617");
-    }
-
-    #[test]
-    fn test_2022_16_2() {
-        let lines = read_lines("data/2022/16.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "2171");
+    fn test_2022_16_tunnel_listed_once() {
+        let scan = "Valve AA has flow rate=0; tunnels lead to valves BB, CC
+Valve BB has flow rate=13; tunnel leads to valve AA
+Valve CC has flow rate=2; tunnel leads to valve BB
+";
+        let dot = valve_graph(scan.as_bytes().lines()).unwrap().to_string();
+        assert!(dot.contains("\"BB\" [label=\"BB 13\", rate=\"13\", shape=\"box\"];"));
+        let edges: Vec<&str> = dot.lines().filter(|l| l.contains(" -- ")).collect();
+        assert_eq!(
+            edges,
+            vec![
+                "  \"AA\" -- \"BB\";",
+                "  \"AA\" -- \"CC\";",
+                "  \"BB\" -- \"CC\";"
+            ]
+        );
     }
 }
@@ -4,17 +4,22 @@
 /// However, it took far to long to implement the structs and code the methods.
 /// Still, I was very surprised that my rank wasn't that bad...
 ///
-use std::{fs::File, io};
+use std::{
+    io::{self, BufRead},
+    iter,
+};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use std::collections::HashSet;
+
+use crate::{
+    cycle::find_cycle,
+    next_line,
+    visualize::{self, BLACK, WHITE},
+    AdventError,
+};
+use anyhow::Result;
 
-use std::collections::{HashMap, HashSet};
+solution!(2022, 17, "Pyroclastic Flow");
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 enum BlockType {
@@ -339,56 +344,88 @@ impl Block for SquareBlock {
 
 type Floor = Vec<Vec<usize>>;
 
-fn print_floor(floor: &Floor) {
-    for f in floor.into_iter().rev() {
-        print!("|");
-        for b in f {
-            if *b == 0 {
-                print!(".");
-            } else {
-                print!("#");
-            }
+/// Records the top of the tower, padded to the same height for every frame
+fn record_top(name: &str, floor: &Floor) {
+    const ROWS: usize = 40;
+    let empty = vec![0; 7];
+    let rows = floor.iter().rev().chain(iter::repeat(&empty)).take(ROWS);
+    visualize::record(name, rows, |&b| if b == 0 { BLACK } else { WHITE });
+}
+
+fn read_wind(lines: &mut io::Lines<impl BufRead>) -> Result<Vec<i32>> {
+    next_line(lines)?
+        .bytes()
+        .enumerate()
+        .map(|(col, x)| match x {
+            b'<' | b'>' => Ok((x as i32) - 61),
+            _ => Err(AdventError::parse(1, col + 1, "expected '<' or '>'").into()),
+        })
+        .collect()
+}
+
+struct Tower {
+    wind: Vec<i32>,
+    blow_idx: usize,
+    floor: Floor,
+    block: Box<dyn Block>,
+}
+
+impl Tower {
+    fn new(wind: Vec<i32>) -> Self {
+        let floor = Vec::new();
+        let block = Box::new(MinusBlock::new(&floor));
+        Self {
+            wind,
+            blow_idx: 0,
+            floor,
+            block,
         }
-        println!("|");
     }
-    println!("+-------+\n");
-}
 
-pub fn riddle_1(mut lines: io::Lines<io::BufReader<File>>) -> String {
-    let wind: Vec<i32> = lines
-        .next()
-        .unwrap()
-        .unwrap()
-        .as_bytes()
-        .into_iter()
-        .map(|x| (*x as i32) - 61)
-        .collect();
-    let mut floor = Vec::new();
-    let mut blow_idx = 0;
-    let mut current_block: Box<dyn Block> = Box::new(MinusBlock::new(&floor));
-    let mut block_counter = 1;
-    loop {
-        let blow = wind[blow_idx];
-        blow_idx = (blow_idx + 1) % wind.len();
-        current_block.push(blow, &floor);
-        if !current_block.fall_down(&mut floor) {
-            current_block = current_block.next_block(&floor);
-            if block_counter == 2022 {
-                break;
+    /// Lets the current block fall until it comes to rest
+    fn drop_block(&mut self) {
+        loop {
+            let blow = self.wind[self.blow_idx];
+            self.blow_idx = (self.blow_idx + 1) % self.wind.len();
+            self.block.push(blow, &self.floor);
+            if !self.block.fall_down(&mut self.floor) {
+                self.block = self.block.next_block(&self.floor);
+                return;
             }
-            block_counter += 1;
         }
     }
-    print_floor(&floor);
-    format!("{}", floor.len())
 }
 
-struct State {
+pub fn riddle_1(mut lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut tower = Tower::new(read_wind(&mut lines)?);
+    for _ in 0..2022 {
+        tower.drop_block();
+        record_top("2022-17-1", &tower.floor);
+    }
+    Ok(format!("{}", tower.floor.len()))
+}
+
+/// What the tower looks like to the next block, which repeats once the
+/// wind, the block type and the rows it can reach are the same
+#[derive(Clone)]
+struct Snapshot {
+    blow_idx: usize,
+    block_type: BlockType,
     top_floor: Floor,
-    block_number: usize,
     height: usize,
 }
 
+impl Snapshot {
+    fn of(tower: &Tower) -> Self {
+        Self {
+            blow_idx: tower.blow_idx,
+            block_type: tower.block.block_type(),
+            top_floor: top_of_floor(&tower.floor),
+            height: tower.floor.len(),
+        }
+    }
+}
+
 fn top_of_floor(floor: &Floor) -> Floor {
     let mut top = floor.len();
     let mut found = HashSet::new();
@@ -411,91 +448,18 @@ fn top_of_floor(floor: &Floor) -> Floor {
     }
 }
 
-fn floor_equal(floor1: &Floor, floor2: &Floor) -> bool {
-    if floor1.len() != floor2.len() {
-        return false;
-    }
-    for i in 0..floor1.len() {
-        for j in 0..7 {
-            if floor1[i][j] != floor2[i][j] {
-                return false;
-            }
-        }
-    }
-    true
-}
-
-pub fn riddle_2(mut lines: io::Lines<io::BufReader<File>>) -> String {
-    let wind: Vec<i32> = lines
-        .next()
-        .unwrap()
-        .unwrap()
-        .as_bytes()
-        .into_iter()
-        .map(|x| (*x as i32) - 61)
-        .collect();
-    let mut floor = Vec::new();
-    let mut blow_idx = 0;
-    let mut current_block: Box<dyn Block> = Box::new(MinusBlock::new(&floor));
-    let mut block_counter: usize = 1;
-    let mut combinations: HashMap<(usize, BlockType), State> = HashMap::new();
-    let mut block_stopper = 0;
-    let mut skipped_height = 0;
-    let total_block_num = 1000000000000_usize;
-    loop {
-        let blow = wind[blow_idx];
-        blow_idx = (blow_idx + 1) % wind.len();
-        current_block.push(blow, &floor);
-        if !current_block.fall_down(&mut floor) {
-            let sample = (blow_idx, current_block.block_type());
-            if combinations.contains_key(&sample) {
-                let top_floor = top_of_floor(&floor);
-                if floor_equal(&top_floor, &combinations[&sample].top_floor) && blow_idx == 2434 {
-                    let state = &combinations[&sample];
-                    let delta_block_num = block_counter - state.block_number;
-                    let delta_height = floor.len() - state.height;
-                    let blocks_to_skip = (total_block_num - block_counter) / delta_block_num;
-                    skipped_height = blocks_to_skip * delta_height;
-                    block_stopper =
-                        block_counter + (total_block_num - block_counter) % delta_block_num;
-                }
-            } else {
-                combinations.insert(
-                    sample,
-                    State {
-                        top_floor: top_of_floor(&floor),
-                        block_number: block_counter,
-                        height: floor.len(),
-                    },
-                );
-            }
-            if block_counter == block_stopper {
-                break;
-            }
-            current_block = current_block.next_block(&floor);
-            block_counter += 1;
-        }
-    }
-    let total_height = floor.len() + skipped_height;
-    format!("{total_height}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_17_1() {
-        let lines = read_lines("data/2022/17.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "3130");
-    }
-
-    #[test]
-    fn test_2022_17_2() {
-        let lines = read_lines("data/2022/17.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1556521739139");
-    }
+pub fn riddle_2(mut lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut tower = Tower::new(read_wind(&mut lines)?);
+    // the tower itself is too large to copy for every block, so each step
+    // advances it and only returns a snapshot of its top
+    let (cycle, snapshots) = find_cycle(
+        Snapshot::of(&tower),
+        |_| {
+            tower.drop_block();
+            Snapshot::of(&tower)
+        },
+        |s| (s.blow_idx, s.block_type.clone(), s.top_floor.clone()),
+    );
+    let total_height = cycle.extrapolate(1000000000000, |n| snapshots[n].height as i64);
+    Ok(format!("{total_height}"))
 }
@@ -1,15 +1,12 @@
-use std::{fs::File, io};
-
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use std::io::{self, BufRead};
 
 use regex::Regex;
 
+use crate::{parallel, parse_lines};
+use anyhow::{anyhow, Result};
+
+solution!(2022, 19, "Not Enough Minerals", parse = read_blueprints);
+
 #[derive(Debug, Clone)]
 struct Cost {
     pub ore: i32,
@@ -35,48 +32,46 @@ impl BluePrint {
     }
 }
 
-fn read_blueprints(lines: io::Lines<io::BufReader<File>>) -> Vec<BluePrint> {
+fn read_blueprints(lines: io::Lines<impl BufRead>) -> Result<Vec<BluePrint>> {
     let re = Regex::new(
         r"Blueprint ([0-9]*):.* ([0-9]*) ore.* ([0-9]*) ore.* ([0-9]*) ore.* ([0-9]*) clay.* ([0-9]*) ore.* ([0-9]*) obsidian",
     )
     .unwrap();
-    lines
-        .into_iter()
-        .filter_map(|x| x.ok())
-        .map(|x| {
-            let r = re.captures_iter(&x).into_iter().next().unwrap();
-            let id = r.get(1).unwrap().as_str().parse::<i32>().unwrap();
-            let ore1 = r.get(2).unwrap().as_str().parse::<i32>().unwrap();
-            let ore2 = r.get(3).unwrap().as_str().parse::<i32>().unwrap();
-            let ore3 = r.get(4).unwrap().as_str().parse::<i32>().unwrap();
-            let clay = r.get(5).unwrap().as_str().parse::<i32>().unwrap();
-            let ore4 = r.get(6).unwrap().as_str().parse::<i32>().unwrap();
-            let obsidian = r.get(7).unwrap().as_str().parse::<i32>().unwrap();
-            BluePrint {
-                id,
-                ore_robot_cost: Cost {
-                    ore: ore1,
-                    clay: 0,
-                    obsidian: 0,
-                },
-                clay_robot_cost: Cost {
-                    ore: ore2,
-                    clay: 0,
-                    obsidian: 0,
-                },
-                obsidian_robot_cost: Cost {
-                    ore: ore3,
-                    clay,
-                    obsidian: 0,
-                },
-                geode_robot_cost: Cost {
-                    ore: ore4,
-                    clay: 0,
-                    obsidian,
-                },
-            }
+    parse_lines(lines, |x| {
+        let r = re
+            .captures(x)
+            .ok_or_else(|| anyhow!("expected blueprint description"))?;
+        let id = r[1].parse::<i32>()?;
+        let ore1 = r[2].parse::<i32>()?;
+        let ore2 = r[3].parse::<i32>()?;
+        let ore3 = r[4].parse::<i32>()?;
+        let clay = r[5].parse::<i32>()?;
+        let ore4 = r[6].parse::<i32>()?;
+        let obsidian = r[7].parse::<i32>()?;
+        Ok(BluePrint {
+            id,
+            ore_robot_cost: Cost {
+                ore: ore1,
+                clay: 0,
+                obsidian: 0,
+            },
+            clay_robot_cost: Cost {
+                ore: ore2,
+                clay: 0,
+                obsidian: 0,
+            },
+            obsidian_robot_cost: Cost {
+                ore: ore3,
+                clay,
+                obsidian: 0,
+            },
+            geode_robot_cost: Cost {
+                ore: ore4,
+                clay: 0,
+                obsidian,
+            },
         })
-        .collect()
+    })
 }
 
 #[derive(Debug, Clone, Hash)]
@@ -125,18 +120,17 @@ fn round_up_div(x: i32, y: i32) -> i32 {
     }
 }
 
+/// Depth first, so that only the branches of the current path are kept
 fn max_geode(minutes: i32, bp: &BluePrint) -> i32 {
-    let mut state = vec![Inventory::new(minutes)];
+    let mut stack = vec![Inventory::new(minutes)];
     let mut max_geode = 0;
-    loop {
-        let mut branch = Vec::new();
-        for v in &state {
-            max_geode = max_geode.max(make_inv_branch(v, bp, &mut branch));
-        }
-        state = branch;
-        if state.is_empty() {
-            break;
+    while let Some(inv) = stack.pop() {
+        // not even a new geode robot every minute would beat the best so far
+        let t = inv.time_left;
+        if inv.geode + inv.geode_robots * t + t * (t - 1) / 2 <= max_geode {
+            continue;
         }
+        max_geode = max_geode.max(make_inv_branch(&inv, bp, &mut stack));
     }
     max_geode
 }
@@ -223,47 +217,22 @@ fn make_inv_branch(inv: &Inventory, bp: &BluePrint, branch: &mut Vec<Inventory>)
             branch.push(inv);
         }
     }
-    return max_geode;
-}
-
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let blue_prints = read_blueprints(lines);
-
-    let mut quality_level = 0;
-    for bp in &blue_prints {
-        let ql = bp.id * max_geode(24, bp);
-        quality_level += ql;
-    }
-    format!("{quality_level}")
+    max_geode
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let blue_prints = read_blueprints(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let blue_prints = read_blueprints(lines)?;
 
-    let mut total_score = 1;
-    for bp in &blue_prints[0..3] {
-        let score = max_geode(32, bp);
-        total_score *= score;
-    }
-    format!("{total_score}")
+    let quality_level: i32 = parallel::map(&blue_prints, |bp| bp.id * max_geode(24, bp)).sum();
+    Ok(format!("{quality_level}"))
 }
 
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_19_1() {
-        let lines = read_lines("data/2022/19.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "1766");
-    }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let blue_prints = read_blueprints(lines)?;
 
-    #[test]
-    fn test_2022_19_2() {
-        let lines = read_lines("data/2022/19.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "30780");
-    }
+    let total_score: i32 = parallel::map(&blue_prints[..blue_prints.len().min(3)], |bp| {
+        max_geode(32, bp)
+    })
+    .product();
+    Ok(format!("{total_score}"))
 }
@@ -1,36 +1,37 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::parse_lines;
+use anyhow::{anyhow, bail, Result};
+
+solution!(
+    2022,
+    2,
+    "Rock Paper Scissors",
+    parse = |lines| parse_lines(lines, parse_letters)
+);
 
-fn parse_letters(s: String) -> (String, String) {
-    let mut parts = s.split(' ');
-    (
-        parts.next().unwrap_or("").to_string(),
-        parts.next().unwrap_or("").to_string(),
-    )
+fn parse_letters(s: &str) -> Result<(i32, i32)> {
+    let (abc, xyz) = s
+        .split_once(' ')
+        .ok_or_else(|| anyhow!("expected two letters"))?;
+    Ok((abc_to_num(abc)?, xyz_to_num(xyz)?))
 }
 
-fn abc_to_num(s: String) -> i32 {
-    match s.as_str() {
-        "A" => 1,
-        "B" => 2,
-        "C" => 3,
-        _ => panic!("invalid abc input"),
+fn abc_to_num(s: &str) -> Result<i32> {
+    match s {
+        "A" => Ok(1),
+        "B" => Ok(2),
+        "C" => Ok(3),
+        _ => bail!("invalid abc input '{s}'"),
     }
 }
 
-fn xyz_to_num(s: String) -> i32 {
-    match s.as_str() {
-        "X" => 1,
-        "Y" => 2,
-        "Z" => 3,
-        _ => panic!("invalid xyz input"),
+fn xyz_to_num(s: &str) -> Result<i32> {
+    match s {
+        "X" => Ok(1),
+        "Y" => Ok(2),
+        "Z" => Ok(3),
+        _ => bail!("invalid xyz input '{s}'"),
     }
 }
 
@@ -52,46 +53,20 @@ fn score_strat2(x: i32, y: i32) -> i32 {
     score(x, z) + z
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let total_score: i32 = lines
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let total_score: i32 = parse_lines(lines, parse_letters)?
         .into_iter()
-        .filter_map(|s| s.ok())
-        .map(parse_letters)
-        .map(|(x, y)| (abc_to_num(x), xyz_to_num(y)))
         .map(|(x, y)| score(x, y) + y)
         .sum();
 
-    format!("{total_score}")
+    Ok(format!("{total_score}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let total_score: i32 = lines
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let total_score: i32 = parse_lines(lines, parse_letters)?
         .into_iter()
-        .filter_map(|s| s.ok())
-        .map(parse_letters)
-        .map(|(x, y)| (abc_to_num(x), xyz_to_num(y)))
         .map(|(x, y)| score_strat2(x, y))
         .sum();
 
-    format!("{total_score}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_2_1() {
-        let lines = read_lines("data/2022/2.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "13009");
-    }
-
-    #[test]
-    fn test_2022_2_2() {
-        let lines = read_lines("data/2022/2.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "10398");
-    }
+    Ok(format!("{total_score}"))
 }
@@ -1,27 +1,20 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::parse_lines;
+use anyhow::Result;
+
+solution!(2022, 20, "Grove Positioning System", parse = read_values);
 
-fn read_values(lines: io::Lines<io::BufReader<File>>) -> Vec<(i64, i64)> {
+fn read_values(lines: io::Lines<impl BufRead>) -> Result<Vec<(i64, i64)>> {
     let mut idx = 0;
-    lines
-        .into_iter()
-        .filter_map(|x| x.ok())
-        .map(|s| {
-            let value = (s.parse::<i64>().unwrap(), idx);
-            idx += 1;
-            value
-        })
-        .collect()
+    parse_lines(lines, |s| {
+        let value = (s.parse::<i64>()?, idx);
+        idx += 1;
+        Ok(value)
+    })
 }
 
-fn scramble(vals: &mut Vec<(i64, i64)>) {
+fn scramble(vals: &mut [(i64, i64)]) {
     let len = vals.len() as i64;
     for i in 0..len {
         let mut old_idx = 0;
@@ -61,7 +54,7 @@ fn scramble(vals: &mut Vec<(i64, i64)>) {
     }
 }
 
-fn get_coordinate(vals: &Vec<(i64, i64)>) -> i64 {
+fn get_coordinate(vals: &[(i64, i64)]) -> i64 {
     let mut zero_idx = 0;
     for i in 0..vals.len() {
         if vals[i].0 == 0 {
@@ -74,10 +67,10 @@ fn get_coordinate(vals: &Vec<(i64, i64)>) -> i64 {
     vals[first].0 + vals[second].0 + vals[third].0
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut values = read_values(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut values = read_values(lines)?;
     scramble(&mut values);
-    format!("{:?}", get_coordinate(&values))
+    Ok(format!("{:?}", get_coordinate(&values)))
 }
 
 pub fn multiply_with_key(vals: &mut Vec<(i64, i64)>, key: i64) {
@@ -86,32 +79,12 @@ pub fn multiply_with_key(vals: &mut Vec<(i64, i64)>, key: i64) {
     }
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut values = read_values(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut values = read_values(lines)?;
     let key = 811589153_i64;
     multiply_with_key(&mut values, key);
     for _ in 0..10 {
         scramble(&mut values);
     }
-    format!("{:?}", get_coordinate(&values))
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_20_1() {
-        let lines = read_lines("data/2022/20.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "17490");
-    }
-
-    #[test]
-    fn test_2022_20_2() {
-        let lines = read_lines("data/2022/20.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1632917375836");
-    }
+    Ok(format!("{:?}", get_coordinate(&values)))
 }
@@ -1,16 +1,13 @@
-use std::{fs::File, io};
-
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use std::io::{self, BufRead};
 
 use regex::Regex;
 use std::collections::HashMap;
 
+use crate::{parse_lines, AdventError};
+use anyhow::{anyhow, bail, Result};
+
+solution!(2022, 21, "Monkey Math", parse = read_operations);
+
 #[derive(Debug, Clone)]
 enum Operation {
     Number(i64),
@@ -21,50 +18,70 @@ enum Operation {
     Equal(String, String),
 }
 
-fn read_operations(lines: io::Lines<io::BufReader<File>>) -> HashMap<String, Operation> {
-    let re_num = Regex::new("([a-z]+): ([0-9]+)").unwrap();
-    let re_op = Regex::new("([a-z]+): ([a-z]+) ([-+*=/]) ([a-z]+)").unwrap();
-    let mut ops = HashMap::new();
-    for line in lines {
-        let line = line.unwrap();
-        for cap in re_num.captures_iter(&line) {
-            ops.insert(
-                cap[1].to_owned(),
-                Operation::Number(cap[2].parse::<i64>().unwrap()),
-            );
+fn read_operations(lines: io::Lines<impl BufRead>) -> Result<HashMap<String, Operation>> {
+    let re_num = Regex::new("^([a-z]+): ([0-9]+)$").unwrap();
+    let re_op = Regex::new("^([a-z]+): ([a-z]+) ([-+*=/]) ([a-z]+)$").unwrap();
+    let ops = parse_lines(lines, |line| {
+        if let Some(cap) = re_num.captures(line) {
+            return Ok((cap[1].to_owned(), Operation::Number(cap[2].parse::<i64>()?)));
         }
-        for cap in re_op.captures_iter(&line) {
-            let op = match &cap[3] {
-                "+" => Operation::Add(cap[2].to_owned(), cap[4].to_owned()),
-                "-" => Operation::Sub(cap[2].to_owned(), cap[4].to_owned()),
-                "*" => Operation::Mul(cap[2].to_owned(), cap[4].to_owned()),
-                "/" => Operation::Div(cap[2].to_owned(), cap[4].to_owned()),
-                "=" => Operation::Equal(cap[2].to_owned(), cap[4].to_owned()),
-                s => panic!("Operation '{s}' is not supported"),
-            };
-            ops.insert(cap[1].to_owned(), op);
+        let cap = re_op
+            .captures(line)
+            .ok_or_else(|| anyhow!("expected number or operation"))?;
+        let op = match &cap[3] {
+            "+" => Operation::Add(cap[2].to_owned(), cap[4].to_owned()),
+            "-" => Operation::Sub(cap[2].to_owned(), cap[4].to_owned()),
+            "*" => Operation::Mul(cap[2].to_owned(), cap[4].to_owned()),
+            "/" => Operation::Div(cap[2].to_owned(), cap[4].to_owned()),
+            _ => Operation::Equal(cap[2].to_owned(), cap[4].to_owned()),
+        };
+        Ok((cap[1].to_owned(), op))
+    })?;
+    let ops: HashMap<String, Operation> = ops.into_iter().collect();
+    if !ops.contains_key("root") {
+        bail!(AdventError::invalid("no monkey named root"));
+    }
+    for op in ops.values() {
+        match op {
+            Operation::Number(_) => {}
+            Operation::Add(a, b)
+            | Operation::Sub(a, b)
+            | Operation::Mul(a, b)
+            | Operation::Div(a, b)
+            | Operation::Equal(a, b) => {
+                if let Some(name) = [a, b].into_iter().find(|name| !ops.contains_key(*name)) {
+                    bail!(AdventError::invalid(format!("no monkey named {name}")));
+                }
+            }
         }
     }
-    ops
+    Ok(ops)
+}
+
+fn divide(a: i64, b: i64) -> Result<i64> {
+    a.checked_div(b)
+        .ok_or_else(|| AdventError::invalid(format!("cannot divide {a} by {b}")).into())
 }
 
-fn calc(current: String, ops: &mut HashMap<String, Operation>) -> i64 {
+fn calc(current: String, ops: &mut HashMap<String, Operation>) -> Result<i64> {
     let value = match ops[&current].clone() {
         Operation::Number(x) => x,
-        Operation::Add(a, b) => calc(a, ops) + calc(b, ops),
-        Operation::Sub(a, b) => calc(a, ops) - calc(b, ops),
-        Operation::Mul(a, b) => calc(a, ops) * calc(b, ops),
-        Operation::Div(a, b) => calc(a, ops) / calc(b, ops),
-        _ => panic!("Unsupported operation found for mode 1"),
+        Operation::Add(a, b) => calc(a, ops)? + calc(b, ops)?,
+        Operation::Sub(a, b) => calc(a, ops)? - calc(b, ops)?,
+        Operation::Mul(a, b) => calc(a, ops)? * calc(b, ops)?,
+        Operation::Div(a, b) => divide(calc(a, ops)?, calc(b, ops)?)?,
+        Operation::Equal(_, _) => bail!(AdventError::invalid(format!(
+            "{current} compares, which only root may do in part 2"
+        ))),
     };
     ops.insert(current, Operation::Number(value));
-    value
+    Ok(value)
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut ops = read_operations(lines);
-    let result = calc("root".to_string(), &mut ops);
-    format!("{result}")
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut ops = read_operations(lines)?;
+    let result = calc("root".to_string(), &mut ops)?;
+    Ok(format!("{result}"))
 }
 
 fn check(a: String, b: String, ops: &mut HashMap<String, Operation>) -> Option<(i64, i64)> {
@@ -105,7 +122,7 @@ fn pre_calc(current: String, ops: &mut HashMap<String, Operation>) -> Option<i64
         }
         Operation::Div(a, b) => {
             if let Some((x, y)) = check(a, b, ops) {
-                Some(x / y)
+                x.checked_div(y)
             } else {
                 None
             }
@@ -121,33 +138,48 @@ fn pre_calc(current: String, ops: &mut HashMap<String, Operation>) -> Option<i64
     value
 }
 
-fn calc_plain(current: &str, ops: &HashMap<String, Operation>) -> i64 {
-    let value = match ops[current].clone() {
-        Operation::Number(x) => x,
-        Operation::Add(a, b) => calc_plain(&a, ops) + calc_plain(&b, ops),
-        Operation::Sub(a, b) => calc_plain(&a, ops) - calc_plain(&b, ops),
-        Operation::Mul(a, b) => calc_plain(&a, ops) * calc_plain(&b, ops),
-        Operation::Div(a, b) => calc_plain(&a, ops) / calc_plain(&b, ops),
-        _ => panic!("Unsupported operation found for mode 1"),
-    };
-    value
+fn calc_plain(current: &str, ops: &HashMap<String, Operation>) -> Result<i64> {
+    Ok(match &ops[current] {
+        Operation::Number(x) => *x,
+        Operation::Add(a, b) => calc_plain(a, ops)? + calc_plain(b, ops)?,
+        Operation::Sub(a, b) => calc_plain(a, ops)? - calc_plain(b, ops)?,
+        Operation::Mul(a, b) => calc_plain(a, ops)? * calc_plain(b, ops)?,
+        Operation::Div(a, b) => divide(calc_plain(a, ops)?, calc_plain(b, ops)?)?,
+        Operation::Equal(_, _) => bail!(AdventError::invalid(format!(
+            "{current} compares, which only root may do"
+        ))),
+    })
 }
 
-fn roots_judgement(ops: &HashMap<String, Operation>) -> (i64, i64) {
+fn roots_judgement(ops: &HashMap<String, Operation>) -> Result<(i64, i64)> {
     if let Operation::Equal(a, b) = &ops["root"] {
-        (calc_plain(a, ops), calc_plain(b, ops))
+        Ok((calc_plain(a, ops)?, calc_plain(b, ops)?))
     } else {
-        panic!("root is not equal operation");
+        bail!(AdventError::invalid("root does not compare with '='"));
     }
 }
 
+/// In the second part root compares its operands, whatever its operation
+fn root_compares(ops: &mut HashMap<String, Operation>) {
+    let compared = match &ops["root"] {
+        Operation::Number(_) => return,
+        Operation::Add(a, b)
+        | Operation::Sub(a, b)
+        | Operation::Mul(a, b)
+        | Operation::Div(a, b)
+        | Operation::Equal(a, b) => Operation::Equal(a.clone(), b.clone()),
+    };
+    ops.insert("root".to_string(), compared);
+}
+
 fn solution_included(r1: (i64, i64), r2: (i64, i64)) -> bool {
-    ((r1.1 - r1.0) / (r1.1 - r1.0).abs()) * ((r2.1 - r2.0) / (r2.1 - r2.0).abs()) < 0
+    (r1.1 - r1.0).signum() * (r2.1 - r2.0).signum() <= 0
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut ops = read_operations(lines);
-    let keys: Vec<String> = ops.keys().into_iter().map(|x| x.clone()).collect();
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut ops = read_operations(lines)?;
+    root_compares(&mut ops);
+    let keys: Vec<String> = ops.keys().cloned().collect();
     for k in keys {
         let _ = pre_calc(k.to_owned(), &mut ops);
     }
@@ -158,21 +190,24 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
     let mut ur;
     loop {
         ops.insert("humn".to_owned(), Operation::Number(lower));
-        lr = roots_judgement(&ops);
+        lr = roots_judgement(&ops)?;
         ops.insert("humn".to_owned(), Operation::Number(upper));
-        ur = roots_judgement(&ops);
+        ur = roots_judgement(&ops)?;
         if solution_included(lr, ur) {
             break;
         }
         lower *= 10;
-        upper *= 10;
+        upper = upper.checked_mul(10).ok_or(AdventError::NoSolution)?;
     }
     loop {
         let middle = (lower + upper) / 2;
         ops.insert("humn".to_owned(), Operation::Number(middle));
-        let mr = roots_judgement(&ops);
+        let mr = roots_judgement(&ops)?;
         if mr.0 == mr.1 {
-            return format!("{middle}");
+            return Ok(format!("{middle}"));
+        }
+        if upper - lower <= 1 {
+            bail!(AdventError::NoSolution);
         }
         if solution_included(lr, mr) {
             upper = middle;
@@ -181,23 +216,3 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
         }
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_21_1() {
-        let lines = read_lines("data/2022/21.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "169525884255464");
-    }
-
-    #[test]
-    fn test_2022_21_2() {
-        let lines = read_lines("data/2022/21.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "3247317268284");
-    }
-}
@@ -1,15 +1,12 @@
-use std::{fs::File, io};
-
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use std::io::{self, BufRead};
 
 use regex::Regex;
 
+use crate::AdventError;
+use anyhow::{bail, Result};
+
+solution!(2022, 22, "Monkey Map", parse = read_map_and_moves);
+
 #[derive(Debug, Clone)]
 enum Move {
     TurnRight,
@@ -97,7 +94,7 @@ impl State {
                 return;
             }
             self.row = r as usize;
-            self.col = c as usize;
+            self.col = c;
         }
     }
 
@@ -240,14 +237,14 @@ const WALL: u8 = 35;
 
 type Map = Vec<Vec<u8>>;
 
-fn read_map_and_moves(lines: io::Lines<io::BufReader<File>>) -> (Map, Vec<Move>) {
+fn read_map_and_moves(lines: io::Lines<impl BufRead>) -> Result<(Map, Vec<Move>)> {
     let re = Regex::new("([0-9]+)|([RL]{1})").unwrap();
     let mut map = Vec::new();
     let mut moves = Vec::new();
     let mut map_mode = true;
     for line in lines {
-        let line = line.unwrap();
-        if line == "" {
+        let line = line?;
+        if line.is_empty() {
             map_mode = false;
             continue;
         }
@@ -258,61 +255,41 @@ fn read_map_and_moves(lines: io::Lines<io::BufReader<File>>) -> (Map, Vec<Move>)
                 moves.push(match &cap[0] {
                     "R" => Move::TurnRight,
                     "L" => Move::TurnLeft,
-                    num => Move::Forward(num.parse::<i32>().unwrap()),
+                    num => Move::Forward(num.parse::<i32>()?),
                 });
             }
         }
     }
-    (map, moves)
+    Ok((map, moves))
 }
 
-fn find_start(map: &Map) -> State {
+fn find_start(map: &Map) -> Result<State> {
     for row in 0..map.len() {
         for col in 0..map[row].len() {
             if map[row][col] == EMPTY {
-                return State {
+                return Ok(State {
                     row,
                     col,
                     dir: Orientation::Right,
-                };
+                });
             }
         }
     }
-    panic!("Found no starting point");
+    bail!(AdventError::invalid("found no starting point"));
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let (map, moves) = read_map_and_moves(lines);
-    let mut state = find_start(&map);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (map, moves) = read_map_and_moves(lines)?;
+    let mut state = find_start(&map)?;
     state.walk(&map, &moves);
     let password = state.calc_password();
-    format!("{password}")
+    Ok(format!("{password}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let (map, moves) = read_map_and_moves(lines);
-    let mut state = find_start(&map);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (map, moves) = read_map_and_moves(lines)?;
+    let mut state = find_start(&map)?;
     state.cubic_walk(&map, &moves);
     let password = state.calc_password();
-    format!("{password}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_22_1() {
-        let lines = read_lines("data/2022/22.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "20494");
-    }
-
-    #[test]
-    fn test_2022_22_2() {
-        let lines = read_lines("data/2022/22.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "55343");
-    }
+    Ok(format!("{password}"))
 }
@@ -1,28 +1,23 @@
-use std::{fs::File, io};
-
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use std::io::{self, BufRead};
 
 use std::collections::{HashMap, HashSet};
 
-fn read_map(mut lines: io::Lines<io::BufReader<File>>) -> HashSet<(i64, i64)> {
-    let mut row = 0;
+use anyhow::Result;
+
+solution!(2022, 23, "Unstable Diffusion", parse = read_map);
+
+fn read_map(lines: io::Lines<impl BufRead>) -> Result<HashSet<(i64, i64)>> {
     let mut elves = HashSet::new();
-    while let Some(Ok(s)) = lines.next() {
+    for (row, s) in (0..).zip(lines) {
+        let s = s?;
         let bytes = s.as_bytes();
         for col in 0..bytes.len() {
             if bytes[col] == 35 {
                 elves.insert((row, col as i64));
             }
         }
-        row += 1;
     }
-    elves
+    Ok(elves)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -74,7 +69,7 @@ impl State {
                             || self.elves.contains(&(e.0 - 1, e.1))
                             || self.elves.contains(&(e.0 - 1, e.1 + 1)))
                         {
-                            if let Some(_) = moves.insert((e.0 - 1, e.1), *e) {
+                            if moves.insert((e.0 - 1, e.1), *e).is_some() {
                                 moves.remove(&(e.0 - 1, e.1));
                             }
                             break;
@@ -85,7 +80,7 @@ impl State {
                             || self.elves.contains(&(e.0 + 1, e.1))
                             || self.elves.contains(&(e.0 + 1, e.1 + 1)))
                         {
-                            if let Some(_) = moves.insert((e.0 + 1, e.1), *e) {
+                            if moves.insert((e.0 + 1, e.1), *e).is_some() {
                                 moves.remove(&(e.0 + 1, e.1));
                             }
                             break;
@@ -97,7 +92,7 @@ impl State {
                             || self.elves.contains(&(e.0, e.1 - 1))
                             || self.elves.contains(&(e.0 + 1, e.1 - 1)))
                         {
-                            if let Some(_) = moves.insert((e.0, e.1 - 1), *e) {
+                            if moves.insert((e.0, e.1 - 1), *e).is_some() {
                                 moves.remove(&(e.0, e.1 - 1));
                             }
                             break;
@@ -108,7 +103,7 @@ impl State {
                             || self.elves.contains(&(e.0, e.1 + 1))
                             || self.elves.contains(&(e.0 + 1, e.1 + 1)))
                         {
-                            if let Some(_) = moves.insert((e.0, e.1 + 1), *e) {
+                            if moves.insert((e.0, e.1 + 1), *e).is_some() {
                                 moves.remove(&(e.0, e.1 + 1));
                             }
                             break;
@@ -152,9 +147,9 @@ impl State {
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut state = State {
-        elves: read_map(lines),
+        elves: read_map(lines)?,
         direction: Direction::North,
     };
 
@@ -163,12 +158,12 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
         state.move_elves(&moves);
     }
     let result = state.count_space();
-    format!("{result}")
+    Ok(format!("{result}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut state = State {
-        elves: read_map(lines),
+        elves: read_map(lines)?,
         direction: Direction::North,
     };
 
@@ -181,25 +176,5 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
         state.move_elves(&moves);
         round += 1;
     }
-    format!("{round}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_23_1() {
-        let lines = read_lines("data/2022/23.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "4005");
-    }
-
-    #[test]
-    fn test_2022_23_2() {
-        let lines = read_lines("data/2022/23.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1008");
-    }
+    Ok(format!("{round}"))
 }
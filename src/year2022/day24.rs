@@ -1,19 +1,13 @@
-use std::{fs::File, io};
-
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use std::io::{self, BufRead};
 
 use num::traits::Zero;
 use std::fmt;
-use std::{
-    collections::HashSet,
-    ops::{Add, Sub},
-};
+use std::ops::{Add, Sub};
+
+use crate::{search::bfs, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2022, 24, "Blizzard Basin", parse = read_map);
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 struct Point<T> {
@@ -95,12 +89,15 @@ impl Map {
     }
 }
 
-fn read_map(mut lines: io::Lines<io::BufReader<File>>) -> Map {
-    let mut row = 0;
+fn read_map(lines: io::Lines<impl BufRead>) -> Result<Map> {
     let mut map = Map::new();
     let mut xdim = 0;
-    while let Some(Ok(s)) = lines.next() {
+    for (row, s) in (0..).zip(lines) {
+        let s = s?;
         let bytes = s.as_bytes();
+        if bytes.len() < 4 || (row > 0 && bytes.len() != xdim + 2) {
+            bail!(AdventError::at_line(row as usize, "unexpected row length"));
+        }
         if row == 0 {
             for col in 1..bytes.len() {
                 if bytes[col] == 46 {
@@ -132,102 +129,40 @@ fn read_map(mut lines: io::Lines<io::BufReader<File>>) -> Map {
                 }
             }
         }
-        row += 1;
     }
-    map
+    Ok(map)
 }
 
-struct State {
-    free: HashSet<Point<i32>>,
-    time: i32,
-}
-
-impl State {
-    fn new(start: Point<i32>) -> Self {
-        let mut state = Self {
-            free: HashSet::new(),
-            time: 0,
-        };
-        state.free.insert(start);
-        state
-    }
-
-    fn time_to_get_to(&mut self, end: Point<i32>, map: &Map) {
-        loop {
-            if self.free.contains(&end) {
-                break;
-            }
-            self.time += 1;
-            let mut free = HashSet::new();
-            for p in &self.free {
-                self.add_to_free(&p, &mut free, map);
-                self.add_to_free(&p.plus(1, 0), &mut free, map);
-                if p.x != 0 {
-                    self.add_to_free(&p.plus(0, -1), &mut free, map);
-                    self.add_to_free(&p.plus(-1, 0), &mut free, map);
-                    self.add_to_free(&p.plus(0, 1), &mut free, map);
-                }
-            }
-            if free.is_empty() {
-                println!("Sorry, no solution found!");
-                break;
+/// Earliest time at which `end` can be reached when leaving `start` at `time`
+fn time_to_get_to(start: &Point<i32>, end: &Point<i32>, time: i32, map: &Map) -> Result<i32> {
+    let steps = bfs(
+        [(start.clone(), time)],
+        |(p, time)| {
+            let mut moves = vec![p.clone(), p.plus(1, 0)];
+            if p.x != 0 {
+                moves.extend([p.plus(0, -1), p.plus(-1, 0), p.plus(0, 1)]);
             }
-            self.free = free;
-        }
-    }
-
-    fn add_to_free(&self, p: &Point<i32>, free: &mut HashSet<Point<i32>>, map: &Map) {
-        if free.contains(p) {
-            return;
-        }
-        if map.is_free(p, self.time) {
-            free.insert(p.clone());
-        }
-    }
+            moves
+                .into_iter()
+                .filter(|p| map.is_free(p, time + 1))
+                .map(|p| (p, time + 1))
+                .collect()
+        },
+        |(p, _)| p == end,
+    );
+    Ok(time + steps.ok_or(AdventError::NoSolution)? as i32)
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let map = read_map(lines);
-    let mut state = State::new(map.start.clone());
-    state.time_to_get_to(map.end.clone(), &map);
-    format!("{}", state.time)
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let map = read_map(lines)?;
+    let time = time_to_get_to(&map.start, &map.end, 0, &map)?;
+    Ok(format!("{time}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut map = read_map(lines);
-    let mut state = State::new(map.start.clone());
-    state.time_to_get_to(map.end.clone(), &map);
-    let start = map.start;
-    map.start = map.end;
-    map.end = start;
-    state.free = HashSet::new();
-    state.free.insert(map.start.clone());
-    state.time_to_get_to(map.end.clone(), &map);
-    let start = map.start;
-    map.start = map.end;
-    map.end = start;
-    state.free = HashSet::new();
-    state.free.insert(map.start.clone());
-    state.time_to_get_to(map.end.clone(), &map);
-    format!("{}", state.time)
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_24_1() {
-        let lines = read_lines("data/2022/24.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "334");
-    }
-
-    #[test]
-    fn test_2022_24_2() {
-        let lines = read_lines("data/2022/24.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "934");
-    }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let map = read_map(lines)?;
+    let time = time_to_get_to(&map.start, &map.end, 0, &map)?;
+    let time = time_to_get_to(&map.end, &map.start, time, &map)?;
+    let time = time_to_get_to(&map.start, &map.end, time, &map)?;
+    Ok(format!("{time}"))
 }
@@ -1,13 +1,17 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use std::{error::Error, fmt, str::FromStr};
 
-use std::{fmt, str::FromStr};
+use crate::parse_lines;
+use anyhow::Result;
+
+solution!(
+    2022,
+    25,
+    "Full of Hot Air",
+    parse = read_numbers,
+    part_1_only
+);
 
 #[derive(Debug, Clone)]
 enum Digit {
@@ -23,6 +27,16 @@ enum DigitError {
     InvalidDigit,
 }
 
+impl fmt::Display for DigitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DigitError::InvalidDigit => write!(f, "invalid SNAFU digit"),
+        }
+    }
+}
+
+impl Error for DigitError {}
+
 impl FromStr for Digit {
     type Err = DigitError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -157,31 +171,13 @@ impl From<i64> for Snafu {
     }
 }
 
-fn read_numbers(lines: io::Lines<io::BufReader<File>>) -> Vec<i64> {
-    lines
-        .into_iter()
-        .filter_map(|s| s.ok())
-        .map(|s| s.parse::<Snafu>().unwrap())
-        .map(|s| (&s).into())
-        .collect()
+fn read_numbers(lines: io::Lines<impl BufRead>) -> Result<Vec<i64>> {
+    parse_lines(lines, |s| Ok((&s.parse::<Snafu>()?).into()))
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let numbers = read_numbers(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let numbers = read_numbers(lines)?;
     let sum: i64 = numbers.iter().sum();
     let snafu_sum: Snafu = sum.into();
-    format!("{snafu_sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_25_1() {
-        let lines = read_lines("data/2022/25.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "2-==10--=-0101==1201");
-    }
+    Ok(format!("{snafu_sum}"))
 }
@@ -1,12 +1,9 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{parse_lines, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2022, 3, "Rucksack Reorganization", parse = read_rucksacks);
 
 fn byte_to_priority(byte: u8) -> i32 {
     if byte >= 97 {
@@ -29,16 +26,23 @@ fn find_double(bytes: Vec<u8>) -> u8 {
     0
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let total_priorities: i32 = lines
+fn read_rucksacks(lines: io::Lines<impl BufRead>) -> Result<Vec<Vec<u8>>> {
+    parse_lines(lines, |s| {
+        if let Some(col) = s.find(|c: char| !c.is_ascii_alphabetic()) {
+            bail!(AdventError::parse(0, col + 1, "expected item letter"));
+        }
+        Ok(s.as_bytes().to_vec())
+    })
+}
+
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let total_priorities: i32 = read_rucksacks(lines)?
         .into_iter()
-        .filter_map(|s| s.ok())
-        .map(|s| s.into_bytes())
         .map(find_double)
         .map(byte_to_priority)
         .sum();
 
-    format!("{total_priorities}")
+    Ok(format!("{total_priorities}"))
 }
 
 fn group_priority(one: &[u8], two: &[u8], three: &[u8]) -> i32 {
@@ -56,12 +60,8 @@ fn group_priority(one: &[u8], two: &[u8], three: &[u8]) -> i32 {
     0
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let all_rucksacks: Vec<Vec<u8>> = lines
-        .into_iter()
-        .filter_map(|s| s.ok())
-        .map(|s| s.into_bytes())
-        .collect();
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let all_rucksacks = read_rucksacks(lines)?;
 
     let mut sum = 0;
     for i in 0..all_rucksacks.len() / 3 {
@@ -73,25 +73,5 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
         );
     }
 
-    format!("{sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_3_1() {
-        let lines = read_lines("data/2022/3.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "7597");
-    }
-
-    #[test]
-    fn test_2022_3_2() {
-        let lines = read_lines("data/2022/3.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "2607");
-    }
+    Ok(format!("{sum}"))
 }
@@ -1,12 +1,9 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{next_line, parse_lines_after, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2022, 5, "Supply Stacks", parse = read_stacks_and_moves);
 
 fn parse_init_stack(lines: Vec<String>) -> Vec<Vec<u8>> {
     let mut stacks = Vec::new();
@@ -16,8 +13,9 @@ fn parse_init_stack(lines: Vec<String>) -> Vec<Vec<u8>> {
         let idx = i * 4 + 1;
         for j in 0..len {
             let bytes = lines[len - j - 1].as_bytes();
-            if bytes[idx] != 32 {
-                stack.push(bytes[idx]);
+            match bytes.get(idx) {
+                None | Some(b' ') => {}
+                Some(&c) => stack.push(c),
             }
         }
         stacks.push(stack);
@@ -25,100 +23,88 @@ fn parse_init_stack(lines: Vec<String>) -> Vec<Vec<u8>> {
     stacks
 }
 
-fn parse_move(s: String) -> (usize, usize, usize) {
-    let mut parts = s.split(" ");
-    parts.advance_by(1).unwrap();
-    let count: usize = parts.next().unwrap().parse().unwrap();
-    parts.advance_by(1).unwrap();
-    let from: usize = parts.next().unwrap().parse().unwrap();
-    parts.advance_by(1).unwrap();
-    let to: usize = parts.next().unwrap().parse().unwrap();
-    (count, from, to)
+type Move = (usize, usize, usize);
+
+fn parse_move(s: &str) -> Result<Move> {
+    let parts: Vec<&str> = s.split(" ").collect();
+    let ["move", count, "from", from, "to", to] = parts[..] else {
+        bail!("expected 'move <n> from <stack> to <stack>'");
+    };
+    let (count, from, to) = (count.parse()?, from.parse()?, to.parse()?);
+    if !(1..=9).contains(&from) || !(1..=9).contains(&to) {
+        bail!("stacks are numbered from 1 to 9");
+    }
+    Ok((count, from, to))
 }
 
-fn make_move(stacks: Vec<Vec<u8>>, m: (usize, usize, usize)) -> Vec<Vec<u8>> {
+fn check_move(stacks: &[Vec<u8>], m: Move) -> Result<()> {
+    if stacks[m.1 - 1].len() < m.0 {
+        bail!(AdventError::invalid(format!(
+            "can't move {} crates from stack {}",
+            m.0, m.1
+        )));
+    }
+    Ok(())
+}
+
+fn make_move(stacks: Vec<Vec<u8>>, m: Move) -> Result<Vec<Vec<u8>>> {
+    check_move(&stacks, m)?;
     let mut stacks = stacks;
     for _ in 0..m.0 {
         let c = stacks[m.1 - 1].pop().unwrap();
         stacks[m.2 - 1].push(c);
     }
-    stacks
+    Ok(stacks)
 }
 
-fn make_move_9001(stacks: Vec<Vec<u8>>, m: (usize, usize, usize)) -> Vec<Vec<u8>> {
+fn make_move_9001(stacks: Vec<Vec<u8>>, m: Move) -> Result<Vec<Vec<u8>>> {
+    check_move(&stacks, m)?;
     let mut stacks = stacks;
     let len = stacks[m.1 - 1].len();
     for i in len - m.0..len {
-        let c = stacks[m.1 - 1][i].clone();
+        let c = stacks[m.1 - 1][i];
         stacks[m.2 - 1].push(c);
     }
     for _ in 0..m.0 {
         stacks[m.1 - 1].pop();
     }
-    stacks
+    Ok(stacks)
 }
 
 fn get_tops(stacks: &mut Vec<Vec<u8>>) -> String {
     let mut tops = Vec::new();
     for v in stacks {
-        let c = v.pop().unwrap();
-        tops.push(c);
+        if let Some(c) = v.pop() {
+            tops.push(c);
+        }
     }
-    String::from_utf8(tops).unwrap()
+    String::from_utf8_lossy(&tops).into_owned()
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut lines_iter = lines.into_iter();
-
+fn read_stacks_and_moves(mut lines: io::Lines<impl BufRead>) -> Result<(Vec<Vec<u8>>, Vec<Move>)> {
     let mut init_stack_lines = Vec::new();
     for _ in 0..8 {
-        init_stack_lines.push(lines_iter.next().unwrap().unwrap());
+        init_stack_lines.push(next_line(&mut lines)?);
     }
-    lines_iter.advance_by(2).unwrap();
+    next_line(&mut lines)?;
+    next_line(&mut lines)?;
     let stacks = parse_init_stack(init_stack_lines);
-
-    let mut stacks = lines_iter
-        .map(|x| parse_move(x.unwrap()))
-        .fold(stacks, make_move);
-
-    let top_crates = get_tops(&mut stacks);
-    format!("{top_crates}")
+    let moves = parse_lines_after(lines, 10, parse_move)?;
+    Ok((stacks, moves))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut lines_iter = lines.into_iter();
-
-    let mut init_stack_lines = Vec::new();
-    for _ in 0..8 {
-        init_stack_lines.push(lines_iter.next().unwrap().unwrap());
-    }
-    lines_iter.advance_by(2).unwrap();
-    let stacks = parse_init_stack(init_stack_lines);
-
-    let mut stacks = lines_iter
-        .map(|x| parse_move(x.unwrap()))
-        .fold(stacks, make_move_9001);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (stacks, moves) = read_stacks_and_moves(lines)?;
+    let mut stacks = moves.into_iter().try_fold(stacks, make_move)?;
 
     let top_crates = get_tops(&mut stacks);
-    format!("{top_crates}")
+    Ok(top_crates.to_string())
 }
 
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_5_1() {
-        let lines = read_lines("data/2022/5.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "VWLCWGSDQ");
-    }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (stacks, moves) = read_stacks_and_moves(lines)?;
+    let mut stacks = moves.into_iter().try_fold(stacks, make_move_9001)?;
 
-    #[test]
-    fn test_2022_5_2() {
-        let lines = read_lines("data/2022/5.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "TCGLQSLPW");
-    }
+    let top_crates = get_tops(&mut stacks);
+    Ok(top_crates.to_string())
 }
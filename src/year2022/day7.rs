@@ -1,12 +1,14 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{parse_lines, AdventError};
+use anyhow::{bail, Result};
+
+solution!(
+    2022,
+    7,
+    "No Space Left On Device",
+    parse = parse_file_structure
+);
 
 #[derive(Debug, Clone)]
 enum Entity {
@@ -29,35 +31,29 @@ enum Command {
     ListItem(Entity, String, Option<usize>),
 }
 
-fn add_node(files: &mut Vec<Node>, node: Node, dir_idx: usize) {
+fn add_node(files: &mut Vec<Node>, node: Node, dir_idx: usize) -> Result<()> {
     let idx = files.len();
+    let Entity::Dir(v) = &mut files[dir_idx].entity else {
+        bail!(AdventError::invalid(format!(
+            "{} is no directory",
+            files[dir_idx].name
+        )));
+    };
+    v.push(idx);
     files.push(node);
-    match &mut files[dir_idx].entity {
-        Entity::Dir(v) => v.push(idx),
-        _ => panic!("Tried to add entity to none-dir"),
-    }
+    Ok(())
 }
 
-fn parse_command(s: &str) -> Command {
-    let mut parts = s.split(" ");
-    match parts.next().unwrap() {
-        "$" => match parts.next().unwrap() {
-            "cd" => {
-                let name = parts.next().unwrap().to_string();
-                Command::Cd(name)
-            }
-            "ls" => Command::Ls,
-            cmd => panic!("unknown command '{cmd}'"),
-        },
-        first_str => {
-            let name = parts.next().unwrap().to_string();
-            if let Ok(num) = first_str.parse() {
-                Command::ListItem(Entity::File, name, Some(num))
-            } else {
-                Command::ListItem(Entity::Dir(Vec::new()), name, None)
-            }
-        }
-    }
+fn parse_command(s: &str) -> Result<Command> {
+    let parts: Vec<&str> = s.split(" ").collect();
+    Ok(match parts[..] {
+        ["$", "cd", name] => Command::Cd(name.to_string()),
+        ["$", "ls"] => Command::Ls,
+        ["$", ..] => bail!("unknown command '{s}'"),
+        ["dir", name] => Command::ListItem(Entity::Dir(Vec::new()), name.to_string(), None),
+        [size, name] => Command::ListItem(Entity::File, name.to_string(), Some(size.parse()?)),
+        _ => bail!("expected command or directory listing"),
+    })
 }
 
 fn calc_dir_sizes(node_idx: usize, files: &mut Vec<Node>, total_sum: usize) -> (usize, usize) {
@@ -85,8 +81,8 @@ fn calc_dir_sizes(node_idx: usize, files: &mut Vec<Node>, total_sum: usize) -> (
     }
 }
 
-fn parse_file_structure(lines: io::Lines<io::BufReader<File>>) -> Vec<Node> {
-    let lines: Vec<String> = lines.into_iter().map(|x| x.unwrap()).collect();
+fn parse_file_structure(lines: io::Lines<impl BufRead>) -> Result<Vec<Node>> {
+    let commands = parse_lines(lines, parse_command)?;
     let mut files = Vec::new();
     files.push(Node {
         entity: Entity::Dir(Vec::new()),
@@ -95,25 +91,30 @@ fn parse_file_structure(lines: io::Lines<io::BufReader<File>>) -> Vec<Node> {
         parent: None,
     });
     let mut dir_idx = 0;
-    for line in lines {
-        match parse_command(&line) {
+    for (line, command) in commands.into_iter().enumerate() {
+        match command {
             Command::Cd(name) => match name.as_str() {
                 "/" => {
                     dir_idx = 0;
                 }
                 ".." => {
-                    dir_idx = files[dir_idx].parent.unwrap();
+                    dir_idx = files[dir_idx].parent.ok_or_else(|| {
+                        AdventError::at_line(line, "can't leave the root directory")
+                    })?;
+                }
+                dir => {
+                    let Entity::Dir(entities) = &files[dir_idx].entity else {
+                        bail!(AdventError::at_line(line, "not in a directory"));
+                    };
+                    dir_idx = *entities
+                        .iter()
+                        .find(|e| {
+                            files[**e].name == dir && matches!(files[**e].entity, Entity::Dir(_))
+                        })
+                        .ok_or_else(|| {
+                            AdventError::at_line(line, format!("no directory named {dir}"))
+                        })?;
                 }
-                dir => match &files[dir_idx].entity {
-                    Entity::Dir(entities) => {
-                        for e in entities {
-                            if files[*e].name == dir {
-                                dir_idx = *e;
-                            }
-                        }
-                    }
-                    _ => panic!("{dir_idx} is no directory"),
-                },
             },
             Command::Ls => {}
             Command::ListItem(entity, name, size) => add_node(
@@ -125,20 +126,20 @@ fn parse_file_structure(lines: io::Lines<io::BufReader<File>>) -> Vec<Node> {
                     parent: Some(dir_idx),
                 },
                 dir_idx,
-            ),
+            )?,
         }
     }
 
-    files
+    Ok(files)
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut files = parse_file_structure(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut files = parse_file_structure(lines)?;
     let (_size, total_sum) = calc_dir_sizes(0, &mut files, 0);
-    format!("{total_sum}")
+    Ok(format!("{total_sum}"))
 }
 
-fn find_smallest_dir_size(files: &Vec<Node>, required_space: usize) -> usize {
+fn find_smallest_dir_size(files: &[Node], required_space: usize) -> usize {
     let mut smallest_dir_size = files[0].size.unwrap();
     for i in 1..files.len() {
         match &files[i].entity {
@@ -154,33 +155,13 @@ fn find_smallest_dir_size(files: &Vec<Node>, required_space: usize) -> usize {
     smallest_dir_size
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
     let total_size = 70000000;
     let required_space = 30000000;
-    let mut files = parse_file_structure(lines);
+    let mut files = parse_file_structure(lines)?;
     let (size, _total_sum) = calc_dir_sizes(0, &mut files, 0);
-    let to_be_removed = required_space - (total_size - size);
+    let to_be_removed = (required_space + size).saturating_sub(total_size);
 
     let smallest_dir_size = find_smallest_dir_size(&files, to_be_removed);
-    format!("{smallest_dir_size}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_7_1() {
-        let lines = read_lines("data/2022/7.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "1232307");
-    }
-
-    #[test]
-    fn test_2022_7_2() {
-        let lines = read_lines("data/2022/7.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "7268994");
-    }
+    Ok(format!("{smallest_dir_size}"))
 }
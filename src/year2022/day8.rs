@@ -1,159 +1,67 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{AdventError, Grid};
+use anyhow::{bail, Result};
 
-fn read_grid(lines: io::Lines<io::BufReader<File>>) -> Vec<Vec<u8>> {
-    lines
-        .into_iter()
-        .map(|s| {
-            s.unwrap()
-                .as_bytes()
-                .into_iter()
-                .map(|x| *x - 48)
-                .collect::<Vec<u8>>()
-        })
-        .collect()
-}
+solution!(2022, 8, "Treetop Tree House", parse = read_grid);
 
-fn find_invisible(grid: &Vec<Vec<u8>>) -> usize {
-    let mut count = 0;
-    let xdim = grid[0].len();
-    let ydim = grid.len();
-    for x in 1..xdim - 1 {
-        for y in 1..ydim - 1 {
-            let mut invisible = false;
-            let height = grid[y][x];
-            for i in 0..x {
-                if height <= grid[y][i] {
-                    invisible = true;
-                    break;
-                }
-            }
-            if !invisible {
-                continue;
-            }
-            invisible = false;
-            for i in 0..y {
-                if height <= grid[i][x] {
-                    invisible = true;
-                    break;
-                }
-            }
-            if !invisible {
-                continue;
-            }
-            invisible = false;
-            for i in x + 1..xdim {
-                if height <= grid[y][i] {
-                    invisible = true;
-                    break;
-                }
-            }
-            if !invisible {
-                continue;
-            }
-            invisible = false;
-            for i in y + 1..ydim {
-                if height <= grid[i][x] {
-                    invisible = true;
-                    break;
-                }
-            }
-            if invisible {
-                count += 1;
-            }
-        }
+fn read_grid(lines: io::Lines<impl BufRead>) -> Result<Grid<u8>> {
+    let grid = Grid::parse_digits(lines)?;
+    if grid.width() == 0 || grid.height() == 0 {
+        bail!(AdventError::invalid("empty grid"));
     }
-    xdim * ydim - count
+    Ok(grid)
 }
 
-fn calc_max_scenic_score(grid: &Vec<Vec<u8>>) -> usize {
-    let mut score = 0;
-    let xdim = grid[0].len();
-    let ydim = grid.len();
-    for x in 1..xdim - 1 {
-        for y in 1..ydim - 1 {
-            let height = grid[y][x];
-
-            let mut left = 0;
-            for i in (0..x).rev() {
-                if height >= grid[y][i] {
-                    left += 1;
-                }
-                if height <= grid[y][i] {
-                    break;
-                }
-            }
-
-            let mut top = 0;
-            for i in (0..y).rev() {
-                if height >= grid[i][x] {
-                    top += 1;
-                }
-                if height <= grid[i][x] {
-                    break;
-                }
-            }
-
-            let mut right = 0;
-            for i in x + 1..xdim {
-                if height >= grid[y][i] {
-                    right += 1;
-                }
-                if height <= grid[y][i] {
-                    break;
-                }
-            }
-
-            let mut bottom = 0;
-            for i in y + 1..ydim {
-                if height >= grid[i][x] {
-                    bottom += 1;
-                }
-                if height <= grid[i][x] {
-                    break;
-                }
-            }
-            score = score.max(left * right * top * bottom);
-        }
-    }
-    score
+/// Heights of the trees seen from the tree at `x`, `y` looking left, up,
+/// right and down, nearest first
+fn lines_of_sight(grid: &Grid<u8>, x: usize, y: usize) -> [Vec<u8>; 4] {
+    let row = grid.row(y);
+    let column: Vec<u8> = grid.column(x).copied().collect();
+    [
+        row[..x].iter().rev().copied().collect(),
+        column[..y].iter().rev().copied().collect(),
+        row[x + 1..].to_vec(),
+        column[y + 1..].to_vec(),
+    ]
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let grid = read_grid(lines);
-    let count = find_invisible(&grid);
-    format!("{count}")
+fn count_visible(grid: &Grid<u8>) -> usize {
+    grid.iter()
+        .filter(|(pos, &height)| {
+            lines_of_sight(grid, pos.x as usize, pos.y as usize)
+                .iter()
+                .any(|trees| trees.iter().all(|&tree| tree < height))
+        })
+        .count()
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let grid = read_grid(lines);
-    let score = calc_max_scenic_score(&grid);
-    format!("{score}")
+fn calc_max_scenic_score(grid: &Grid<u8>) -> usize {
+    grid.iter()
+        .map(|(pos, &height)| {
+            lines_of_sight(grid, pos.x as usize, pos.y as usize)
+                .iter()
+                .map(|trees| {
+                    // the view ends at the first tree at least as tall
+                    trees
+                        .iter()
+                        .position(|&tree| tree >= height)
+                        .map_or(trees.len(), |idx| idx + 1)
+                })
+                .product()
+        })
+        .max()
+        .unwrap_or(0)
 }
 
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_8_1() {
-        let lines = read_lines("data/2022/8.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "1854");
-    }
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let grid = read_grid(lines)?;
+    let count = count_visible(&grid);
+    Ok(format!("{count}"))
+}
 
-    #[test]
-    fn test_2022_8_2() {
-        let lines = read_lines("data/2022/8.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "527340");
-    }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let grid = read_grid(lines)?;
+    let score = calc_max_scenic_score(&grid);
+    Ok(format!("{score}"))
 }
@@ -1,20 +1,23 @@
 use std::collections::HashSet;
-use std::{fs::File, io};
-
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
-
-fn parse_move(s: String) -> (String, i32) {
-    let mut parts = s.split(" ");
-    (
-        parts.next().unwrap().to_owned(),
-        parts.next().unwrap().parse().unwrap(),
-    )
+use std::io::{self, BufRead};
+
+use crate::parse_lines;
+use anyhow::{anyhow, bail, Result};
+
+solution!(2022, 9, "Rope Bridge", parse = parse_moves);
+
+fn parse_move(s: &str) -> Result<((i32, i32), i32)> {
+    let (direction, count) = s
+        .split_once(" ")
+        .ok_or_else(|| anyhow!("expected direction and step count"))?;
+    let step = match direction {
+        "R" => (1, 0),
+        "L" => (-1, 0),
+        "D" => (0, -1),
+        "U" => (0, 1),
+        _ => bail!("invalid move '{direction}'"),
+    };
+    Ok((step, count.parse()?))
 }
 
 struct Rope {
@@ -33,14 +36,8 @@ impl Rope {
         Self { knots, visited }
     }
 
-    fn make_move(&mut self, m: &(String, i32)) {
-        let step = match m.0.as_str() {
-            "R" => (1, 0),
-            "L" => (-1, 0),
-            "D" => (0, -1),
-            "U" => (0, 1),
-            _ => panic!("invalid move"),
-        };
+    fn make_move(&mut self, m: &((i32, i32), i32)) {
+        let step = m.0;
         for _ in 0..m.1 {
             self.knots[0].0 += step.0;
             self.knots[0].1 += step.1;
@@ -73,52 +70,28 @@ impl Rope {
     }
 }
 
-fn parse_moves(lines: io::Lines<io::BufReader<File>>) -> Vec<(String, i32)> {
-    lines
-        .into_iter()
-        .filter_map(|s| s.ok())
-        .map(parse_move)
-        .collect()
+fn parse_moves(lines: io::Lines<impl BufRead>) -> Result<Vec<((i32, i32), i32)>> {
+    parse_lines(lines, parse_move)
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let moves = parse_moves(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let moves = parse_moves(lines)?;
 
     let mut rope = Rope::new(2);
     for m in moves {
         rope.make_move(&m);
     }
 
-    format!("{}", rope.count())
+    Ok(format!("{}", rope.count()))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let moves = parse_moves(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let moves = parse_moves(lines)?;
 
     let mut rope = Rope::new(10);
     for m in moves {
         rope.make_move(&m);
     }
 
-    format!("{}", rope.count())
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2022_9_1() {
-        let lines = read_lines("data/2022/9.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "5683");
-    }
-
-    #[test]
-    fn test_2022_9_2() {
-        let lines = read_lines("data/2022/9.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "2372");
-    }
+    Ok(format!("{}", rope.count()))
 }
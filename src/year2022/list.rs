@@ -2,5 +2,5 @@
 pub enum List {
     Empty,
     Num(u8),
-    Array(Vec<Box<List>>),
+    Array(Vec<List>),
 }
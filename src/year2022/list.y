@@ -5,12 +5,12 @@ List -> Result<List, ()>:
     | '(' ')' { Ok(List::Empty) }
     ;
 
-Item -> Result< Box<List>, ()>:
-        Num { Ok(Box::new($1?)) }
-    |   List { Ok(Box::new($1?)) }
+Item -> Result<List, ()>:
+        Num { $1 }
+    |   List { $1 }
     ;
 
-Itemlist -> Result< Vec<Box<List>>, ()>:
+Itemlist -> Result<Vec<List>, ()>:
         Itemlist ',' Item {
             let mut v = $1?;
             v.push($3?);
@@ -1,4 +1,4 @@
-use std::{fs::File, io};
+use crate::Solution;
 
 mod list;
 
@@ -28,33 +28,30 @@ mod day7;
 mod day8;
 mod day9;
 
-pub fn execute(day: u32, part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match day {
-        1 => day1::execute(part, lines),
-        2 => day2::execute(part, lines),
-        3 => day3::execute(part, lines),
-        4 => day4::execute(part, lines),
-        5 => day5::execute(part, lines),
-        6 => day6::execute(part, lines),
-        7 => day7::execute(part, lines),
-        8 => day8::execute(part, lines),
-        9 => day9::execute(part, lines),
-        10 => day10::execute(part, lines),
-        11 => day11::execute(part, lines),
-        12 => day12::execute(part, lines),
-        13 => day13::execute(part, lines),
-        14 => day14::execute(part, lines),
-        15 => day15::execute(part, lines),
-        16 => day16::execute(part, lines),
-        17 => day17::execute(part, lines),
-        18 => day18::execute(part, lines),
-        19 => day19::execute(part, lines),
-        20 => day20::execute(part, lines),
-        21 => day21::execute(part, lines),
-        22 => day22::execute(part, lines),
-        23 => day23::execute(part, lines),
-        24 => day24::execute(part, lines),
-        25 => day25::execute(part, lines),
-        _ => format!("Error: day {day} not found"),
-    }
-}
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day1::Puzzle,
+    &day2::Puzzle,
+    &day3::Puzzle,
+    &day4::Puzzle,
+    &day5::Puzzle,
+    &day6::Puzzle,
+    &day7::Puzzle,
+    &day8::Puzzle,
+    &day9::Puzzle,
+    &day10::Puzzle,
+    &day11::Puzzle,
+    &day12::Puzzle,
+    &day13::Puzzle,
+    &day14::Puzzle,
+    &day15::Puzzle,
+    &day16::Puzzle,
+    &day17::Puzzle,
+    &day18::Puzzle,
+    &day19::Puzzle,
+    &day20::Puzzle,
+    &day21::Puzzle,
+    &day22::Puzzle,
+    &day23::Puzzle,
+    &day24::Puzzle,
+    &day25::Puzzle,
+];
@@ -1,12 +1,8 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use anyhow::Result;
+
+solution!(2023, 1, "Trebuchet?!");
 
 fn get_num(bytes: &[u8]) -> i32 {
     let mut num = 0;
@@ -24,13 +20,13 @@ fn get_num(bytes: &[u8]) -> i32 {
     0
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut sum = 0;
     for line in lines {
-        let num = get_num(line.unwrap().as_bytes());
+        let num = get_num(line?.as_bytes());
         sum += num;
     }
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
 fn is_digit2(i: usize, b: &[u8]) -> Option<u8> {
@@ -78,31 +74,11 @@ fn get_num2(bytes: &[u8]) -> i32 {
     0
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut sum = 0;
     for line in lines {
-        let num = get_num2(line.unwrap().as_bytes());
+        let num = get_num2(line?.as_bytes());
         sum += num;
     }
-    format!("{sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_1_1() {
-        let lines = read_lines("data/2023/1.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "54951");
-    }
-
-    #[test]
-    fn test_2023_1_2() {
-        let lines = read_lines("data/2023/1.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "55218");
-    }
+    Ok(format!("{sum}"))
 }
@@ -1,12 +1,9 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{parse_lines, polygon::interior_points, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2023, 10, "Pipe Maze", parse = read_map);
 
 #[derive(Clone)]
 enum Direction {
@@ -18,15 +15,15 @@ enum Direction {
 
 use Direction::*;
 
-fn find_start(map: &[Vec<u8>]) -> (usize, usize) {
+fn find_start(map: &[Vec<u8>]) -> Result<(usize, usize)> {
     for i in 0..map.len() {
         for j in 0..map[i].len() {
             if map[i][j] == b'S' {
-                return (i, j);
+                return Ok((i, j));
             }
         }
     }
-    panic!("No start found");
+    bail!(AdventError::invalid("no start found"));
 }
 
 #[derive(Clone)]
@@ -41,263 +38,89 @@ impl Position {
         Self { x, y, dir }
     }
 
-    fn next(&mut self, map: &[Vec<u8>]) {
-        match (map[self.x][self.y], &self.dir) {
-            (b'|', Up) => {
-                self.dir = Up;
-                self.x -= 1;
-            }
-            (b'7', Up) => {
-                self.dir = Left;
-                self.y -= 1;
-            }
-            (b'F', Up) => {
-                self.dir = Right;
-                self.y += 1;
-            }
-            (b'7', Right) => {
-                self.dir = Down;
-                self.x += 1;
-            }
-            (b'-', Right) => {
-                self.dir = Right;
-                self.y += 1;
-            }
-            (b'J', Right) => {
-                self.dir = Up;
-                self.x -= 1;
-            }
-            (b'|', Down) => {
-                self.dir = Down;
-                self.x += 1;
-            }
-            (b'L', Down) => {
-                self.dir = Right;
-                self.y += 1;
-            }
-            (b'J', Down) => {
-                self.dir = Left;
-                self.y -= 1;
-            }
-            (b'L', Left) => {
-                self.dir = Up;
-                self.x -= 1;
-            }
-            (b'-', Left) => {
-                self.dir = Left;
-                self.y -= 1;
-            }
-            (b'F', Left) => {
-                self.dir = Down;
-                self.x += 1;
-            }
-            _ => {
-                panic!("I am stuck")
-            }
-        }
-    }
-}
-
-fn walk_loop(mut pos: Position, map: &[Vec<u8>]) -> u64 {
-    let mut step = 1;
-    while map[pos.x][pos.y] != b'S' {
-        pos.next(&map);
-        step += 1;
+    /// Follows the pipe the position is on to the next tile
+    fn next(&mut self, map: &[Vec<u8>]) -> Result<()> {
+        self.dir = match (map[self.x][self.y], &self.dir) {
+            (b'|', Up) | (b'J', Right) | (b'L', Left) => Up,
+            (b'7', Up) | (b'J', Down) | (b'-', Left) => Left,
+            (b'F', Up) | (b'-', Right) | (b'L', Down) => Right,
+            (b'7', Right) | (b'|', Down) | (b'F', Left) => Down,
+            (tile, _) => bail!(AdventError::parse(
+                self.x + 1,
+                self.y + 1,
+                format!("the loop does not continue at '{}'", tile as char)
+            )),
+        };
+        let (x, y) = step(map, self.x, self.y, &self.dir)
+            .ok_or_else(|| AdventError::parse(self.x + 1, self.y + 1, "the loop leaves the map"))?;
+        (self.x, self.y) = (x, y);
+        Ok(())
     }
-    step
 }
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut map = Vec::new();
-    for l in lines {
-        let line = l.unwrap();
-        map.push(line.as_bytes().to_vec());
-    }
-    let start = find_start(&map);
-    let pos = if [b'-', b'F', b'L'].contains(&map[start.0][start.1 - 1]) {
-        Position::new(start.0, start.1 - 1, Left)
-    } else if [b'|', b'F', b'7'].contains(&map[start.0 - 1][start.1]) {
-        Position::new(start.0 - 1, start.1, Up)
-    } else if [b'-', b'7', b'J'].contains(&map[start.0][start.1 + 1]) {
-        Position::new(start.0, start.1 + 1, Right)
-    } else {
-        Position::new(start.0 + 1, start.1, Down)
+/// The tile next to `x`, `y` in direction `dir`, if it is on the map
+fn step(map: &[Vec<u8>], x: usize, y: usize, dir: &Direction) -> Option<(usize, usize)> {
+    let (x, y) = match dir {
+        Up => (x.checked_sub(1)?, y),
+        Down => (x + 1, y),
+        Left => (x, y.checked_sub(1)?),
+        Right => (x, y + 1),
     };
-    let loop_length = walk_loop(pos, &map);
-    format!("{}", loop_length / 2)
+    map.get(x)?.get(y)?;
+    Some((x, y))
 }
 
-fn find_loop(mut pos: Position, map: &[Vec<u8>]) -> Vec<Vec<u8>> {
-    let mut loop_map = Vec::new();
-    for i in 0..map.len() {
-        loop_map.push(vec![b'.'; map[i].len()]);
-    }
-    loop_map[pos.x][pos.y] = map[pos.x][pos.y];
-    while map[pos.x][pos.y] != b'S' {
-        pos.next(&map);
-        loop_map[pos.x][pos.y] = map[pos.x][pos.y];
-    }
-    loop_map
+fn read_map(lines: io::Lines<impl BufRead>) -> Result<Vec<Vec<u8>>> {
+    parse_lines(lines, |line| Ok(line.as_bytes().to_vec()))
 }
 
-fn mark(x: usize, y: usize, b: u8, map: &mut [Vec<u8>]) {
-    if map[x][y] == b'.' {
-        map[x][y] = b;
-    }
-}
-
-fn set_marks(pos: &Position, map: &mut [Vec<u8>]) {
-    let x = pos.x;
-    let y = pos.y;
-    match (map[x][y], &pos.dir) {
-        (b'7', Right) => {
-            mark(x - 1, y, b'O', map);
-            mark(x - 1, y + 1, b'O', map);
-            mark(x, y + 1, b'O', map);
-            mark(x + 1, y - 1, b'#', map);
-        }
-        (b'7', Up) => {
-            mark(x - 1, y, b'#', map);
-            mark(x - 1, y + 1, b'#', map);
-            mark(x, y + 1, b'#', map);
-            mark(x + 1, y + 1, b'O', map);
-        }
-        (b'L', Left) => {
-            mark(x + 1, y, b'O', map);
-            mark(x + 1, y - 1, b'O', map);
-            mark(x, y - 1, b'O', map);
-            mark(x - 1, y + 1, b'#', map);
-        }
-        (b'L', Down) => {
-            mark(x + 1, y, b'#', map);
-            mark(x + 1, y - 1, b'#', map);
-            mark(x, y - 1, b'#', map);
-            mark(x - 1, y + 1, b'O', map);
-        }
-        (b'J', Down) => {
-            mark(x, y + 1, b'O', map);
-            mark(x + 1, y + 1, b'O', map);
-            mark(x, y + 1, b'O', map);
-            mark(x - 1, y - 1, b'#', map);
-        }
-        (b'J', Right) => {
-            mark(x, y + 1, b'#', map);
-            mark(x + 1, y + 1, b'#', map);
-            mark(x, y + 1, b'#', map);
-            mark(x - 1, y - 1, b'O', map);
-        }
-        (b'F', Up) => {
-            mark(x, y - 1, b'O', map);
-            mark(x - 1, y - 1, b'O', map);
-            mark(x - 1, y, b'O', map);
-            mark(x + 1, y + 1, b'#', map);
-        }
-        (b'F', Left) => {
-            mark(x, y - 1, b'#', map);
-            mark(x - 1, y - 1, b'#', map);
-            mark(x - 1, y, b'#', map);
-            mark(x + 1, y + 1, b'O', map);
-        }
-        (b'|', Up) => {
-            mark(x, y - 1, b'O', map);
-            mark(x, y + 1, b'#', map);
-        }
-        (b'|', Down) => {
-            mark(x, y - 1, b'#', map);
-            mark(x, y + 1, b'O', map);
-        }
-        (b'-', Right) => {
-            mark(x - 1, y, b'O', map);
-            mark(x + 1, y, b'#', map);
-        }
-        (b'-', Left) => {
-            mark(x - 1, y, b'#', map);
-            mark(x + 1, y, b'O', map);
-        }
-        _ => panic!("I am stuck"),
-    }
+/// The first tile of the loop after the start, with the direction into it
+fn first_step(map: &[Vec<u8>]) -> Result<Position> {
+    let (x, y) = find_start(map)?;
+    let connections: [(Direction, &[u8]); 4] = [
+        (Left, b"-FL"),
+        (Up, b"|F7"),
+        (Right, b"-7J"),
+        (Down, b"|LJ"),
+    ];
+    for (dir, pipes) in connections {
+        if let Some((nx, ny)) = step(map, x, y, &dir) {
+            if pipes.contains(&map[nx][ny]) {
+                return Ok(Position::new(nx, ny, dir));
+            }
+        }
+    }
+    bail!(AdventError::parse(
+        x + 1,
+        y + 1,
+        "no pipe connects to the start"
+    ));
 }
 
-fn mark_left_right(mut pos: Position, map: &mut [Vec<u8>]) {
+/// The tiles of the loop as grid points, ending with the start tile
+fn loop_tiles(mut pos: Position, map: &[Vec<u8>]) -> Result<Vec<crate::Position>> {
+    let tiles_on_map: usize = map.iter().map(Vec::len).sum();
+    let mut tiles = vec![crate::Position::new(pos.y as i32, pos.x as i32)];
     while map[pos.x][pos.y] != b'S' {
-        set_marks(&pos, map);
-        pos.next(&map);
-    }
-}
-
-fn fill_gaps(map: &mut [Vec<u8>], mark: u8) {
-    for i in 0..map.len() {
-        for j in 1..map[i].len() {
-            if map[i][j] == b'.' && map[i][j - 1] == mark {
-                map[i][j] = mark;
-            }
+        if tiles.len() > tiles_on_map {
+            bail!(AdventError::invalid(
+                "the loop does not return to the start"
+            ));
         }
+        pos.next(map)?;
+        tiles.push(crate::Position::new(pos.y as i32, pos.x as i32));
     }
+    Ok(tiles)
 }
 
-fn count_inner(map: &[Vec<u8>], mark: u8) -> u64 {
-    let mut count = 0;
-    for row in map {
-        for cell in row {
-            if *cell == mark {
-                count += 1;
-            }
-        }
-    }
-    count
+fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let map = read_map(lines)?;
+    let loop_length = loop_tiles(first_step(&map)?, &map)?.len();
+    Ok(format!("{}", loop_length / 2))
 }
-fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut map = Vec::new();
-    for l in lines {
-        let line = l.unwrap();
-        map.push(line.as_bytes().to_vec());
-    }
-    let start = find_start(&map);
-    let start_pos = if [b'-', b'F', b'L'].contains(&map[start.0][start.1 - 1]) {
-        Position::new(start.0, start.1 - 1, Left)
-    } else if [b'|', b'F', b'7'].contains(&map[start.0 - 1][start.1]) {
-        Position::new(start.0 - 1, start.1, Up)
-    } else if [b'-', b'7', b'J'].contains(&map[start.0][start.1 + 1]) {
-        Position::new(start.0, start.1 + 1, Right)
-    } else {
-        Position::new(start.0 + 1, start.1, Down)
-    };
-    let mut loop_map = find_loop(start_pos.clone(), &map);
-    mark_left_right(start_pos, &mut loop_map);
-    // check wich marker is inside
-    let mut inside_marker = b'.';
-    let half = loop_map.len() / 2;
-    for i in 0..loop_map[half].len() {
-        if loop_map[half][i] != b'.' {
-            if loop_map[half][i] == b'O' {
-                inside_marker = b'#';
-            } else {
-                inside_marker = b'O';
-            }
-            break;
-        }
-    }
-    fill_gaps(&mut loop_map, inside_marker);
-    let solution = count_inner(&loop_map, inside_marker);
-    format!("{solution}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
 
-    #[test]
-    fn test_2023_10_1() {
-        let lines = read_lines("data/2023/10.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "6682");
-    }
-
-    #[test]
-    fn test_2023_10_2() {
-        let lines = read_lines("data/2023/10.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "353");
-    }
+fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let map = read_map(lines)?;
+    let solution = interior_points(&loop_tiles(first_step(&map)?, &map)?);
+    Ok(format!("{solution}"))
 }
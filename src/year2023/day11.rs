@@ -1,25 +1,23 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::AdventError;
+use anyhow::{bail, Result};
+
+solution!(2023, 11, "Cosmic Expansion");
 
-fn solve(lines: io::Lines<io::BufReader<File>>, mut expansion_factor: usize) -> String {
+fn solve(lines: io::Lines<impl BufRead>, mut expansion_factor: usize) -> Result<String> {
     expansion_factor -= 1;
     let mut galaxies = Vec::new();
     let mut col_marks = Vec::new();
     let mut row_offsets = Vec::new();
-    let mut row: usize = 0;
     let mut row_offset = 0;
-    for l in lines {
-        let line = l.unwrap();
+    for (row, l) in lines.enumerate() {
+        let line = l?;
         let line = line.trim();
         if row == 0 {
             col_marks = vec![true; line.len()];
+        } else if line.len() != col_marks.len() {
+            bail!(AdventError::at_line(row, "rows differ in length"));
         }
         let mut found_galaxy = false;
         for (col, c) in line.as_bytes().iter().enumerate() {
@@ -33,7 +31,6 @@ fn solve(lines: io::Lines<io::BufReader<File>>, mut expansion_factor: usize) ->
             row_offset += expansion_factor;
         }
         row_offsets.push(row_offset);
-        row += 1;
     }
     let mut col_offsets = Vec::new();
     let mut col_offset: usize = 0;
@@ -44,7 +41,7 @@ fn solve(lines: io::Lines<io::BufReader<File>>, mut expansion_factor: usize) ->
         }
     }
     let mut sum = 0;
-    for i in 0..galaxies.len() - 1 {
+    for i in 0..galaxies.len().saturating_sub(1) {
         let xi = galaxies[i].0 + row_offsets[galaxies[i].0];
         let yi = galaxies[i].1 + col_offsets[galaxies[i].1];
         for j in i + 1..galaxies.len() {
@@ -53,33 +50,13 @@ fn solve(lines: io::Lines<io::BufReader<File>>, mut expansion_factor: usize) ->
             sum += (xj as i64 - xi as i64).abs() + (yj as i64 - yi as i64).abs();
         }
     }
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
+fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
     solve(lines, 2)
 }
 
-fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
     solve(lines, 1000000)
 }
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_11_1() {
-        let lines = read_lines("data/2023/11.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "9274989");
-    }
-
-    #[test]
-    fn test_2023_11_2() {
-        let lines = read_lines("data/2023/11.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "357134560737");
-    }
-}
@@ -1,18 +1,12 @@
 use std::{
-    collections::HashMap,
     fmt::{self, Display},
-    fs::File,
-    hash::Hash,
-    io,
+    io::{self, BufRead},
 };
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{memo::Memo, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2023, 12, "Hot Springs");
 
 struct StaticInfo {
     map: Vec<u8>,
@@ -42,68 +36,14 @@ impl Display for State {
     }
 }
 
-struct Solver {
-    cache: HashMap<State, usize>,
-    info: StaticInfo,
-}
-
-impl Solver {
-    fn new(info: StaticInfo) -> Self {
-        Self {
-            cache: HashMap::new(),
-            info,
-        }
-    }
-
-    fn solve(&mut self, state: State) -> usize {
-        //println!("state: {state}");
-        if state.pattern_pos == self.info.damaged_pattern.len() {
-            return 1;
-        }
-        if state.pos >= self.info.map.len() {
-            return 0;
-        }
-        if let Some(score) = self.cache.get(&state) {
-            return *score;
-        }
-
-        let mut pos = state.pos;
-        let mut total_score = 0;
-        let pattern_pos = state.pattern_pos;
-        let pattern_len = self.info.damaged_pattern[pattern_pos];
-        let remaining = state.remaining;
-        while pos <= self.info.map.len() - state.remaining as usize {
-            if let Some((new_pos, used)) = self.find_next_pattern(pos, pattern_len, remaining) {
-                let score = self.solve(State {
-                    pos: new_pos + pattern_len + 1,
-                    pattern_pos: pattern_pos + 1,
-                    remaining: remaining - used,
-                });
-                if score == 0 {
-                    pos += 1;
-                    continue;
-                }
-                total_score += score;
-                if self.info.map[new_pos] == b'#' {
-                    break;
-                }
-                pos = new_pos + 1;
-            } else {
-                break;
-            }
-        }
-        self.cache.insert(state, total_score);
-
-        total_score
-    }
-
+impl StaticInfo {
     fn find_next_pattern(
         &self,
         pos: usize,
         len: usize,
         remaining: usize,
     ) -> Option<(usize, usize)> {
-        let map = &self.info.map;
+        let map = &self.map;
 
         let mut last_round = false;
         for i in pos..=(map.len() - len) {
@@ -139,47 +79,101 @@ impl Solver {
     }
 }
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
+/// Number of arrangements of the damaged springs from `state` on
+fn arrangements(memo: &mut Memo<State, usize>, info: &StaticInfo, state: State) -> usize {
+    if state.pattern_pos == info.damaged_pattern.len() {
+        return 1;
+    }
+    if state.pos >= info.map.len() {
+        return 0;
+    }
+    memo.get(state.clone(), |memo| {
+        let mut pos = state.pos;
+        let mut total_score = 0;
+        let pattern_pos = state.pattern_pos;
+        let pattern_len = info.damaged_pattern[pattern_pos];
+        let remaining = state.remaining;
+        while pos <= info.map.len() - state.remaining {
+            if let Some((new_pos, used)) = info.find_next_pattern(pos, pattern_len, remaining) {
+                let score = arrangements(
+                    memo,
+                    info,
+                    State {
+                        pos: new_pos + pattern_len + 1,
+                        pattern_pos: pattern_pos + 1,
+                        remaining: remaining - used,
+                    },
+                );
+                if score == 0 {
+                    pos += 1;
+                    continue;
+                }
+                total_score += score;
+                if info.map[new_pos] == b'#' {
+                    break;
+                }
+                pos = new_pos + 1;
+            } else {
+                break;
+            }
+        }
+        total_score
+    })
+}
+
+/// Number of arrangements of a row, which must have at least one
+fn count_arrangements(map: Vec<u8>, pattern: Vec<usize>) -> Result<usize> {
+    let count = map.iter().filter(|&&m| m == b'#').count();
+    let total_damaged: usize = pattern.iter().sum();
+    let state = State {
+        pos: 0,
+        remaining: total_damaged
+            .checked_sub(count)
+            .ok_or(AdventError::NoSolution)?,
+        pattern_pos: 0,
+    };
+    let info = StaticInfo {
+        map,
+        damaged_pattern: pattern,
+    };
+    let combinations = arrangements(&mut Memo::new(), &info, state);
+    if combinations == 0 {
+        bail!(AdventError::NoSolution);
+    }
+    Ok(combinations)
+}
+
+fn read_row(parts: &[&str]) -> Result<(Vec<u8>, Vec<usize>)> {
+    let [map, pattern] = parts else {
+        bail!(AdventError::invalid(format!(
+            "expected springs and pattern, found {parts:?}"
+        )));
+    };
+    let pattern = pattern
+        .split(',')
+        .map(|s| s.parse())
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|err| AdventError::invalid(format!("invalid pattern '{pattern}': {err}")))?;
+    Ok((map.as_bytes().to_vec(), pattern))
+}
+
+fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut sum = 0;
     for l in lines {
-        let line = l.unwrap();
+        let line = l?;
         let parts: Vec<&str> = line.trim().split(' ').collect();
-        println!("row: {:?}", parts);
-        let map = parts[0].as_bytes().to_vec();
-        let pattern: Vec<usize> = parts[1].split(',').map(|s| s.parse().unwrap()).collect();
-        let mut count: usize = 0;
-        for m in &map {
-            if *m == b'#' {
-                count += 1;
-            }
-        }
-        let total_damaged: usize = pattern.iter().map(|p| *p).sum();
-        let mut solver = Solver::new(StaticInfo {
-            map,
-            damaged_pattern: pattern,
-        });
-        let state = State {
-            pos: 0,
-            remaining: total_damaged - count,
-            pattern_pos: 0,
-        };
-        let combinations = solver.solve(state);
-        println!("combinations: {combinations}");
-        if combinations == 0 {
-            panic!("no solution for pattern {:?}", parts);
-        }
-        sum += combinations;
+        let (map, pattern) = read_row(&parts)?;
+        sum += count_arrangements(map, pattern)?;
     }
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
-fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut sum = 0;
     for l in lines {
-        let line = l.unwrap();
+        let line = l?;
         let parts: Vec<&str> = line.trim().split(' ').collect();
-        let map = parts[0].as_bytes().to_vec();
-        let pattern: Vec<usize> = parts[1].split(',').map(|s| s.parse().unwrap()).collect();
+        let (map, pattern) = read_row(&parts)?;
 
         let mut full_map = map.clone();
         let mut full_pattern = pattern.clone();
@@ -188,47 +182,7 @@ fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
             full_map.extend(&map);
             full_pattern.extend(&pattern);
         }
-        let total_damaged: usize = full_pattern.iter().map(|p| *p).sum();
-        let mut count: usize = 0;
-        for m in &full_map {
-            if *m == b'#' {
-                count += 1;
-            }
-        }
-        let mut solver = Solver::new(StaticInfo {
-            map: full_map,
-            damaged_pattern: full_pattern,
-        });
-        let state = State {
-            pos: 0,
-            remaining: total_damaged - count,
-            pattern_pos: 0,
-        };
-        let combinations = solver.solve(state);
-        if combinations == 0 {
-            panic!("no solution for pattern {:?}", parts);
-        }
-        sum += combinations;
-    }
-    format!("{sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_12_1() {
-        let lines = read_lines("data/2023/12.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "7195");
-    }
-
-    #[test]
-    fn test_2023_12_2() {
-        let lines = read_lines("data/2023/12.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "33992866292225");
+        sum += count_arrangements(full_map, full_pattern)?;
     }
+    Ok(format!("{sum}"))
 }
@@ -1,12 +1,9 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::AdventError;
+use anyhow::{bail, Result};
+
+solution!(2023, 13, "Point of Incidence");
 
 fn row_equal(i: usize, j: usize, grid: &[Vec<u8>]) -> bool {
     for k in 0..grid[i].len() {
@@ -26,13 +23,12 @@ fn col_equal(i: usize, j: usize, grid: &[Vec<u8>]) -> bool {
     true
 }
 
-fn calc_score(grid: &[Vec<u8>]) -> usize {
+fn calc_score(grid: &[Vec<u8>]) -> Result<usize> {
     let rows = grid.len();
     let cols = grid[0].len();
     for i in 0..rows - 1 {
         if row_equal(i, i + 1, grid) {
             let width = i.min(rows - i - 2);
-            println!("width: {width}, i: {i}");
             let mut is_mirror = true;
             for w in 1..=width {
                 if !row_equal(i - w, i + w + 1, grid) {
@@ -41,7 +37,7 @@ fn calc_score(grid: &[Vec<u8>]) -> usize {
                 }
             }
             if is_mirror {
-                return (i + 1) * 100;
+                return Ok((i + 1) * 100);
             }
         }
     }
@@ -56,29 +52,29 @@ fn calc_score(grid: &[Vec<u8>]) -> usize {
                 }
             }
             if is_mirror {
-                return i + 1;
+                return Ok(i + 1);
             }
         }
     }
-    panic!("no mirror found!");
+    bail!(AdventError::NoSolution);
 }
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
+fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut sum = 0;
     let mut grid = Vec::new();
     for l in lines {
-        let line = l.unwrap();
+        let line = l?;
         if line.is_empty() {
-            sum += calc_score(&grid);
+            sum += calc_score(&grid)?;
             grid.clear();
         } else {
             grid.push(line.trim().as_bytes().to_vec());
         }
     }
     if !grid.is_empty() {
-        sum += calc_score(&grid);
+        sum += calc_score(&grid)?;
     }
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
 fn row_equal_with_error(i: usize, j: usize, grid: &[Vec<u8>]) -> u32 {
@@ -101,7 +97,7 @@ fn col_equal_with_error(i: usize, j: usize, grid: &[Vec<u8>]) -> u32 {
     errors
 }
 
-fn calc_score_with_smudge(grid: &[Vec<u8>]) -> usize {
+fn calc_score_with_smudge(grid: &[Vec<u8>]) -> Result<usize> {
     let rows = grid.len();
     let cols = grid[0].len();
     for i in 0..rows - 1 {
@@ -115,7 +111,7 @@ fn calc_score_with_smudge(grid: &[Vec<u8>]) -> usize {
                 }
             }
             if errors == 1 {
-                return (i + 1) * 100;
+                return Ok((i + 1) * 100);
             }
         }
     }
@@ -130,47 +126,27 @@ fn calc_score_with_smudge(grid: &[Vec<u8>]) -> usize {
                 }
             }
             if errors == 1 {
-                return i + 1;
+                return Ok(i + 1);
             }
         };
     }
-    panic!("no mirror found!");
+    bail!(AdventError::NoSolution);
 }
 
-fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut sum = 0;
     let mut grid = Vec::new();
     for l in lines {
-        let line = l.unwrap();
+        let line = l?;
         if line.is_empty() {
-            sum += calc_score_with_smudge(&grid);
+            sum += calc_score_with_smudge(&grid)?;
             grid.clear();
         } else {
             grid.push(line.trim().as_bytes().to_vec());
         }
     }
-    if grid.len() > 0 {
-        sum += calc_score_with_smudge(&grid);
-    }
-    format!("{sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_13_1() {
-        let lines = read_lines("data/2023/13.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "31877");
-    }
-
-    #[test]
-    fn test_2023_13_2() {
-        let lines = read_lines("data/2023/13.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "42996");
+    if !grid.is_empty() {
+        sum += calc_score_with_smudge(&grid)?;
     }
+    Ok(format!("{sum}"))
 }
@@ -1,12 +1,9 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{next_line, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2023, 15, "Lens Library");
 
 fn hash(s: &str) -> u8 {
     let mut h = 0;
@@ -16,14 +13,14 @@ fn hash(s: &str) -> u8 {
     h as u8
 }
 
-fn riddle_1(mut lines: io::Lines<io::BufReader<File>>) -> String {
-    let l = lines.next().unwrap().unwrap();
+fn riddle_1(mut lines: io::Lines<impl BufRead>) -> Result<String> {
+    let l = next_line(&mut lines)?;
     let steps: Vec<&str> = l.split(',').collect();
     let mut solution = 0;
     for step in steps {
         solution += hash(step) as u64;
     }
-    format!("{solution}")
+    Ok(format!("{solution}"))
 }
 
 #[derive(Clone, Debug)]
@@ -135,27 +132,32 @@ impl List {
     }
 }
 
-fn find_op(b: &[u8]) -> usize {
+fn find_op(b: &[u8]) -> Result<usize> {
     for i in 0..b.len() {
         if b[i] == b'-' || b[i] == b'=' {
-            return i;
+            return Ok(i);
         }
     }
-    panic!("invalid instruction: {}", String::from_utf8_lossy(b));
+    bail!(AdventError::invalid(format!(
+        "invalid instruction: {}",
+        String::from_utf8_lossy(b)
+    )));
 }
 
-fn riddle_2(mut lines: io::Lines<io::BufReader<File>>) -> String {
-    let l = lines.next().unwrap().unwrap();
+fn riddle_2(mut lines: io::Lines<impl BufRead>) -> Result<String> {
+    let l = next_line(&mut lines)?;
     let steps: Vec<&str> = l.split(',').collect();
     let mut boxes = vec![List::new(); 256];
     for s in steps {
         let bytes = s.as_bytes();
-        let op_idx = find_op(bytes);
+        let op_idx = find_op(bytes)?;
         let name = s[0..op_idx].to_string();
         let box_id = hash(&name) as usize;
         match bytes[op_idx] {
             b'=' => {
-                let focal: u8 = bytes[op_idx + 1] - b'0';
+                let focal: u8 = s[op_idx + 1..]
+                    .parse()
+                    .map_err(|_| AdventError::invalid(format!("invalid focal length in '{s}'")))?;
                 boxes[box_id].insert_or_update(Item {
                     next: None,
                     prev: None,
@@ -164,38 +166,15 @@ fn riddle_2(mut lines: io::Lines<io::BufReader<File>>) -> String {
                 });
                 boxes[box_id].check();
             }
-            b'-' => {
+            _ => {
                 boxes[box_id].remove(name);
                 boxes[box_id].check();
             }
-            _ => {
-                panic!("invalid operation: {}", s);
-            }
         }
     }
     let mut solution = 0;
     for i in 0..256 {
         solution += (i + 1) * boxes[i].score();
     }
-    format!("{solution}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_15_1() {
-        let lines = read_lines("data/2023/15.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "497373");
-    }
-
-    #[test]
-    fn test_2023_15_2() {
-        let lines = read_lines("data/2023/15.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "259356");
-    }
+    Ok(format!("{solution}"))
 }
@@ -1,12 +1,12 @@
-use std::{fs::File, io, vec};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{
+    visualize::{self, Rgb, BLACK, GREY, LAVA},
+    Grid, Position,
+};
+use anyhow::Result;
+
+solution!(2023, 16, "The Floor Will Be Lava", parse = Grid::parse);
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 enum Direction {
@@ -25,12 +25,13 @@ impl Direction {
             Left => 8,
         }
     }
-    fn next_pos(&self, x: usize, y: usize) -> (usize, usize) {
+
+    fn next_pos(&self, pos: &Position) -> Position {
         match self {
-            Up => (x - 1, y),
-            Down => (x + 1, y),
-            Right => (x, y + 1),
-            Left => (x, y - 1),
+            Up => Position::new(pos.x, pos.y - 1),
+            Down => Position::new(pos.x, pos.y + 1),
+            Right => Position::new(pos.x + 1, pos.y),
+            Left => Position::new(pos.x - 1, pos.y),
         }
     }
 }
@@ -38,207 +39,127 @@ impl Direction {
 use Direction::*;
 
 struct Solver {
-    map: Vec<Vec<u8>>,
-    beams: Vec<Vec<u8>>,
+    map: Grid<u8>,
+    beams: Grid<u8>,
+    /// Simulation to record a frame of whenever the beams entered the given
+    /// number of tiles more, if any
+    frames: Option<(&'static str, usize)>,
+    steps: usize,
 }
 
 impl Solver {
-    fn new(map: Vec<Vec<u8>>) -> Self {
-        let x = map.len();
-        let y = map[0].len();
+    fn new(map: Grid<u8>) -> Self {
+        let beams = Grid::new(map.width(), map.height(), 0);
         Self {
             map,
-            beams: vec![vec![0u8; y]; x],
+            beams,
+            frames: None,
+            steps: 0,
         }
     }
 
-    fn make_beams(&mut self, dir: Direction, x: usize, y: usize) {
-        // check for map boundary
-        if x >= self.map.len() {
+    fn make_beams(&mut self, dir: Direction, pos: Position) {
+        let Some(&tile) = self.map.get(&pos) else {
             return;
-        }
-        if y >= self.map[0].len() {
+        };
+        if self.check_and_update(&dir, &pos) {
             return;
         }
-        // map cell
-        if self.check_and_update(dir.clone(), x, y) {
-            return;
-        }
-        // advanced boundary.
-        // Since x and y can not be negative, this is more complicated
-        // Using i64 instead of usize for x and y might be the better choice
-        let tile = self.map[x][y];
-        if x == 0 {
-            if dir == Up && (tile == b'.' || tile == b'|') {
-                return;
-            }
-            if dir == Right && tile == b'/' {
-                return;
-            }
-            if dir == Left && tile == b'\\' {
-                return;
-            }
-        }
-        if y == 0 {
-            if dir == Left && (tile == b'.' || tile == b'-') {
-                return;
-            }
-            if dir == Down && tile == b'/' {
-                return;
-            }
-            if dir == Up && tile == b'\\' {
-                return;
+        self.steps += 1;
+        if let Some((name, every)) = self.frames {
+            if self.steps.is_multiple_of(every) {
+                self.record(name);
             }
         }
 
-        // proceed beam
-        if tile == b'-' && (dir == Up || dir == Down) {
-            self.make_beams(Right, x, y + 1);
-            if y > 0 {
-                self.make_beams(Left, x, y - 1)
-            };
-            return;
-        }
-        if tile == b'|' && (dir == Right || dir == Left) {
-            self.make_beams(Down, x + 1, y);
-            if x > 0 {
-                self.make_beams(Up, x - 1, y)
-            };
-            return;
-        }
-        if tile == b'/' {
-            match dir {
-                Up => {
-                    self.make_beams(Right, x, y + 1);
-                }
-                Down => {
-                    self.make_beams(Left, x, y - 1);
-                }
-                Left => {
-                    self.make_beams(Down, x + 1, y);
-                }
-                Right => {
-                    self.make_beams(Up, x - 1, y);
-                }
+        // splitters hit on their flat side send beams both ways
+        let dir = match (tile, &dir) {
+            (b'-', Up | Down) => {
+                self.make_beams(Right, Right.next_pos(&pos));
+                Left
             }
-            return;
-        }
-        if tile == b'\\' {
-            match dir {
-                Up => {
-                    self.make_beams(Left, x, y - 1);
-                }
-                Down => {
-                    self.make_beams(Right, x, y + 1);
-                }
-                Left => {
-                    self.make_beams(Up, x - 1, y);
-                }
-                Right => {
-                    self.make_beams(Down, x + 1, y);
-                }
+            (b'|', Right | Left) => {
+                self.make_beams(Down, Down.next_pos(&pos));
+                Up
             }
-            return;
-        }
-        let (x, y) = dir.next_pos(x, y);
-        self.make_beams(dir, x, y);
+            (b'/', Up) | (b'\\', Down) => Right,
+            (b'/', Down) | (b'\\', Up) => Left,
+            (b'/', Left) | (b'\\', Right) => Down,
+            (b'/', Right) | (b'\\', Left) => Up,
+            _ => dir,
+        };
+        let next = dir.next_pos(&pos);
+        self.make_beams(dir, next);
     }
 
-    fn check_and_update(&mut self, dir: Direction, x: usize, y: usize) -> bool {
-        if (self.beams[x][y] & dir.as_u8()) != 0 {
+    fn check_and_update(&mut self, dir: &Direction, pos: &Position) -> bool {
+        if (self.beams[pos] & dir.as_u8()) != 0 {
             true
         } else {
-            self.beams[x][y] |= dir.as_u8();
+            self.beams[pos] |= dir.as_u8();
             false
         }
     }
 
     fn energized(&self) -> u64 {
-        let mut sum = 0;
-        for row in &self.beams {
-            for b in row {
-                if *b != 0 {
-                    sum += 1;
-                }
-            }
-        }
-        sum
+        self.beams.iter().filter(|(_, b)| **b != 0).count() as u64
     }
 
     fn clear(&mut self) {
-        self.beams = vec![vec![0u8; self.map[0].len()]; self.map.len()];
+        self.beams = Grid::new(self.map.width(), self.map.height(), 0);
     }
 
-    #[allow(dead_code)]
-    fn print(&self) {
-        for row in &self.beams {
-            for b in row {
-                if *b != 0 {
-                    print!("#");
-                } else {
-                    print!(".");
-                }
-            }
-            println!();
-        }
+    /// Records the energized tiles and the mirrors and splitters
+    fn record(&self, name: &str) {
+        let rows = self
+            .map
+            .rows()
+            .zip(self.beams.rows())
+            .map(|(tiles, beams)| {
+                tiles
+                    .iter()
+                    .zip(beams)
+                    .map(|(&tile, &beam)| match (tile, beam) {
+                        (_, 1..) => LAVA,
+                        (b'.', _) => BLACK,
+                        _ => GREY,
+                    })
+                    .collect::<Vec<Rgb>>()
+            });
+        visualize::record(name, rows, |&colour| colour);
     }
 }
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut map = Vec::new();
-    for l in lines {
-        map.push(l.unwrap().as_bytes().to_vec());
-    }
-    let mut solver = Solver::new(map);
-    solver.make_beams(Direction::Right, 0, 0);
+fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut solver = Solver::new(Grid::parse(lines)?);
+    solver.frames = Some(("2023-16-1", 20));
+    solver.make_beams(Direction::Right, Position::new(0, 0));
+    solver.record("2023-16-1");
     let solution = solver.energized();
-    format!("{solution}")
+    Ok(format!("{solution}"))
 }
 
-fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut map = Vec::new();
-    for l in lines {
-        map.push(l.unwrap().as_bytes().to_vec());
+fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let map = Grid::parse(lines)?;
+    let width = map.width() as i32;
+    let height = map.height() as i32;
+    let mut starts = Vec::new();
+    for y in 0..height {
+        starts.push((Direction::Right, Position::new(0, y)));
+        starts.push((Direction::Left, Position::new(width - 1, y)));
     }
-    let x_len = map.len();
-    let y_len = map[0].len();
-    let mut solution = 0;
-    let mut solver = Solver::new(map.clone());
-    for i in 0..x_len {
-        solver.clear();
-        solver.make_beams(Direction::Right, i, 0);
-        solution = solution.max(solver.energized());
-        solver.clear();
-        solver.make_beams(Direction::Left, i, y_len - 1);
-        solution = solution.max(solver.energized());
+    for x in 0..width {
+        starts.push((Direction::Down, Position::new(x, 0)));
+        starts.push((Direction::Up, Position::new(x, height - 1)));
     }
-    for i in 0..y_len {
-        solver.clear();
-        solver.make_beams(Direction::Down, 0, i);
-        solution = solution.max(solver.energized());
+    let mut solution = 0;
+    // one frame per start, as every start is a simulation of its own
+    let mut solver = Solver::new(map);
+    for (dir, pos) in starts {
         solver.clear();
-        solver.make_beams(Direction::Up, x_len - 1, i);
+        solver.make_beams(dir, pos);
+        solver.record("2023-16-2");
         solution = solution.max(solver.energized());
     }
-    format!("{solution}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_16_1() {
-        let lines = read_lines("data/2023/16.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "7951");
-    }
-
-    #[test]
-    fn test_2023_16_2() {
-        let lines = read_lines("data/2023/16.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "8148");
-    }
+    Ok(format!("{solution}"))
 }
@@ -1,12 +1,20 @@
-use std::{collections::HashMap, fs::File, io};
+use std::{
+    collections::HashMap,
+    io::{self, BufRead},
+};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{interval::Cuboid, parse_lines_after, AdventError};
+use anyhow::{anyhow, bail, Result};
+
+solution!(
+    2023,
+    19,
+    "Aplenty",
+    parse = |mut lines| read_rules(&mut lines)
+);
+
+/// Ranges of the ratings x, m, a and s
+type Parts = Cuboid<u64, 4>;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Operation {
@@ -16,11 +24,11 @@ enum Operation {
 }
 
 impl Operation {
-    fn new(b: u8, param: u32) -> Self {
+    fn new(b: u8, param: u32) -> Result<Self> {
         match b {
-            b'<' => Self::LowerThan(param),
-            b'>' => Self::GreaterThan(param),
-            _ => panic!("invalid op"),
+            b'<' => Ok(Self::LowerThan(param)),
+            b'>' => Ok(Self::GreaterThan(param)),
+            _ => bail!("invalid operator '{}'", b as char),
         }
     }
     fn test(&self, value: u32) -> bool {
@@ -30,45 +38,16 @@ impl Operation {
             Self::True => true,
         }
     }
-    fn apply_on_range(&self, r: Range) -> (Range, Range) {
+    /// Splits `parts` into those whose rating `var_idx` passes the test
+    /// and the others
+    fn split(&self, parts: &Parts, var_idx: usize) -> (Option<Parts>, Option<Parts>) {
         match self {
-            Self::LowerThan(param) => {
-                if r.max < *param {
-                    (r, Range { min: 0, max: 0 })
-                } else if r.min >= *param {
-                    (Range { min: 0, max: 0 }, r)
-                } else {
-                    (
-                        Range {
-                            min: r.min,
-                            max: r.max.min(param - 1),
-                        },
-                        Range {
-                            min: r.min.max(*param),
-                            max: r.max,
-                        },
-                    )
-                }
-            }
+            Self::LowerThan(param) => parts.split_at(var_idx, *param as u64),
             Self::GreaterThan(param) => {
-                if r.min > *param {
-                    (r, Range { min: 0, max: 0 })
-                } else if r.max <= *param {
-                    (Range { min: 0, max: 0 }, r)
-                } else {
-                    (
-                        Range {
-                            min: r.min.max(*param + 1),
-                            max: r.max,
-                        },
-                        Range {
-                            min: r.min,
-                            max: r.max.min(*param),
-                        },
-                    )
-                }
+                let (lower, greater) = parts.split_at(var_idx, *param as u64 + 1);
+                (greater, lower)
             }
-            Self::True => (r, Range { min: 0, max: 0 }),
+            Self::True => (Some(parts.clone()), None),
         }
     }
 }
@@ -102,176 +81,131 @@ impl RuleSet {
         false
     }
 
-    fn passing_items(&self, mut ranges: [Range; 4], rules: &HashMap<String, RuleSet>) -> u64 {
+    fn passing_items(&self, parts: Parts, rules: &HashMap<String, RuleSet>) -> u64 {
         let mut sum = 0;
-        for i in 0..self.rules.len() {
-            let rule = &self.rules[i];
-            let (passed_range, rejected_range) = rule.op.apply_on_range(ranges[rule.var_idx]);
-            if &rule.target == "A" {
-                let mut prod = (passed_range.max - passed_range.min + 1) as u64;
-                for j in 0..4 {
-                    if j != rule.var_idx {
-                        if ranges[j].max > 0 {
-                            prod *= (ranges[j].max - ranges[j].min + 1) as u64;
-                        } else {
-                            prod = 0;
-                        }
-                    }
+        let mut rest = Some(parts);
+        for rule in &self.rules {
+            let Some(parts) = rest else {
+                break;
+            };
+            let (passed, rejected) = rule.op.split(&parts, rule.var_idx);
+            if let Some(passed) = passed {
+                if &rule.target == "A" {
+                    sum += passed.volume();
+                } else if &rule.target != "R" {
+                    sum += rules[&rule.target].passing_items(passed, rules);
                 }
-                sum += prod;
-            } else if &rule.target != "R" {
-                let mut new_ranges = ranges.clone();
-                new_ranges[rule.var_idx] = passed_range;
-                sum += rules[&rule.target].passing_items(new_ranges, rules);
             }
-            ranges[rule.var_idx] = rejected_range;
+            rest = rejected;
         }
         sum
     }
 }
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut rules_mode = true;
-    let mut rules = HashMap::new();
-    let mut items = Vec::new();
-    for l in lines {
-        let line = l.unwrap();
-        if line.is_empty() {
-            rules_mode = false;
-            continue;
-        }
-        if rules_mode {
-            let parts: Vec<&str> = line.split('{').collect();
-            let name = parts[0].to_string();
-            let rules_len = parts[1].len() - 1;
-            let rule_infos: Vec<&str> = parts[1][0..rules_len].split(',').collect();
-            let mut set_rules = Vec::new();
-            for rule_info in rule_infos {
-                let rule_parts: Vec<&str> = rule_info.split(':').collect();
-                let rule = if rule_parts.len() == 1 {
-                    let target = rule_parts[0].to_string();
-                    Rule {
-                        var_idx: 0,
-                        op: Operation::True,
-                        target,
-                    }
-                } else {
-                    let condition_variable = match rule_parts[0].as_bytes()[0] {
-                        b'x' => 0,
-                        b'm' => 1,
-                        b'a' => 2,
-                        b's' => 3,
-                        _ => panic!("invalid variable"),
-                    };
-                    let operator = rule_parts[0].as_bytes()[1];
-                    let condition_value: u32 = rule_parts[0][2..].parse().unwrap();
-                    let op = Operation::new(operator, condition_value);
-                    let target = rule_parts[1].to_string();
-                    Rule {
-                        var_idx: condition_variable,
-                        op,
-                        target,
-                    }
-                };
-                set_rules.push(rule);
-            }
-            rules.insert(name, RuleSet { rules: set_rules });
-        } else {
-            let parts: Vec<&str> = line[1..line.len() - 1].split(',').collect();
-            let mut tile = [0u32; 4];
-            for i in 0..4 {
-                tile[i] = parts[i].split('=').skip(1).next().unwrap().parse().unwrap();
-            }
-            items.push(tile);
-        }
-    }
-
-    let mut sum = 0;
-    for item in items {
-        if rules["in"].check(&item, &rules) {
-            sum += item[0] + item[1] + item[2] + item[3];
-        }
+fn read_rule(rule_info: &str) -> Result<Rule> {
+    let Some((condition, target)) = rule_info.split_once(':') else {
+        return Ok(Rule {
+            var_idx: 0,
+            op: Operation::True,
+            target: rule_info.to_string(),
+        });
+    };
+    let condition = condition.as_bytes();
+    if condition.len() < 3 {
+        bail!("invalid condition in rule '{rule_info}'");
     }
-    format!("{sum}")
+    let condition_variable = match condition[0] {
+        b'x' => 0,
+        b'm' => 1,
+        b'a' => 2,
+        b's' => 3,
+        _ => bail!("invalid variable in rule '{rule_info}'"),
+    };
+    let condition_value: u32 = std::str::from_utf8(&condition[2..])?.parse()?;
+    let op = Operation::new(condition[1], condition_value)?;
+    Ok(Rule {
+        var_idx: condition_variable,
+        op,
+        target: target.to_string(),
+    })
 }
 
-#[derive(Clone, Copy, Debug)]
-struct Range {
-    min: u32,
-    max: u32,
+fn read_rule_set(line: &str) -> Result<(String, RuleSet)> {
+    let (name, rule_infos) = line
+        .strip_suffix('}')
+        .and_then(|l| l.split_once('{'))
+        .ok_or_else(|| anyhow!("expected '<name>{{<rules>}}'"))?;
+    let rules = rule_infos
+        .split(',')
+        .map(read_rule)
+        .collect::<Result<Vec<Rule>>>()?;
+    Ok((name.to_string(), RuleSet { rules }))
 }
 
-impl Range {
-    fn new() -> Self {
-        Self { min: 1, max: 4000 }
+fn read_item(line: &str) -> Result<[u32; 4]> {
+    let parts: Vec<&str> = line
+        .strip_prefix('{')
+        .and_then(|l| l.strip_suffix('}'))
+        .ok_or_else(|| anyhow!("expected '{{x=..,m=..,a=..,s=..}}'"))?
+        .split(',')
+        .collect();
+    if parts.len() != 4 {
+        bail!("expected 4 ratings");
     }
+    let mut tile = [0u32; 4];
+    for i in 0..4 {
+        let (_, value) = parts[i]
+            .split_once('=')
+            .ok_or_else(|| anyhow!("expected '<category>=<rating>'"))?;
+        tile[i] = value.parse()?;
+    }
+    Ok(tile)
 }
 
-fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+/// Reads the workflows up to the first empty line
+fn read_rules(lines: &mut io::Lines<impl BufRead>) -> Result<HashMap<String, RuleSet>> {
     let mut rules = HashMap::new();
-    for l in lines {
-        let line = l.unwrap();
+    for (idx, l) in lines.enumerate() {
+        let line = l?;
         if line.is_empty() {
             break;
         }
-        let parts: Vec<&str> = line.split('{').collect();
-        let name = parts[0].to_string();
-        let rules_len = parts[1].len() - 1;
-        let rule_infos: Vec<&str> = parts[1][0..rules_len].split(',').collect();
-        let mut set_rules = Vec::new();
-        for rule_info in rule_infos {
-            let rule_parts: Vec<&str> = rule_info.split(':').collect();
-            let rule = if rule_parts.len() == 1 {
-                let target = rule_parts[0].to_string();
-                Rule {
-                    var_idx: 0,
-                    op: Operation::True,
-                    target,
-                }
-            } else {
-                let condition_variable = match rule_parts[0].as_bytes()[0] {
-                    b'x' => 0,
-                    b'm' => 1,
-                    b'a' => 2,
-                    b's' => 3,
-                    _ => panic!("invalid variable"),
-                };
-                let operator = rule_parts[0].as_bytes()[1];
-                let condition_value: u32 = rule_parts[0][2..].parse().unwrap();
-                let op = Operation::new(operator, condition_value);
-                let target = rule_parts[1].to_string();
-                Rule {
-                    var_idx: condition_variable,
-                    op,
-                    target,
-                }
-            };
-            set_rules.push(rule);
+        let (name, rule_set) =
+            read_rule_set(&line).map_err(|err| AdventError::at_line(idx, err.to_string()))?;
+        rules.insert(name, rule_set);
+    }
+    if !rules.contains_key("in") {
+        bail!(AdventError::invalid("no workflow named 'in'"));
+    }
+    for rule in rules.values().flat_map(|r| &r.rules) {
+        if rule.target != "A" && rule.target != "R" && !rules.contains_key(&rule.target) {
+            bail!(AdventError::invalid(format!(
+                "unknown workflow '{}'",
+                rule.target
+            )));
         }
-        rules.insert(name, RuleSet { rules: set_rules });
     }
-
-    let ranges = [Range::new(); 4];
-    let sum = rules["in"].passing_items(ranges, &rules);
-    format!("{sum}")
+    Ok(rules)
 }
 
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
+fn riddle_1(mut lines: io::Lines<impl BufRead>) -> Result<String> {
+    let rules = read_rules(&mut lines)?;
+    let skipped = rules.len() + 1;
+    let items = parse_lines_after(lines, skipped, read_item)?;
 
-    #[test]
-    fn test_2023_19_1() {
-        let lines = read_lines("data/2023/19.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "333263");
+    let mut sum = 0;
+    for item in items {
+        if rules["in"].check(&item, &rules) {
+            sum += item[0] + item[1] + item[2] + item[3];
+        }
     }
+    Ok(format!("{sum}"))
+}
 
-    #[test]
-    fn test_2023_19_2() {
-        let lines = read_lines("data/2023/19.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "130745440937650");
-    }
+fn riddle_2(mut lines: io::Lines<impl BufRead>) -> Result<String> {
+    let rules = read_rules(&mut lines)?;
+
+    let parts = Cuboid::new(std::array::from_fn(|_| 1..4001));
+    let sum = rules["in"].passing_items(parts, &rules);
+    Ok(format!("{sum}"))
 }
@@ -1,111 +1,56 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
+use crate::parse_lines;
+use anyhow::{anyhow, bail, Result};
+
+solution!(
+    2023,
+    2,
+    "Cube Conundrum",
+    parse = |lines| parse_lines(lines, read_game)
+);
+
+/// Reads a game as its id and the maximal number of red, blue and green cubes
+fn read_game(line: &str) -> Result<(u32, (u32, u32, u32))> {
+    let (game, draws) = line
+        .split_once(": ")
+        .ok_or_else(|| anyhow!("expected 'Game <id>: <draws>'"))?;
+    let id = game
+        .strip_prefix("Game ")
+        .ok_or_else(|| anyhow!("expected 'Game <id>'"))?
+        .parse()?;
+    let mut rbg = (0, 0, 0);
+    for draw in draws.split("; ") {
+        for cube in draw.split(", ") {
+            let (count, color) = cube
+                .split_once(' ')
+                .ok_or_else(|| anyhow!("expected '<count> <color>'"))?;
+            let count: u32 = count.parse()?;
+            match color {
+                "red" => rbg.0 = rbg.0.max(count),
+                "blue" => rbg.1 = rbg.1.max(count),
+                "green" => rbg.2 = rbg.2.max(count),
+                _ => bail!("invalid color '{color}'"),
+            }
+        }
     }
+    Ok((id, rbg))
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut sum = 0;
-    let mut idx = 0;
-    for line_result in lines {
-        let line = line_result.unwrap();
-        idx += 1;
-        let game: Vec<&str> = line.split(": ").collect();
-        let game_info: Vec<&str> = game[0].split(' ').collect();
-        let read_idx: usize = game_info[1].parse().unwrap();
-        if idx != read_idx {
-            eprintln!("*** Index error");
-        }
-        let draws: Vec<&str> = game[1].split("; ").collect();
-        let mut rbg = (0, 0, 0);
-        for draw in draws {
-            let cubes: Vec<&str> = draw.split(", ").collect();
-            for cube in cubes {
-                let c: Vec<&str> = cube.split(' ').collect();
-                let count: u32 = c[0].parse().unwrap();
-                match c[1] {
-                    "red" => {
-                        rbg.0 = rbg.0.max(count);
-                    }
-                    "blue" => {
-                        rbg.1 = rbg.1.max(count);
-                    }
-                    "green" => {
-                        rbg.2 = rbg.2.max(count);
-                    }
-                    _ => {
-                        eprintln!("*** invalid color");
-                    }
-                }
-            }
-        }
+    for (idx, rbg) in parse_lines(lines, read_game)? {
         if rbg.0 <= 12 && rbg.1 <= 14 && rbg.2 <= 13 {
             sum += idx;
         }
     }
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut sum = 0;
-    let mut idx = 0;
-    for line_result in lines {
-        let line = line_result.unwrap();
-        idx += 1;
-        let game: Vec<&str> = line.split(": ").collect();
-        let game_info: Vec<&str> = game[0].split(' ').collect();
-        let read_idx: usize = game_info[1].parse().unwrap();
-        if idx != read_idx {
-            eprintln!("*** Index error");
-        }
-        let draws: Vec<&str> = game[1].split("; ").collect();
-        let mut rbg = (0, 0, 0);
-        for draw in draws {
-            let cubes: Vec<&str> = draw.split(", ").collect();
-            for cube in cubes {
-                let c: Vec<&str> = cube.split(' ').collect();
-                let count: u32 = c[0].parse().unwrap();
-                match c[1] {
-                    "red" => {
-                        rbg.0 = rbg.0.max(count);
-                    }
-                    "blue" => {
-                        rbg.1 = rbg.1.max(count);
-                    }
-                    "green" => {
-                        rbg.2 = rbg.2.max(count);
-                    }
-                    _ => {
-                        eprintln!("*** invalid color");
-                    }
-                }
-            }
-        }
+    for (_, rbg) in parse_lines(lines, read_game)? {
         sum += rbg.0 * rbg.1 * rbg.2;
     }
-    format!("{sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_2_1() {
-        let lines = read_lines("data/2023/2.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "2317");
-    }
-
-    #[test]
-    fn test_2023_2_2() {
-        let lines = read_lines("data/2023/2.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "74804");
-    }
+    Ok(format!("{sum}"))
 }
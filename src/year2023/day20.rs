@@ -1,16 +1,18 @@
 use std::{
     collections::{HashMap, VecDeque},
-    fs::File,
-    io,
+    io::{self, BufRead},
 };
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{dot::Graph, math::lcm_all, parse_lines, AdventError};
+use anyhow::{anyhow, bail, Result};
+
+solution!(
+    2023,
+    20,
+    "Pulse Propagation",
+    parse = read_machines,
+    dot = module_graph
+);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Pulse {
@@ -171,7 +173,7 @@ impl Machine for Broadcaster {
     fn state(&self, _total: &mut Vec<u8>) {}
 }
 
-fn insert_inputs(mapping: &mut Vec<Vec<usize>>, from: usize, outputs: &[usize]) {
+fn insert_inputs(mapping: &mut [Vec<usize>], from: usize, outputs: &[usize]) {
     for o in outputs {
         if *o < mapping.len() {
             mapping[*o].push(from);
@@ -179,56 +181,114 @@ fn insert_inputs(mapping: &mut Vec<Vec<usize>>, from: usize, outputs: &[usize])
     }
 }
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut rules = Vec::new();
-    for l in lines {
-        rules.push(l.unwrap());
-    }
+type NameMap = HashMap<String, usize>;
+
+/// Kind, name and outputs of a module, the kind being `%` for flip-flops,
+/// `&` for conjunctions and `b` for the broadcaster
+type Rule = (u8, String, Vec<String>);
+
+/// The rules of all modules, in the order of the input
+fn read_rules(lines: io::Lines<impl BufRead>) -> Result<Vec<Rule>> {
+    parse_lines(lines, |line| {
+        let (name, outputs) = line
+            .split_once(" -> ")
+            .ok_or_else(|| anyhow!("expected '<module> -> <outputs>'"))?;
+        let (kind, name) = match name.strip_prefix(['%', '&']) {
+            Some(stripped) => (name.as_bytes()[0], stripped),
+            None if name == "broadcaster" => (b'b', name),
+            None => bail!("invalid machine type"),
+        };
+        let outputs: Vec<String> = outputs.split(", ").map(|s| s.to_string()).collect();
+        Ok((kind, name.to_string(), outputs))
+    })
+}
 
+/// Reads the machines, indexed by their position in the input. Modules which
+/// only receive pulses, like `rx`, get indices past the last machine.
+fn read_machines(lines: io::Lines<impl BufRead>) -> Result<(NameMap, Vec<Box<dyn Machine>>)> {
+    build_machines(&read_rules(lines)?)
+}
+
+fn build_machines(rules: &[Rule]) -> Result<(NameMap, Vec<Box<dyn Machine>>)> {
     let mut name_map = HashMap::new();
-    let mut idx = 0usize;
-    for line in &rules {
-        let parts: Vec<&str> = line.split(" -> ").collect();
-        if parts[0] == "broadcaster" {
-            name_map.insert(parts[0].to_string(), idx);
-        } else {
-            name_map.insert(parts[0][1..].to_string(), idx);
-        }
-        idx += 1;
+    for (idx, (_, name, _)) in rules.iter().enumerate() {
+        name_map.insert(name.clone(), idx);
+    }
+    if !name_map.contains_key("broadcaster") {
+        bail!(AdventError::invalid("no broadcaster found"));
     }
-    name_map.insert("rx".to_string(), idx);
-
-    let mut machines: Vec<Box<dyn Machine>> = Vec::with_capacity(idx);
-    let mut input_map = vec![Vec::new(); idx];
-    for line in &rules {
-        let parts: Vec<&str> = line.split(" -> ").collect();
-        let outputs: Vec<usize> = parts[1].split(", ").map(|s| name_map[s]).collect();
-        if parts[0] == "broadcaster" {
-            let idx = name_map[parts[0]];
-            insert_inputs(&mut input_map, idx, &outputs);
-            machines.push(Box::new(Broadcaster::new(idx, outputs)));
-        } else if parts[0].starts_with('%') {
-            let idx = name_map[&parts[0][1..]];
-            insert_inputs(&mut input_map, idx, &outputs);
-            machines.push(Box::new(FlipFlop::new(idx, outputs)));
-        } else if parts[0].starts_with('&') {
-            let idx = name_map[&parts[0][1..]];
-            insert_inputs(&mut input_map, idx, &outputs);
-            machines.push(Box::new(Conjunction::new(idx, outputs)));
-        } else {
-            panic!("invalid machine type");
+    for (_, _, outputs) in rules {
+        for output in outputs {
+            let idx = name_map.len();
+            name_map.entry(output.clone()).or_insert(idx);
         }
     }
 
+    let mut machines: Vec<Box<dyn Machine>> = Vec::with_capacity(rules.len());
+    let mut input_map = vec![Vec::new(); rules.len()];
+    for (idx, (kind, _, outputs)) in rules.iter().enumerate() {
+        let outputs: Vec<usize> = outputs.iter().map(|s| name_map[s]).collect();
+        insert_inputs(&mut input_map, idx, &outputs);
+        machines.push(match kind {
+            b'%' => Box::new(FlipFlop::new(idx, outputs)),
+            b'&' => Box::new(Conjunction::new(idx, outputs)),
+            _ => Box::new(Broadcaster::new(idx, outputs)),
+        });
+    }
+
     for from in 0..input_map.len() {
         machines[from].init_inputs(&input_map[from]);
     }
+    Ok((name_map, machines))
+}
+
+/// The conjunctions feeding the single conjunction in front of `rx`, which
+/// sends `rx` a low pulse once all of them send a high one
+fn trigger_nodes(rules: &[Rule]) -> Result<Vec<&str>> {
+    let feeding = |target: &str| -> Vec<&Rule> {
+        rules
+            .iter()
+            .filter(|(_, _, outputs)| outputs.iter().any(|o| o == target))
+            .collect()
+    };
+    let [(b'&', last, _)] = feeding("rx")[..] else {
+        bail!(AdventError::invalid("rx isn't fed by a single conjunction"));
+    };
+    let triggers = feeding(last);
+    if triggers.is_empty() || triggers.iter().any(|(kind, _, _)| *kind != b'&') {
+        bail!(AdventError::invalid(format!(
+            "{last} isn't fed by conjunctions only"
+        )));
+    }
+    Ok(triggers.iter().map(|(_, name, _)| name.as_str()).collect())
+}
+
+/// The module network, which shows the conjunctions that have to send high
+/// pulses at once for `rx` to get a low one
+fn module_graph(lines: io::Lines<impl BufRead>) -> Result<Graph> {
+    let mut graph = Graph::directed("2023-20");
+    for (kind, name, outputs) in read_rules(lines)? {
+        let (label, shape) = match kind {
+            b'%' => ("flip-flop", "ellipse"),
+            b'&' => ("conjunction", "box"),
+            _ => ("broadcaster", "diamond"),
+        };
+        graph.node(&name, &[("kind", &label), ("shape", &shape)]);
+        for output in outputs {
+            graph.edge(&name, &output, &[]);
+        }
+    }
+    Ok(graph)
+}
+
+fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (name_map, mut machines) = read_machines(lines)?;
     let mut init_state = Vec::new();
     for m in &machines {
         m.state(&mut init_state);
     }
     let mut queue = Queue::new();
-    for _ in 0..1000 {
+    for presses in 1..=1000 {
         queue.add_signals(usize::MAX, &[name_map["broadcaster"]], Pulse::Low);
         while !queue.inner.is_empty() {
             let signal = queue.inner.pop_front().unwrap();
@@ -240,66 +300,27 @@ fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
         for m in &machines {
             m.state(&mut state);
         }
-        if state == init_state {
+        // back at the start, the remaining presses repeat the ones so far
+        if state == init_state && 1000 % presses == 0 {
+            let repeats = 1000 / presses;
+            queue.low_count *= repeats;
+            queue.high_count *= repeats;
             break;
         }
     }
     let score = queue.score();
-    format!("{}", score)
+    Ok(format!("{}", score))
 }
 
-fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut rules = Vec::new();
-    for l in lines {
-        rules.push(l.unwrap());
-    }
+fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let rules = read_rules(lines)?;
+    let (name_map, mut machines) = build_machines(&rules)?;
 
-    let mut name_map = HashMap::new();
-    let mut idx = 0usize;
-    for line in &rules {
-        let parts: Vec<&str> = line.split(" -> ").collect();
-        if parts[0] == "broadcaster" {
-            name_map.insert(parts[0].to_string(), idx);
-        } else {
-            name_map.insert(parts[0][1..].to_string(), idx);
-        }
-        idx += 1;
-    }
-    name_map.insert("rx".to_string(), idx);
-
-    let mut machines: Vec<Box<dyn Machine>> = Vec::with_capacity(idx);
-    let mut input_map = vec![Vec::new(); idx];
-    for line in &rules {
-        let parts: Vec<&str> = line.split(" -> ").collect();
-        let outputs: Vec<usize> = parts[1].split(", ").map(|s| name_map[s]).collect();
-        if parts[0] == "broadcaster" {
-            let idx = name_map[parts[0]];
-            insert_inputs(&mut input_map, idx, &outputs);
-            machines.push(Box::new(Broadcaster::new(idx, outputs)));
-        } else if parts[0].starts_with('%') {
-            let idx = name_map[&parts[0][1..]];
-            insert_inputs(&mut input_map, idx, &outputs);
-            machines.push(Box::new(FlipFlop::new(idx, outputs)));
-        } else if parts[0].starts_with('&') {
-            let idx = name_map[&parts[0][1..]];
-            insert_inputs(&mut input_map, idx, &outputs);
-            machines.push(Box::new(Conjunction::new(idx, outputs)));
-        } else {
-            panic!("invalid machine type");
-        }
-    }
-
-    for from in 0..input_map.len() {
-        machines[from].init_inputs(&input_map[from]);
-    }
-
-    // After analyzing the input, we need to have the following machines send a high pulse
-    // in order to get a low pulse on rx, therefore we record the number of hits when
-    // each of this will be hit first. We could automate this, but than we also
-    // would have to check that these are really conjunctions, ...
-    let trigger_nodes = ["rv", "vp", "dc", "cq"];
+    // the trigger nodes have to send a high pulse at once for rx to get a low
+    // one, so we record the press at which each of them is first hit by a low
+    // pulse, after which it sends a high one
     let mut trigger_events: HashMap<usize, usize> = HashMap::new();
-    for node in trigger_nodes {
+    for node in trigger_nodes(&rules)? {
         trigger_events.insert(name_map[node], 0);
     }
     let mut queue = Queue::new();
@@ -310,22 +331,22 @@ fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
         queue.add_signals(usize::MAX, &[name_map["broadcaster"]], Pulse::Low);
         while !queue.inner.is_empty() {
             let signal = queue.inner.pop_front().unwrap();
-            if trigger_events.contains_key(&signal.to) {
-                if signal.p == Pulse::Low {
-                    if trigger_events.contains_key(&signal.to) && trigger_events[&signal.to] == 0 {
-                        *trigger_events.get_mut(&signal.to).unwrap() = count;
-                        all_set = true;
-                        for n in trigger_events.values() {
-                            if *n == 0 {
-                                all_set = false;
-                                break;
-                            }
-                        }
-                        if all_set {
-                            break;
-                        }
+            if trigger_events.contains_key(&signal.to)
+                && signal.p == Pulse::Low
+                && trigger_events.contains_key(&signal.to)
+                && trigger_events[&signal.to] == 0
+            {
+                *trigger_events.get_mut(&signal.to).unwrap() = count;
+                all_set = true;
+                for n in trigger_events.values() {
+                    if *n == 0 {
+                        all_set = false;
+                        break;
                     }
                 }
+                if all_set {
+                    break;
+                }
             }
             if signal.to < machines.len() {
                 machines[signal.to].process(&signal, &mut queue);
@@ -335,30 +356,50 @@ fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
             break;
         }
     }
-    let mut total_count = 1;
-    // Here we trust that the numbers have no common divisors
-    for n in trigger_events.values() {
-        total_count *= *n;
-    }
-    format!("{total_count}")
+    // the trigger nodes repeat their high pulses with these periods
+    let total_count = lcm_all(trigger_events.values().map(|&n| n as i64))
+        .ok_or_else(|| AdventError::invalid("button presses overflow"))?;
+    Ok(format!("{total_count}"))
 }
 
 #[cfg(test)]
 mod test {
-    use super::execute;
-    use crate::read_lines;
+    use super::{module_graph, read_rules, trigger_nodes};
+    use std::io::BufRead;
 
     #[test]
-    fn test_2023_20_1() {
-        let lines = read_lines("data/2023/20.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "45159");
+    fn test_2023_20_trigger_nodes() {
+        let example =
+            "broadcaster -> a, b\n%a -> x\n%b -> y\n&x -> last\n&y -> last\n&last -> rx\n";
+        let rules = read_rules(example.as_bytes().lines()).unwrap();
+        assert_eq!(trigger_nodes(&rules).unwrap(), ["x", "y"]);
+        let example = "broadcaster -> a\n%a -> last\n&last -> rx\n";
+        let rules = read_rules(example.as_bytes().lines()).unwrap();
+        assert!(trigger_nodes(&rules).is_err());
+        let rules = read_rules("broadcaster -> a\n%a -> b\n".as_bytes().lines()).unwrap();
+        assert!(trigger_nodes(&rules).is_err());
     }
 
     #[test]
-    fn test_2023_20_2() {
-        let lines = read_lines("data/2023/20.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "229414480926893");
+    fn test_2023_20_module_graph() {
+        let example = "broadcaster -> a\n%a -> inv, con\n&inv -> b\n%b -> con\n&con -> output\n";
+        let graph = module_graph(example.as_bytes().lines()).unwrap();
+        assert_eq!(
+            graph.to_string(),
+            "digraph \"2023-20\" {
+  \"broadcaster\" [kind=\"broadcaster\", shape=\"diamond\"];
+  \"a\" [kind=\"flip-flop\", shape=\"ellipse\"];
+  \"inv\" [kind=\"conjunction\", shape=\"box\"];
+  \"b\" [kind=\"flip-flop\", shape=\"ellipse\"];
+  \"con\" [kind=\"conjunction\", shape=\"box\"];
+  \"broadcaster\" -> \"a\";
+  \"a\" -> \"inv\";
+  \"a\" -> \"con\";
+  \"inv\" -> \"b\";
+  \"b\" -> \"con\";
+  \"con\" -> \"output\";
+}
+"
+        );
     }
 }
@@ -1,11 +1,22 @@
-use std::{fs::File, io};
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead},
+};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
+use crate::{parse_lines, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2023, 21, "Step Counter", parse = read_map);
+
+fn read_map(lines: io::Lines<impl BufRead>) -> Result<Vec<Vec<u8>>> {
+    let map = parse_lines(lines, |l| Ok(l.as_bytes().to_vec()))?;
+    if map.is_empty() {
+        bail!(AdventError::invalid("empty map"));
     }
+    if let Some(row) = map.iter().position(|row| row.len() != map[0].len()) {
+        bail!(AdventError::at_line(row, "rows differ in length"));
+    }
+    Ok(map)
 }
 
 struct Solver {
@@ -59,64 +70,65 @@ impl Solver {
             .sum()
     }
 
-    fn infinite_sum(&self, steps: i64) -> i64 {
-        let max_sum = if steps % 2 == 1 {
-            // 4*3 + 4*5 + 4*7+ ... + 4*step
-            // steps = 2n+1  => n = (steps - 1) / 2
-            //4 + 4 * ((steps-1)/2+1)*(steps-1)/2
-            //4 + (steps-1 + 2)(steps-1)
-            //4 + (steps +1) *(steps -1)
-            //4+ steps*steps -1
-            3 + steps * steps
-        } else {
-            1 + 4 * 2 + 4 * 4
+    /// Plots reachable in exactly `steps` steps on the map repeated in every
+    /// direction. This relies on the shape of the input: a square map with the
+    /// start in its centre and `steps` ending on the edge of a copy of it, so
+    /// that the count grows quadratically with the copies walked across.
+    fn infinite_sum(&self, steps: usize) -> Result<i64> {
+        let size = self.map.len();
+        let half = size / 2;
+        if self.map[0].len() != size || self.map[half][half] != b'S' || steps % size != half {
+            bail!(AdventError::invalid(
+                "expected a square map with S in its centre"
+            ));
+        }
+        // distances on 5 x 5 copies of the map reach the first three counts
+        let len = 5 * size;
+        let mut distances = vec![vec![usize::MAX; len]; len];
+        let mut queue = VecDeque::from([(2 * size + half, 2 * size + half)]);
+        distances[2 * size + half][2 * size + half] = 0;
+        while let Some((x, y)) = queue.pop_front() {
+            let next = distances[x][y] + 1;
+            let neighbours = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbours {
+                if nx < len
+                    && ny < len
+                    && self.map[nx % size][ny % size] != b'#'
+                    && distances[nx][ny] == usize::MAX
+                {
+                    distances[nx][ny] = next;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        let count = |steps: usize| {
+            distances
+                .iter()
+                .flatten()
+                .filter(|&&d| d <= steps && d % 2 == steps % 2)
+                .count() as i64
         };
-        max_sum
+        let (a0, a1, a2) = (count(half), count(half + size), count(half + 2 * size));
+        let n = ((steps - half) / size) as i64;
+        Ok(a0 + (a1 - a0) * n + n * (n - 1) / 2 * (a2 - 2 * a1 + a0))
     }
 }
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut map: Vec<Vec<u8>> = Vec::new();
-    for l in lines {
-        map.push(l.unwrap().as_bytes().to_vec());
-    }
+fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let map = read_map(lines)?;
     let solver = Solver::new(map);
     let sum = solver.sum(64);
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
-fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut map: Vec<Vec<u8>> = Vec::new();
-    for l in lines {
-        map.push(l.unwrap().as_bytes().to_vec());
-    }
-    println!(
-        "x: {}, y: {}, x*y: {}",
-        map.len(),
-        map[0].len(),
-        map.len() * map[0].len()
-    );
+fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let map = read_map(lines)?;
     let solver = Solver::new(map);
-    let sum = solver.infinite_sum(26501365);
-    format!("{sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_21_1() {
-        let lines = read_lines("data/2023/21.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "3724");
-    }
-
-    #[test]
-    fn test_2023_21_2() {
-        let lines = read_lines("data/2023/21.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "134549294799713");
-    }
+    let sum = solver.infinite_sum(26501365)?;
+    Ok(format!("{sum}"))
 }
@@ -1,53 +1,18 @@
-use std::{cmp::Ordering, collections::HashSet, fs::File, io};
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    io::{self, BufRead},
+};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Point3D {
-    x: usize,
-    y: usize,
-    z: usize,
-}
+use crate::{parse_lines, vec3::Vec3, AdventError};
+use anyhow::{anyhow, bail, Result};
 
-impl Point3D {
-    fn new(v: &[usize]) -> Self {
-        Self {
-            x: v[0],
-            y: v[1],
-            z: v[2],
-        }
-    }
-}
-impl Ord for Point3D {
-    fn cmp(&self, other: &Self) -> Ordering {
-        if self.z == other.z {
-            if self.y == other.y {
-                self.x.cmp(&other.x)
-            } else {
-                self.y.cmp(&other.y)
-            }
-        } else {
-            self.z.cmp(&other.z)
-        }
-    }
-}
-
-impl PartialOrd for Point3D {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
+solution!(2023, 22, "Sand Slabs", parse = parse_input);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Brick {
-    start: Point3D,
-    end: Point3D,
+    start: Vec3<usize>,
+    end: Vec3<usize>,
 }
 
 impl Brick {
@@ -64,8 +29,10 @@ impl Brick {
     }
 }
 impl Ord for Brick {
+    /// Orders bricks from the ground up by their lowest corner
     fn cmp(&self, other: &Self) -> Ordering {
-        self.start.cmp(&other.start)
+        let key = |p: &Vec3<usize>| (p.z, p.y, p.x);
+        key(&self.start).cmp(&key(&other.start))
     }
 }
 
@@ -95,16 +62,21 @@ impl Solver {
         }
     }
 
-    fn verify_brick_stack(&self, check_for_no_supporter: bool) {
-        for i in 0..self.bricks.len() - 1 {
+    fn verify_brick_stack(&self, check_for_no_supporter: bool) -> Result<()> {
+        for i in 0..self.bricks.len() {
             let brick_a = &self.bricks[i];
             for z in brick_a.start.z..=brick_a.end.z {
                 for j in i + 1..self.bricks.len() {
                     let brick_b = &self.bricks[j];
-                    if z >= brick_b.start.z && z <= brick_b.end.z {
-                        if brick_a.xy_overlaps_with(brick_b) {
-                            panic!("overlapping bricks!")
-                        }
+                    if z >= brick_b.start.z
+                        && z <= brick_b.end.z
+                        && brick_a.xy_overlaps_with(brick_b)
+                    {
+                        bail!(AdventError::invalid(format!(
+                            "bricks {} and {} overlap",
+                            i + 1,
+                            j + 1
+                        )));
                     }
                 }
             }
@@ -123,23 +95,25 @@ impl Solver {
                         continue;
                     }
                     let brick_b = &self.bricks[j];
-                    if brick_b.end.z + 1 == brick_a.start.z {
-                        if brick_a.xy_overlaps_with(brick_b) {
-                            has_supporter = true;
-                            break;
-                        }
+                    if brick_b.end.z + 1 == brick_a.start.z && brick_a.xy_overlaps_with(brick_b) {
+                        has_supporter = true;
+                        break;
                     }
                 }
                 if !has_supporter {
-                    panic!("free floating brick found");
+                    bail!(AdventError::invalid(format!(
+                        "brick {} floats after falling",
+                        i + 1
+                    )));
                 }
             }
         }
+        Ok(())
     }
 
-    fn let_bricks_fall(&mut self) -> u64 {
+    fn let_bricks_fall(&mut self) -> Result<u64> {
         let mut fallen = 0;
-        self.verify_brick_stack(false);
+        self.verify_brick_stack(false)?;
         let bottom_layer_len = self.bottom_layer.len();
         for i in 2..bottom_layer_len {
             let mut falling_bricks = Vec::new();
@@ -175,8 +149,8 @@ impl Solver {
                 self.top_layer[self.bricks[falling.0].end.z].insert(falling.0);
             }
         }
-        self.verify_brick_stack(true);
-        fallen
+        self.verify_brick_stack(true)?;
+        Ok(fallen)
     }
 
     fn count_desintegratable(&self) -> u64 {
@@ -203,7 +177,7 @@ impl Solver {
         (self.bricks.len() - critical_bricks.len()) as u64
     }
 
-    fn count_chain_reaction(&self) -> u64 {
+    fn count_chain_reaction(&self) -> Result<u64> {
         let mut critical_bricks = HashSet::new();
         for brick in &self.bricks {
             if brick.start.z == 1 {
@@ -236,9 +210,9 @@ impl Solver {
             let top_layer = copy_hash_set(&self.top_layer, i);
             let bottom_layer = copy_hash_set(&self.bottom_layer, i);
             let mut fallen_solver = Solver::new(bricks, top_layer, bottom_layer);
-            fallen += fallen_solver.let_bricks_fall();
+            fallen += fallen_solver.let_bricks_fall()?;
         }
-        fallen
+        Ok(fallen)
     }
 }
 
@@ -261,17 +235,35 @@ fn copy_hash_set(sets: &[HashSet<usize>], exclude: usize) -> Vec<HashSet<usize>>
     new_sets
 }
 
-fn parse_input(lines: io::Lines<io::BufReader<File>>) -> Solver {
+fn parse_point(s: &str) -> Result<Vec3<usize>> {
+    let coords = s
+        .split(',')
+        .map(|s| s.parse())
+        .collect::<Result<Vec<usize>, _>>()?;
+    let [x, y, z] = coords[..] else {
+        bail!("expected 3 coordinates, found '{s}'");
+    };
+    Ok(Vec3::new(x, y, z))
+}
+
+fn parse_input(lines: io::Lines<impl BufRead>) -> Result<Solver> {
+    let ends = parse_lines(lines, |line| {
+        let (start, end) = line
+            .split_once('~')
+            .ok_or_else(|| anyhow!("expected '<start>~<end>'"))?;
+        let (start, end) = (parse_point(start)?, parse_point(end)?);
+        if start.z > end.z {
+            bail!("brick ends below its start");
+        }
+        if start.z == 0 {
+            bail!("brick reaches below the ground");
+        }
+        Ok((start, end))
+    })?;
     let mut bricks = Vec::new();
     let mut bottom = Vec::new();
     let mut top = Vec::new();
-    for l in lines {
-        let line = l.unwrap();
-        let parts: Vec<&str> = line.split('~').collect();
-        let start: Vec<usize> = parts[0].split(',').map(|s| s.parse().unwrap()).collect();
-        let end: Vec<usize> = parts[1].split(',').map(|s| s.parse().unwrap()).collect();
-        let start = Point3D::new(&start);
-        let end = Point3D::new(&end);
+    for (start, end) in ends {
         let idx = bricks.len();
         for _ in top.len()..=end.z {
             top.push(HashSet::new());
@@ -283,39 +275,19 @@ fn parse_input(lines: io::Lines<io::BufReader<File>>) -> Solver {
         bottom[start.z].insert(idx);
         bricks.push(Brick { start, end });
     }
-    Solver::new(bricks, top, bottom)
+    Ok(Solver::new(bricks, top, bottom))
 }
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut solver = parse_input(lines);
-    solver.let_bricks_fall();
+fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut solver = parse_input(lines)?;
+    solver.let_bricks_fall()?;
     let sum = solver.count_desintegratable();
-    format!("{sum}")
-}
-
-fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut solver = parse_input(lines);
-    solver.let_bricks_fall();
-    let sum = solver.count_chain_reaction();
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_22_1() {
-        let lines = read_lines("data/2023/22.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "439");
-    }
-
-    #[test]
-    fn test_2023_22_2() {
-        let lines = read_lines("data/2023/22.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "43056");
-    }
+fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut solver = parse_input(lines)?;
+    solver.let_bricks_fall()?;
+    let sum = solver.count_chain_reaction()?;
+    Ok(format!("{sum}"))
 }
@@ -1,12 +1,18 @@
-use std::{collections::HashSet, fs::File, io};
+use std::{
+    collections::HashSet,
+    io::{self, BufRead},
+};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{dot::Graph, parse_lines, AdventError};
+use anyhow::{bail, Result};
+
+solution!(
+    2023,
+    23,
+    "A Long Walk",
+    parse = |lines| parse_input(lines, true),
+    dot = junction_graph
+);
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 struct Position {
@@ -228,46 +234,52 @@ impl Solver {
     }
 }
 
-fn parse_input(lines: io::Lines<io::BufReader<File>>, avoid_slippery: bool) -> Solver {
-    let mut map = Vec::new();
-    for l in lines {
-        let line = l.unwrap();
-        map.push(line.as_bytes().to_vec());
+fn parse_input(lines: io::Lines<impl BufRead>, avoid_slippery: bool) -> Result<Solver> {
+    let map = parse_lines(lines, |line| Ok(line.as_bytes().to_vec()))?;
+    if map.len() < 2 {
+        bail!(AdventError::invalid("map needs at least two rows"));
     }
-    Solver::new(map, avoid_slippery)
+    Ok(Solver::new(map, avoid_slippery))
 }
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut solver = parse_input(lines, true);
+/// The paths between the junctions, which lead downhill where they pass a
+/// slope, labelled with their lengths
+fn junction_graph(lines: io::Lines<impl BufRead>) -> Result<Graph> {
+    let mut solver = parse_input(lines, true)?;
     solver.find_all_simple_path();
-    let solution = solver.find_longest_path(0, HashSet::new());
-    format!("{solution}")
+    let id = |p: &Position| format!("{},{}", p.x, p.y);
+    let mut graph = Graph::directed("2023-23");
+    graph.node(
+        &id(&solver.start),
+        &[("kind", &"start"), ("shape", &"doublecircle")],
+    );
+    graph.node(
+        &id(&solver.end),
+        &[("kind", &"end"), ("shape", &"doublecircle")],
+    );
+    for path in &solver.paths {
+        let (first, last) = (&path.positions[0], path.positions.last().unwrap());
+        let length = path.positions.len() - 1;
+        graph.edge(
+            &id(first),
+            &id(last),
+            &[("label", &length), ("weight", &length)],
+        );
+    }
+    Ok(graph)
 }
 
-fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut solver = parse_input(lines, false);
+fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut solver = parse_input(lines, true)?;
     solver.find_all_simple_path();
-    solver.eliminate_dead_ends();
     let solution = solver.find_longest_path(0, HashSet::new());
-    format!("{solution}")
+    Ok(format!("{solution}"))
 }
 
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_23_1() {
-        let lines = read_lines("data/2023/23.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "2210");
-    }
-
-    #[test]
-    fn test_2023_23_2() {
-        let lines = read_lines("data/2023/23.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "6522");
-    }
+fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut solver = parse_input(lines, false)?;
+    solver.find_all_simple_path();
+    solver.eliminate_dead_ends();
+    let solution = solver.find_longest_path(0, HashSet::new());
+    Ok(format!("{solution}"))
 }
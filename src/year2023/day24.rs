@@ -5,76 +5,17 @@ use mathru::{
     },
     vector,
 };
-use std::{
-    fs::File,
-    io,
-    ops::{Add, Neg, Sub},
-};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Vec3D {
-    x: f64,
-    y: f64,
-    z: f64,
-}
+use crate::{parse_lines, vec3::Vec3, AdventError};
+use anyhow::{anyhow, bail, Result};
 
-impl Vec3D {
-    fn cross(&self, other: &Self) -> Self {
-        Self {
-            x: self.y * other.z - self.z * other.y,
-            y: self.z * other.x - self.x * other.z,
-            z: self.x * other.y - self.y * other.x,
-        }
-    }
-}
-
-impl Neg for Vec3D {
-    type Output = Vec3D;
-    fn neg(self) -> Self::Output {
-        Self::Output {
-            x: -self.x,
-            y: -self.y,
-            z: -self.z,
-        }
-    }
-}
-
-impl Add for Vec3D {
-    type Output = Vec3D;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self::Output {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
-        }
-    }
-}
-
-impl Sub for Vec3D {
-    type Output = Vec3D;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self::Output {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
-        }
-    }
-}
+solution!(2023, 24, "Never Tell Me The Odds", parse = parse_input);
 
 #[derive(Debug, Clone)]
 struct Hail {
-    pos: Vec3D,
-    speed: Vec3D,
+    pos: Vec3<f64>,
+    speed: Vec3<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -156,19 +97,19 @@ impl Solver {
     // r x (v1-v2) - vr x (p1-p2) = p1 x v1 - p2 x v2
     // Using a third hail, we get six linear equations with six variables, which can be solved
     // using standard methods.
-    fn solve_p2(&self, first: usize, second: usize, third: usize) -> Hail {
+    fn solve_p2(&self, first: usize, second: usize, third: usize) -> Result<Hail> {
         let p1 = &self.hails[first].pos;
         let v1 = &self.hails[first].speed;
         let p2 = &self.hails[second].pos;
         let v2 = &self.hails[second].speed;
         let p3 = &self.hails[third].pos;
         let v3 = &self.hails[third].speed;
-        let d1 = p1.cross(v1) - p2.cross(v2);
-        let d2 = p2.cross(v2) - p3.cross(v3);
-        let v12 = v1.clone() - v2.clone();
-        let p12 = p1.clone() - p2.clone();
-        let v23 = v2.clone() - v3.clone();
-        let p23 = p2.clone() - p3.clone();
+        let d1 = p1.cross(*v1) - p2.cross(*v2);
+        let d2 = p2.cross(*v2) - p3.cross(*v3);
+        let v12 = *v1 - *v2;
+        let p12 = *p1 - *p2;
+        let v23 = *v2 - *v3;
+        let p23 = *p2 - *p3;
         let a: General<f64> = General::new(
             6,
             6,
@@ -181,81 +122,49 @@ impl Solver {
         let a = a.transpose();
         let b: Vector<f64> = vector![d1.x, d1.y, d1.z, d2.x, d2.y, d2.z];
         let b = b.transpose();
-        let x: Vector<f64> = a.solve(&b).unwrap();
+        let x: Vector<f64> = a.solve(&b).map_err(|_| AdventError::NoSolution)?;
 
-        let r = Vec3D {
-            x: x[0].round(),
-            y: x[1].round(),
-            z: x[2].round(),
-        };
-        let vr = Vec3D {
-            x: x[3].round(),
-            y: x[4].round(),
-            z: x[5].round(),
-        };
+        let r = Vec3::new(x[0].round(), x[1].round(), x[2].round());
+        let vr = Vec3::new(x[3].round(), x[4].round(), x[5].round());
 
-        Hail { pos: r, speed: vr }
+        Ok(Hail { pos: r, speed: vr })
     }
 }
 
-fn parse_input(lines: io::Lines<io::BufReader<File>>) -> Solver {
-    let mut hails = Vec::new();
-    for l in lines {
-        let line = l.unwrap();
-        let parts: Vec<&str> = line.split(" @ ").collect();
-        let pos: Vec<f64> = parts[0]
-            .split(", ")
-            .map(|s| (*s).trim().parse::<f64>().unwrap())
-            .collect();
-        let speed: Vec<f64> = parts[1]
-            .split(", ")
-            .map(|s| (*s).trim().parse::<f64>().unwrap())
-            .collect();
-        let pos = Vec3D {
-            x: pos[0],
-            y: pos[1],
-            z: pos[2],
-        };
-        let speed = Vec3D {
-            x: speed[0],
-            y: speed[1],
-            z: speed[2],
-        };
-        hails.push(Hail { pos, speed });
-    }
-    Solver::new(hails)
+fn parse_vec(s: &str) -> Result<Vec3<f64>> {
+    let coords = s
+        .split(", ")
+        .map(|s| s.trim().parse::<f64>())
+        .collect::<Result<Vec<f64>, _>>()?;
+    let [x, y, z] = coords[..] else {
+        bail!("expected 3 coordinates, found '{s}'");
+    };
+    Ok(Vec3::new(x, y, z))
+}
+
+fn parse_input(lines: io::Lines<impl BufRead>) -> Result<Solver> {
+    let hails = parse_lines(lines, |line| {
+        let (pos, speed) = line
+            .split_once(" @ ")
+            .ok_or_else(|| anyhow!("expected '<position> @ <velocity>'"))?;
+        Ok(Hail {
+            pos: parse_vec(pos)?,
+            speed: parse_vec(speed)?,
+        })
+    })?;
+    Ok(Solver::new(hails))
 }
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let solver = parse_input(lines);
+fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let solver = parse_input(lines)?;
     let solution = solver.solve();
-    format!("{solution}")
+    Ok(format!("{solution}"))
 }
 
-fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let solver = parse_input(lines);
+fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let solver = parse_input(lines)?;
     // Solution is instable due rounding errors, but this values gave the correct result for my input ;-)
-    let rock = solver.solve_p2(31, 39, 34);
+    let rock = solver.solve_p2(31, 39, 34)?;
     let solution = rock.pos.x + rock.pos.y + rock.pos.z;
-    format!("{solution}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_24_1() {
-        let lines = read_lines("data/2023/24.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "21785");
-    }
-
-    #[test]
-    fn test_2023_24_2() {
-        let lines = read_lines("data/2023/24.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "554668916217145");
-    }
+    Ok(format!("{solution}"))
 }
@@ -1,85 +1,44 @@
 use std::{
-    collections::{BTreeSet, HashMap, HashSet},
-    fs::File,
-    io,
+    collections::{BTreeSet, HashMap},
+    io::{self, BufRead},
 };
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{dot::Graph, graph::min_cut, AdventError};
+use anyhow::{bail, Result};
+
+solution!(
+    2023,
+    25,
+    "Snowverload",
+    parse = parse_input,
+    dot = wiring_graph,
+    part_1_only
+);
 
 #[derive(Debug, Clone)]
 struct Solver {
+    names: Vec<String>,
     connections: BTreeSet<Connection>,
 }
 
 impl Solver {
-    fn new(connections: BTreeSet<Connection>) -> Self {
-        Self { connections }
+    fn new(names: Vec<String>, connections: BTreeSet<Connection>) -> Self {
+        Self { names, connections }
     }
 
-    fn find_connected_groups(&self, exclusions: &[Connection; 3]) -> Vec<usize> {
-        let mut connections = self.connections.clone();
-        for e in exclusions {
-            connections.remove(e);
-        }
-
-        let mut group_sizes = Vec::new();
-        while !connections.is_empty() {
-            let mut group = HashSet::new();
-            let p = connections.pop_first().unwrap();
-            group.insert(p.a);
-            group.insert(p.b);
-            let mut found_pair = true;
-            let mut found_pairs = HashSet::new();
-            while found_pair {
-                found_pair = false;
-                for n in &group {
-                    for p in &connections {
-                        if p.a == *n || p.b == *n {
-                            found_pair = true;
-                            found_pairs.insert(p.clone());
-                        }
-                    }
-                }
-                for p in &found_pairs {
-                    group.insert(p.a);
-                    group.insert(p.b);
-                    connections.remove(p);
-                }
-            }
-            group_sizes.push(group.len());
-        }
-        group_sizes
+    fn edges(&self) -> Vec<(usize, usize)> {
+        self.connections.iter().map(|c| (c.a, c.b)).collect()
     }
 
-    fn solve(&self) -> usize {
-        let c = Connection::new(0, 0);
-        let mut exclusions = [c.clone(), c.clone(), c];
-        for c1 in &self.connections {
-            exclusions[0] = c1.clone();
-            for c2 in &self.connections {
-                if c1 == c2 {
-                    continue;
-                }
-                exclusions[1] = c2.clone();
-                for c3 in &self.connections {
-                    if c3 == c1 || c3 == c2 {
-                        continue;
-                    }
-                    exclusions[2] = c3.clone();
-                    let group_sizes = self.find_connected_groups(&exclusions);
-                    if group_sizes.len() == 2 {
-                        return group_sizes.iter().product();
-                    }
-                }
+    /// Product of the group sizes after cutting the three wires that split
+    /// the components in two
+    fn solve(&self) -> Result<usize> {
+        match min_cut(self.names.len(), &self.edges()) {
+            Some(cut) if cut.edges.len() == 3 && cut.component_sizes.len() == 2 => {
+                Ok(cut.component_sizes.iter().product())
             }
+            _ => bail!(AdventError::NoSolution),
         }
-        0
     }
 }
 
@@ -112,58 +71,50 @@ impl Ord for Connection {
     }
 }
 
-fn parse_input(lines: io::Lines<io::BufReader<File>>) -> Solver {
+fn parse_input(lines: io::Lines<impl BufRead>) -> Result<Solver> {
     let mut nodes = HashMap::new();
+    let mut names = Vec::new();
     let mut connections = BTreeSet::new();
-    let mut idx = 0;
-    for l in lines {
-        let line = l.unwrap();
-        let parts: Vec<&str> = line.split(": ").collect();
-        let links: Vec<&str> = parts[1].split(' ').collect();
-        if !nodes.contains_key(parts[0]) {
-            nodes.insert(parts[0].to_string(), idx);
-            idx += 1;
+    for (line_idx, l) in lines.enumerate() {
+        let line = l?;
+        let (node, links) = line
+            .split_once(": ")
+            .ok_or_else(|| AdventError::at_line(line_idx, "expected '<node>: <nodes>'"))?;
+        if !nodes.contains_key(node) {
+            nodes.insert(node.to_string(), names.len());
+            names.push(node.to_string());
         }
-        let n_idx = nodes[parts[0]];
-        for s in links {
+        let n_idx = nodes[node];
+        for s in links.split(' ') {
             if !nodes.contains_key(s) {
-                nodes.insert(s.to_string(), idx);
-                idx += 1;
+                nodes.insert(s.to_string(), names.len());
+                names.push(s.to_string());
             }
             let l_idx = nodes[s];
             connections.insert(Connection::new(n_idx, l_idx));
         }
     }
-    Solver::new(connections)
+    Ok(Solver::new(names, connections))
 }
 
-fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let solver = parse_input(lines);
-    println!("{solver:?}");
-    let solution: usize = solver.solve();
-    format!("{solution}")
-}
-
-fn riddle_2(_lines: io::Lines<io::BufReader<File>>) -> String {
-    format!("not yet implemented")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_25_1() {
-        let lines = read_lines("data/2023/25.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "21785");
+/// The wiring, with the wires of the smallest cut drawn in red
+fn wiring_graph(lines: io::Lines<impl BufRead>) -> Result<Graph> {
+    let solver = parse_input(lines)?;
+    let cut = min_cut(solver.names.len(), &solver.edges()).map_or_else(Vec::new, |cut| cut.edges);
+    let mut graph = Graph::undirected("2023-25");
+    for (a, b) in solver.edges() {
+        let (from, to) = (&solver.names[a], &solver.names[b]);
+        if cut.contains(&(a, b)) {
+            graph.edge(from, to, &[("color", &"red"), ("penwidth", &3)]);
+        } else {
+            graph.edge(from, to, &[]);
+        }
     }
+    Ok(graph)
+}
 
-    #[test]
-    fn test_2023_25_2() {
-        let lines = read_lines("data/2023/25.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "6522");
-    }
+fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let solver = parse_input(lines)?;
+    let solution = solver.solve()?;
+    Ok(format!("{solution}"))
 }
@@ -1,12 +1,8 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use anyhow::Result;
+
+solution!(2023, 3, "Gear Ratios");
 
 fn test_num(i: usize, j: usize, chart: &[Vec<u8>]) -> (usize, usize) {
     let mut num = 0;
@@ -32,11 +28,11 @@ fn test_num(i: usize, j: usize, chart: &[Vec<u8>]) -> (usize, usize) {
     (k, 0)
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut chart = vec![vec![0u8; 1]];
 
     for line_result in lines {
-        chart.push(format!(".{}.", line_result.unwrap()).as_bytes().to_vec());
+        chart.push(format!(".{}.", line_result?).as_bytes().to_vec());
     }
     let xsize = chart[1].len();
     chart[0] = vec![b'.'; xsize];
@@ -54,7 +50,7 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
             }
         }
     }
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
 #[derive(Debug)]
@@ -120,11 +116,11 @@ fn get_gear(i: usize, j: usize, chart: &[Vec<u8>]) -> usize {
     }
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut chart = vec![vec![0u8; 1]];
 
     for line_result in lines {
-        chart.push(format!(".{}.", line_result.unwrap()).as_bytes().to_vec());
+        chart.push(format!(".{}.", line_result?).as_bytes().to_vec());
     }
     let xsize = chart[1].len();
     chart[0] = vec![b'.'; xsize];
@@ -137,25 +133,5 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
             }
         }
     }
-    format!("{sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_3_1() {
-        let lines = read_lines("data/2023/3.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "528799");
-    }
-
-    #[test]
-    fn test_2023_3_2() {
-        let lines = read_lines("data/2023/3.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "84907174");
-    }
+    Ok(format!("{sum}"))
 }
@@ -1,26 +1,36 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
+use crate::parse_lines;
+use anyhow::{anyhow, Result};
+
+solution!(
+    2023,
+    4,
+    "Scratchcards",
+    parse = |lines| parse_lines(lines, read_card)
+);
+
+fn get_nums(num_str: &str) -> Result<Vec<i32>> {
+    Ok(num_str
+        .split_whitespace()
+        .map(|s| s.parse())
+        .collect::<Result<_, _>>()?)
 }
 
-fn get_nums(num_str: &str) -> Vec<i32> {
-    let nums: Vec<i32> = num_str.split(' ').filter_map(|s| s.parse().ok()).collect();
-    nums
+/// Reads a card as its winning numbers and the numbers we got
+fn read_card(line: &str) -> Result<(Vec<i32>, Vec<i32>)> {
+    let (_, nums) = line
+        .split_once(": ")
+        .ok_or_else(|| anyhow!("expected 'Card <id>: <numbers>'"))?;
+    let (winning, got) = nums
+        .split_once(" | ")
+        .ok_or_else(|| anyhow!("expected '<winning numbers> | <numbers>'"))?;
+    Ok((get_nums(winning)?, get_nums(got)?))
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut sum = 0;
-    for line in lines {
-        let l = line.unwrap().clone();
-        let nums: Vec<&str> = l.split(": ").collect();
-        let num_halfs: Vec<&str> = nums[1].split(" | ").collect();
-        let winning_nums = get_nums(num_halfs[0]);
-        let got_nums = get_nums(num_halfs[1]);
+    for (winning_nums, got_nums) in parse_lines(lines, read_card)? {
         let mut score = 0;
         for got in &got_nums {
             for win in &winning_nums {
@@ -35,7 +45,7 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
         }
         sum += score;
     }
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
 struct Card {
@@ -44,15 +54,10 @@ struct Card {
     count: usize,
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut sum = 0;
     let mut cards = Vec::new();
-    for line in lines {
-        let l = line.unwrap().clone();
-        let nums: Vec<&str> = l.split(": ").collect();
-        let num_halfs: Vec<&str> = nums[1].split(" | ").collect();
-        let winning = get_nums(num_halfs[0]);
-        let got = get_nums(num_halfs[1]);
+    for (winning, got) in parse_lines(lines, read_card)? {
         cards.push(Card {
             winning,
             got,
@@ -75,25 +80,5 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
             cards[j].count += cards[i].count;
         }
     }
-    format!("{sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_4_1() {
-        let lines = read_lines("data/2023/4.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "15268");
-    }
-
-    #[test]
-    fn test_2023_4_2() {
-        let lines = read_lines("data/2023/4.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "6283755");
-    }
+    Ok(format!("{sum}"))
 }
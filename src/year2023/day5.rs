@@ -1,12 +1,9 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{interval::IntervalSet, AdventError};
+use anyhow::{bail, Result};
+
+solution!(2023, 5, "If You Give A Seed A Fertilizer");
 
 #[derive(Debug)]
 struct Range {
@@ -29,17 +26,30 @@ fn convert_values(values: &[u64], map: &[Range]) -> Vec<u64> {
     new_values
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
+fn read_seeds(line: &str) -> Result<Vec<u64>> {
+    let seeds = line
+        .strip_prefix("seeds: ")
+        .ok_or_else(|| AdventError::at_line(0, "expected 'seeds: <numbers>'"))?;
+    seeds
+        .split(' ')
+        .map(|s| Ok(s.parse()?))
+        .collect::<Result<_>>()
+        .map_err(|err| AdventError::at_line(0, err.to_string()).into())
+}
+
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut values: Vec<u64> = Vec::new();
     let mut map = Vec::new();
     for line in lines {
-        let l = line.unwrap().clone();
+        let l = line?;
         if values.is_empty() {
-            let vals: Vec<&str> = l.split(": ").collect();
-            values = vals[1].split(' ').filter_map(|s| s.parse().ok()).collect();
+            values = read_seeds(&l)?;
             continue;
         }
         let range: Vec<u64> = l.split(' ').filter_map(|s| s.parse().ok()).collect();
+        if !range.is_empty() && range.len() != 3 {
+            bail!(AdventError::invalid(format!("invalid map range '{l}'")));
+        }
         if range.is_empty() {
             if !map.is_empty() {
                 values = convert_values(&values, &map);
@@ -56,89 +66,37 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
     if !map.is_empty() {
         values = convert_values(&values, &map);
     }
-    let solution = values.iter().min().unwrap();
-    format!("{solution}")
+    let solution = values.iter().min().ok_or(AdventError::NoSolution)?;
+    Ok(format!("{solution}"))
 }
 
-#[derive(Clone, Debug)]
-struct ValueRange {
-    start: u64,
-    end: u64,
-}
-
-fn convert_value_ranges(values: &[ValueRange], map: &[Range]) -> Vec<ValueRange> {
-    let mut new_values = Vec::new();
-    let mut orig_values = values.to_vec();
+fn convert_value_ranges(values: &IntervalSet<u64>, map: &[Range]) -> IntervalSet<u64> {
+    let mut converted = IntervalSet::new();
+    let mut unmapped = values.clone();
     for r in map {
-        let dest_end = r.dest + (r.end - r.start);
-        let mut extra = None;
-        for v in &mut orig_values {
-            let v_len = v.end - v.start;
-            if v_len == 0 {
-                continue;
-            }
-            if v.start < r.start && v.end > r.end {
-                new_values.push(ValueRange {
-                    start: r.dest,
-                    end: dest_end,
-                });
-                extra = Some(ValueRange {
-                    start: r.end,
-                    end: v.end,
-                });
-                v.end = r.start;
-                break;
-            } else if v.start >= r.start && v.start < r.end {
-                let start = r.dest + (v.start - r.start);
-                let end = (start + v_len).min(dest_end);
-                let len = end - start;
-                new_values.push(ValueRange { start, end });
-                if v_len > len {
-                    v.start += len;
-                } else {
-                    v.start = v.end;
-                }
-            } else if v.end > r.start && v.end <= r.end {
-                let len = v.end - r.start;
-                new_values.push(ValueRange {
-                    start: r.dest,
-                    end: r.dest + len,
-                });
-                v.end -= len;
-            }
-        }
-        if let Some(extra) = extra {
-            orig_values.push(extra);
-        }
-    }
-    for v in orig_values {
-        if v.end > v.start {
-            new_values.push(v);
-        }
+        let source = IntervalSet::from(r.start..r.end);
+        converted = converted.union(&values.intersect(&source).translate(r.start, r.dest));
+        unmapped.remove(r.start..r.end);
     }
-    new_values
+    converted.union(&unmapped)
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut values = Vec::new();
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut values = IntervalSet::new();
     let mut map = Vec::new();
     for line in lines {
-        let l = line.unwrap().clone();
+        let l = line?;
         if values.is_empty() {
-            let val_str: Vec<&str> = l.split(": ").collect();
-            let vals: Vec<u64> = val_str[1]
-                .split(' ')
-                .filter_map(|s| s.parse().ok())
-                .collect();
+            let vals = read_seeds(&l)?;
             for i in 0..vals.len() / 2 {
-                values.push(ValueRange {
-                    start: vals[2 * i],
-                    end: vals[2 * i] + vals[2 * i + 1],
-                })
+                values.insert(vals[2 * i]..vals[2 * i] + vals[2 * i + 1]);
             }
             continue;
         }
         let range: Vec<u64> = l.split(' ').filter_map(|s| s.parse().ok()).collect();
+        if !range.is_empty() && range.len() != 3 {
+            bail!(AdventError::invalid(format!("invalid map range '{l}'")));
+        }
         if range.is_empty() {
             if !map.is_empty() {
                 values = convert_value_ranges(&values, &map);
@@ -155,26 +113,6 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
     if !map.is_empty() {
         values = convert_value_ranges(&values, &map);
     }
-    let solution = values.iter().map(|r| r.start).min().unwrap();
-    format!("{solution}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_5_1() {
-        let lines = read_lines("data/2023/5.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "388071289");
-    }
-
-    #[test]
-    fn test_2023_5_2() {
-        let lines = read_lines("data/2023/5.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "84206669");
-    }
+    let solution = values.min().ok_or(AdventError::NoSolution)?;
+    Ok(format!("{solution}"))
 }
@@ -1,12 +1,14 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::parse_lines;
+use anyhow::{anyhow, bail, Result};
+
+solution!(
+    2023,
+    7,
+    "Camel Cards",
+    parse = |lines| parse_lines(lines, Hand::new)
+);
 
 #[derive(Debug)]
 struct Hand {
@@ -38,9 +40,21 @@ fn calc_frequencies_with_joker(cards: &[u8; 5]) -> u64 {
     (joker_top * 10 + joker_second) as u64
 }
 
+fn read_hand(line: &str) -> Result<([u8; 5], u32)> {
+    let (cards, bet) = line
+        .split_once(' ')
+        .ok_or_else(|| anyhow!("expected cards and bet"))?;
+    let cards = cards
+        .as_bytes()
+        .try_into()
+        .map_err(|_| anyhow!("a hand needs 5 cards"))?;
+    Ok((cards, bet.parse()?))
+}
+
 impl Hand {
-    fn new(vals: &[&str]) -> Self {
-        let mut cards: [u8; 5] = vals[0].as_bytes()[0..5].try_into().unwrap();
+    fn new(line: &str) -> Result<Self> {
+        let (cards, bet) = read_hand(line)?;
+        let mut cards = cards;
         for card in &mut cards {
             *card = match card {
                 b'2'..=b'9' => *card - b'0',
@@ -49,19 +63,19 @@ impl Hand {
                 b'Q' => 12,
                 b'K' => 13,
                 b'A' => 14,
-                _ => 0,
+                _ => bail!("invalid card '{}'", *card as char),
             }
         }
         let mut score = calc_frequencies(&cards);
         for c in cards {
             score = score * 100 + c as u64;
         }
-        let bet = vals[1].parse().unwrap();
-        Self { score, bet }
+        Ok(Self { score, bet })
     }
 
-    fn with_joker(vals: &[&str]) -> Self {
-        let mut cards: [u8; 5] = vals[0].as_bytes()[0..5].try_into().unwrap();
+    fn with_joker(line: &str) -> Result<Self> {
+        let (cards, bet) = read_hand(line)?;
+        let mut cards = cards;
         for card in &mut cards {
             *card = match card {
                 b'2'..=b'9' => *card - b'0',
@@ -70,64 +84,33 @@ impl Hand {
                 b'Q' => 12,
                 b'K' => 13,
                 b'A' => 14,
-                _ => 0,
+                _ => bail!("invalid card '{}'", *card as char),
             }
         }
         let mut score = calc_frequencies_with_joker(&cards);
         for c in cards {
             score = score * 100 + c as u64;
         }
-        let bet = vals[1].parse().unwrap();
-        Self { score, bet }
+        Ok(Self { score, bet })
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut hands = Vec::new();
-    for line in lines {
-        let l = line.unwrap().clone();
-        let vals: Vec<&str> = l.split(' ').collect();
-        hands.push(Hand::new(&vals));
-    }
-    hands.sort_by(|a, b| a.score.cmp(&b.score));
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut hands = parse_lines(lines, Hand::new)?;
+    hands.sort_by_key(|a| a.score);
     let mut solution = 0;
     for (i, hand) in hands.iter().enumerate() {
         solution += (i as u32 + 1) * hand.bet;
     }
-    format!("{solution}")
+    Ok(format!("{solution}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut hands = Vec::new();
-    for line in lines {
-        let l = line.unwrap().clone();
-        let vals: Vec<&str> = l.split(' ').collect();
-        hands.push(Hand::with_joker(&vals));
-    }
-    hands.sort_by(|a, b| a.score.cmp(&b.score));
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut hands = parse_lines(lines, Hand::with_joker)?;
+    hands.sort_by_key(|a| a.score);
     let mut solution = 0;
     for (i, hand) in hands.iter().enumerate() {
         solution += (i as u32 + 1) * hand.bet;
     }
-    format!("{solution}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_7_1() {
-        let lines = read_lines("data/2023/7.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "251058093");
-    }
-
-    #[test]
-    fn test_2023_7_2() {
-        let lines = read_lines("data/2023/7.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "249781879");
-    }
+    Ok(format!("{solution}"))
 }
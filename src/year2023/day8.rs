@@ -1,26 +1,44 @@
-use num::Integer;
 use std::collections::HashMap;
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
+use crate::{
+    cycle::{find_cycle, Cycle},
+    math::{crt_pair, first_at_least},
+    next_line, parse_lines_after, AdventError,
+};
+use anyhow::{anyhow, bail, Result};
+
+solution!(2023, 8, "Haunted Wasteland", parse = read_network);
+
+type Network = HashMap<String, (String, String)>;
+
+fn read_network(mut lines: io::Lines<impl BufRead>) -> Result<(String, Network)> {
+    let directions = next_line(&mut lines)?.trim().to_string();
+    if let Some(col) = directions.find(|c| c != 'L' && c != 'R') {
+        bail!(AdventError::parse(1, col + 1, "expected 'L' or 'R'"));
+    }
+    next_line(&mut lines)?;
+    let nodes = parse_lines_after(lines, 2, |l| {
+        let (node, next) = l
+            .split_once(" = (")
+            .and_then(|(node, next)| Some((node, next.strip_suffix(")")?.split_once(", ")?)))
+            .ok_or_else(|| anyhow!("expected '<node> = (<left>, <right>)'"))?;
+        Ok((node.to_string(), (next.0.to_string(), next.1.to_string())))
+    })?;
+    let map: Network = nodes.into_iter().collect();
+    for (left, right) in map.values() {
+        if let Some(node) = [left, right].into_iter().find(|n| !map.contains_key(*n)) {
+            bail!(AdventError::invalid(format!("unknown node {node}")));
+        }
     }
+    Ok((directions, map))
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut lines = lines.into_iter();
-    let line = lines.next().unwrap().unwrap().clone();
-    let directions = line.trim().as_bytes();
-    let mut map = HashMap::new();
-    for line in lines.skip(1) {
-        let l = line.unwrap();
-        map.insert(
-            l[0..3].to_string(),
-            (l[7..10].to_string(), l[12..15].to_string()),
-        );
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (line, map) = read_network(lines)?;
+    let directions = line.as_bytes();
+    if !map.contains_key("AAA") {
+        bail!(AdventError::invalid("no node AAA to start from"));
     }
     let mut current: &str = "AAA";
     let mut solution = 0;
@@ -33,100 +51,81 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
         };
         solution += 1;
     }
-    format!("{solution}")
+    Ok(format!("{solution}"))
 }
 
-struct State<'a> {
-    current: &'a str,
-    cycle: HashMap<&'a str, usize>,
-    solution: usize,
+/// Walk of a ghost, which repeats in a cycle, with the steps before the
+/// cycle's second start after which the ghost is on an end node
+struct GhostWalk {
+    cycle: Cycle,
+    ends: Vec<usize>,
 }
 
-impl State<'_> {
-    fn check_cycle(&mut self, count: usize) {
-        if !self.cycle.contains_key(self.current) {
-            self.cycle.insert(self.current, count);
-        } else {
-            self.solution = self.cycle[self.current];
-        }
+impl GhostWalk {
+    fn new(start: &str, directions: &[u8], map: &Network) -> Self {
+        let d_len = directions.len();
+        let (cycle, states) = find_cycle(
+            (start, 0),
+            |&(node, count)| {
+                let next = match directions[count % d_len] {
+                    b'L' => &map[node].0,
+                    _ => &map[node].1,
+                };
+                (next.as_str(), count + 1)
+            },
+            |&(node, count)| (node, count % d_len),
+        );
+        let ends = states[..cycle.start + cycle.length]
+            .iter()
+            .filter(|(node, _)| node.ends_with('Z'))
+            .map(|&(_, count)| count)
+            .collect();
+        GhostWalk { cycle, ends }
     }
-}
 
-fn states_complete(states: &[State]) -> bool {
-    for s in states {
-        if s.solution == 0 {
-            return false;
-        }
+    fn is_at_end(&self, steps: usize) -> bool {
+        self.ends.contains(&self.cycle.reduce(steps))
     }
-    true
-}
 
-// Remark: this could relies on the observation, that at least
-// for my input, a location with 'Z' at the ends coincides
-// always with the maximum index of my directions
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut lines = lines.into_iter();
-    let line = lines.next().unwrap().unwrap().clone();
-    let directions = line.trim().as_bytes();
-    let mut map = HashMap::new();
-    for line in lines.skip(1) {
-        let l = line.unwrap();
-        map.insert(
-            l[0..3].to_string(),
-            (l[7..10].to_string(), l[12..15].to_string()),
-        );
+    /// Congruences for the step counts from the cycle start on after which
+    /// the ghost is on an end node
+    fn end_congruences(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        let length = self.cycle.length as i64;
+        self.ends
+            .iter()
+            .filter(|&&e| e >= self.cycle.start)
+            .map(move |&e| (e as i64 % length, length))
     }
+}
 
-    let mut states = Vec::new();
-    for k in map.keys() {
-        if k.as_bytes()[2] == b'A' {
-            states.push(State {
-                current: k.as_str(),
-                cycle: HashMap::new(),
-                solution: 0,
-            });
-        }
+/// Smallest number of steps after which all ghosts are on end nodes at once
+fn first_meeting(walks: &[GhostWalk]) -> Option<i64> {
+    let all_cycling = walks.iter().map(|w| w.cycle.start).max().unwrap_or(0);
+    if let Some(steps) = (0..all_cycling).find(|&s| walks.iter().all(|w| w.is_at_end(s))) {
+        return Some(steps as i64);
     }
-    let mut count = 0;
-    let d_len = directions.len();
-    let s_len = states.len();
-    while !states_complete(&states) {
-        for i in 0..s_len {
-            states[i].current = match directions[count % d_len] {
-                b'L' => &map[states[i].current].0,
-                b'R' => &map[states[i].current].1,
-                _ => panic!("invalid directions"),
-            };
-            if states[i].current.as_bytes()[2] == b'Z' {
-                states[i].check_cycle(count + 1);
-            }
-        }
-        count += 1;
+    // combine every choice of an end node in each ghost's cycle
+    let mut meetings = vec![(0, 1)];
+    for walk in walks {
+        meetings = meetings
+            .iter()
+            .flat_map(|&m| walk.end_congruences().filter_map(move |e| crt_pair(m, e)))
+            .collect();
     }
-    let solution = states
-        .iter()
-        .map(|s| s.solution)
-        .reduce(|acc, e| acc.lcm(&e))
-        .unwrap();
-    format!("{solution}")
+    meetings
+        .into_iter()
+        .map(|m| first_at_least(m, all_cycling as i64))
+        .min()
 }
 
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_8_1() {
-        let lines = read_lines("data/2023/8.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "20569");
-    }
-
-    #[test]
-    fn test_2023_8_2() {
-        let lines = read_lines("data/2023/8.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "21366921060721");
-    }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (line, map) = read_network(lines)?;
+    let directions = line.as_bytes();
+    let walks: Vec<GhostWalk> = map
+        .keys()
+        .filter(|k| k.ends_with('A'))
+        .map(|k| GhostWalk::new(k, directions, &map))
+        .collect();
+    let solution = first_meeting(&walks).ok_or(AdventError::NoSolution)?;
+    Ok(format!("{solution}"))
 }
@@ -1,12 +1,9 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::parse_lines;
+use anyhow::Result;
+
+solution!(2023, 9, "Mirage Maintenance", parse = read_sequences);
 
 struct Triangle {
     len: usize,
@@ -90,58 +87,37 @@ impl Triangle {
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
+fn read_sequences(lines: io::Lines<impl BufRead>) -> Result<Vec<Vec<i64>>> {
+    parse_lines(lines, |line| {
+        Ok(line
+            .split(' ')
+            .map(|s| s.parse())
+            .collect::<Result<Vec<i64>, _>>()?)
+    })
+}
+
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut solution = 0;
     let mut triangle = Triangle::new();
-    for line in lines {
-        let sequence: Vec<i64> = line
-            .unwrap()
-            .split(' ')
-            .map(|s| s.to_string().parse().unwrap())
-            .collect();
+    for sequence in read_sequences(lines)? {
         triangle.clear();
         while !triangle.complete() {
             triangle.add_row(&sequence);
         }
         solution += triangle.prediction() + sequence.last().unwrap();
     }
-    format!("{solution}")
+    Ok(format!("{solution}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
     let mut solution = 0;
     let mut triangle = Triangle::new();
-    for line in lines {
-        let sequence: Vec<i64> = line
-            .unwrap()
-            .split(' ')
-            .map(|s| s.to_string().parse().unwrap())
-            .collect();
+    for sequence in read_sequences(lines)? {
         triangle.clear();
         while !triangle.hist_complete() {
             triangle.add_hist_row(&sequence);
         }
         solution += sequence[0] - triangle.hist_prediction();
     }
-    format!("{solution}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2023_9_1() {
-        let lines = read_lines("data/2023/9.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "1696140818");
-    }
-
-    #[test]
-    fn test_2023_9_2() {
-        let lines = read_lines("data/2023/9.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1152");
-    }
+    Ok(format!("{solution}"))
 }
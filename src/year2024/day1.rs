@@ -1,29 +1,29 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
+use crate::parse_lines;
+use anyhow::{anyhow, Result};
+
+solution!(2024, 1, "Historian Hysteria", parse = read_lists);
+
+fn read_lists(lines: io::Lines<impl BufRead>) -> Result<(Vec<i32>, Vec<i32>)> {
+    let pairs = parse_lines(lines, |line| {
+        let (l, r) = line
+            .split_once("   ")
+            .ok_or_else(|| anyhow!("expected two location ids"))?;
+        Ok((l.parse::<i32>()?, r.parse::<i32>()?))
+    })?;
+    Ok(pairs.into_iter().unzip())
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut left: Vec<i32> = Vec::new();
-    let mut right: Vec<i32> = Vec::new();
-    for line in lines {
-        let line = line.unwrap();
-        let mut parts = line.split("   ");
-        left.push(parts.next().unwrap().parse().unwrap());
-        right.push(parts.next().unwrap().parse().unwrap());
-    }
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (mut left, mut right) = read_lists(lines)?;
     left.sort();
     right.sort();
     let mut sum = 0;
     for i in 0..left.len() {
         sum += (left[i] - right[i]).abs();
     }
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
 pub fn count_frequency(l: i32, right: &Vec<i32>) -> i32 {
@@ -36,39 +36,32 @@ pub fn count_frequency(l: i32, right: &Vec<i32>) -> i32 {
     count
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut left: Vec<i32> = Vec::new();
-    let mut right: Vec<i32> = Vec::new();
-    for line in lines {
-        let line = line.unwrap();
-        let mut parts = line.split("   ");
-        left.push(parts.next().unwrap().parse().unwrap());
-        right.push(parts.next().unwrap().parse().unwrap());
-    }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let (left, right) = read_lists(lines)?;
     let mut sum = 0;
     for l in left {
         let count = count_frequency(l, &right);
         sum += l * count;
     }
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
 #[cfg(test)]
 mod test {
-    use super::execute;
-    use crate::read_lines;
+    use super::{riddle_1, riddle_2};
+    use std::io::BufRead;
 
-    #[test]
-    fn test_2024_1_1() {
-        let lines = read_lines("data/2024/1.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "2742123");
-    }
+    const EXAMPLE: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
 
     #[test]
-    fn test_2024_1_2() {
-        let lines = read_lines("data/2024/1.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "21328497");
+    fn test_2024_1_example() {
+        assert_eq!(riddle_1(EXAMPLE.as_bytes().lines()).unwrap(), "11");
+        assert_eq!(riddle_2(EXAMPLE.as_bytes().lines()).unwrap(), "31");
     }
 }
@@ -1,14 +1,10 @@
-use advent::{Direction, DirectionType, Position};
 use std::collections::HashSet;
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{parse_digits, parse_lines, AdventError, Direction, DirectionType, Position};
+use anyhow::{bail, Result};
+
+solution!(2024, 10, "Hoof It", parse = Solver::create_from_input);
 
 #[derive(Debug, Clone)]
 struct Solver {
@@ -26,20 +22,19 @@ impl Solver {
         DirectionType::Right,
     ];
 
-    fn create_from_input(lines: io::Lines<io::BufReader<File>>) -> Self {
-        let mut map = Vec::new();
-        for line in lines {
-            let row: Vec<u8> = line.unwrap().as_bytes().iter().map(|x| *x - b'0').collect();
-            map.push(row);
+    fn create_from_input(lines: io::Lines<impl BufRead>) -> Result<Self> {
+        let map = parse_lines(lines, parse_digits)?;
+        if map.is_empty() {
+            bail!(AdventError::invalid("empty map"));
         }
         let m = map.len();
         let n = map[0].len();
-        Self {
+        Ok(Self {
             map,
             n,
             m,
             peaks: HashSet::new(),
-        }
+        })
     }
 
     fn find_paths(&mut self, pos: Position) {
@@ -108,34 +103,14 @@ impl Solver {
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut solver = Solver::create_from_input(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut solver = Solver::create_from_input(lines)?;
     let score = solver.solve();
-    format!("{score}")
+    Ok(format!("{score}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let solver = Solver::create_from_input(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let solver = Solver::create_from_input(lines)?;
     let score = solver.solve2();
-    format!("{score}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2024_10_1() {
-        let lines = read_lines("data/2024/10.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "744");
-    }
-
-    #[test]
-    fn test_2024_10_2() {
-        let lines = read_lines("data/2024/10.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1651");
-    }
+    Ok(format!("{score}"))
 }
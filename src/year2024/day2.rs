@@ -1,23 +1,25 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::parse_lines;
+use anyhow::{bail, Result};
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut reports: Vec<Vec<i32>> = Vec::new();
-    for line in lines {
+solution!(2024, 2, "Red-Nosed Reports", parse = read_reports);
+
+fn read_reports(lines: io::Lines<impl BufRead>) -> Result<Vec<Vec<i32>>> {
+    parse_lines(lines, |line| {
         let report = line
-            .unwrap()
             .split(' ')
-            .map(|x| x.parse().unwrap())
-            .collect();
-        reports.push(report);
-    }
+            .map(|x| x.parse())
+            .collect::<Result<Vec<i32>, _>>()?;
+        if report.len() < 3 {
+            bail!("a report needs at least 3 levels");
+        }
+        Ok(report)
+    })
+}
+
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let reports = read_reports(lines)?;
     let mut count = 0;
     for report in reports {
         let diff = report[0] - report[1];
@@ -37,7 +39,7 @@ pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
             count += 1;
         }
     }
-    format!("{count}")
+    Ok(format!("{count}"))
 }
 
 fn check_defect(report: &[i32], ignore: usize) -> bool {
@@ -64,16 +66,8 @@ fn check_defect(report: &[i32], ignore: usize) -> bool {
     true
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut reports: Vec<Vec<i32>> = Vec::new();
-    for line in lines {
-        let report = line
-            .unwrap()
-            .split(' ')
-            .map(|x| x.parse().unwrap())
-            .collect();
-        reports.push(report);
-    }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let reports = read_reports(lines)?;
     let mut count = 0;
     for report in reports {
         let mut is_valid = false;
@@ -91,25 +85,5 @@ pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
             count += 1;
         }
     }
-    format!("{count}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2024_2_1() {
-        let lines = read_lines("data/2024/2.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "524");
-    }
-
-    #[test]
-    fn test_2024_2_2() {
-        let lines = read_lines("data/2024/2.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "569");
-    }
+    Ok(format!("{count}"))
 }
@@ -1,12 +1,9 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::parse_lines;
+use anyhow::Result;
+
+solution!(2024, 4, "Ceres Search");
 
 struct Solver {
     grid: Vec<Vec<u8>>,
@@ -25,7 +22,7 @@ impl Solver {
         (-1, 1),
         (-1, -1),
     ];
-    const XMAS: [u8; 4] = [b'X', b'M', b'A', b'S'];
+    const XMAS: [u8; 4] = *b"XMAS";
 
     fn new(grid: Vec<Vec<u8>>) -> Self {
         let n = grid.len();
@@ -96,44 +93,16 @@ impl Solver {
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut grid = Vec::new();
-    for line in lines {
-        let line = line.unwrap();
-        grid.push(line.as_bytes().to_vec());
-    }
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let grid = parse_lines(lines, |line| Ok(line.as_bytes().to_vec()))?;
     let solver = Solver::new(grid);
     let count = solver.solve1();
-    format!("{count}")
+    Ok(format!("{count}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut grid = Vec::new();
-    for line in lines {
-        let line = line.unwrap();
-        grid.push(line.as_bytes().to_vec());
-    }
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let grid = parse_lines(lines, |line| Ok(line.as_bytes().to_vec()))?;
     let solver = Solver::new(grid);
     let count = solver.solve2();
-    format!("{count}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2024_4_1() {
-        let lines = read_lines("data/2024/4.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "2569");
-    }
-
-    #[test]
-    fn test_2024_4_2() {
-        let lines = read_lines("data/2024/4.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1998");
-    }
+    Ok(format!("{count}"))
 }
@@ -1,13 +1,10 @@
 use std::collections::{HashMap, HashSet};
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{next_line, parse_lines_after, AdventError};
+use anyhow::Result;
+
+solution!(2024, 5, "Print Queue", parse = Solver::create_from_input);
 
 #[derive(Debug)]
 struct Solver {
@@ -16,34 +13,33 @@ struct Solver {
 }
 
 impl Solver {
-    fn create_from_input(mut lines: io::Lines<io::BufReader<File>>) -> Self {
+    fn create_from_input(mut lines: io::Lines<impl BufRead>) -> Result<Self> {
         let mut rules: HashMap<u32, HashSet<u32>> = HashMap::new();
+        let mut idx = 0;
         loop {
-            let line = lines.next().unwrap().unwrap();
+            let line = next_line(&mut lines)?;
             if line.is_empty() {
                 break;
             }
-            let mut parts = line.split("|");
-            let left = parts.next().unwrap().parse::<u32>().unwrap();
-            let right = parts.next().unwrap().parse::<u32>().unwrap();
-            if let std::collections::hash_map::Entry::Vacant(e) = rules.entry(left) {
-                let mut set = HashSet::new();
-                set.insert(right);
-                e.insert(set);
-            } else {
-                rules.get_mut(&left).unwrap().insert(right);
-            }
-        }
-        let mut updates = Vec::new();
-        for line in lines {
-            updates.push(
-                line.unwrap()
-                    .split(',')
-                    .map(|x| x.parse::<u32>().unwrap())
-                    .collect(),
-            )
+            let (left, right) = line
+                .split_once('|')
+                .ok_or_else(|| AdventError::at_line(idx, "expected '<page>|<page>'"))?;
+            let left = left
+                .parse::<u32>()
+                .map_err(|e| AdventError::at_line(idx, e.to_string()))?;
+            let right = right
+                .parse::<u32>()
+                .map_err(|e| AdventError::at_line(idx, e.to_string()))?;
+            rules.entry(left).or_default().insert(right);
+            idx += 1;
         }
-        Self { rules, updates }
+        let updates = parse_lines_after(lines, idx + 1, |line| {
+            Ok(line
+                .split(',')
+                .map(|x| x.parse::<u32>())
+                .collect::<Result<Vec<_>, _>>()?)
+        })?;
+        Ok(Self { rules, updates })
     }
 
     fn solve1(&self) -> u32 {
@@ -109,34 +105,14 @@ impl Solver {
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let solver = Solver::create_from_input(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let solver = Solver::create_from_input(lines)?;
     let count = solver.solve1();
-    format!("{count}")
+    Ok(format!("{count}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let solver = Solver::create_from_input(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let solver = Solver::create_from_input(lines)?;
     let count = solver.solve2();
-    format!("{count}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2024_5_1() {
-        let lines = read_lines("data/2024/5.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "5588");
-    }
-
-    #[test]
-    fn test_2024_5_2() {
-        let lines = read_lines("data/2024/5.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "5331");
-    }
+    Ok(format!("{count}"))
 }
@@ -1,70 +1,57 @@
 use std::collections::HashSet;
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-use advent::{Direction, DirectionType, Position};
+use crate::{parallel, AdventError, Direction, DirectionType, Grid, Position};
+use anyhow::Result;
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+solution!(
+    2024,
+    6,
+    "Guard Gallivant",
+    parse = Solver::create_from_input
+);
 
 #[derive(Debug, Clone)]
 struct Solver {
-    grid: Vec<Vec<u8>>,
+    grid: Grid<u8>,
     pos: Position,
-    n: usize,
-    m: usize,
 }
 
 impl Solver {
-    fn create_from_input(lines: io::Lines<io::BufReader<File>>) -> Self {
-        let mut grid = Vec::new();
-        let mut pos = Position::new(0, 0);
-        for line in lines {
-            let mut row = line.unwrap().as_bytes().to_vec();
-            for (i, item) in row.iter_mut().enumerate() {
-                if *item == b'^' {
-                    pos = Position::new(i as i32, grid.len() as i32);
-                    *item = b'.';
-                }
-            }
-            grid.push(row);
-        }
-        let n = grid[0].len();
-        let m = grid.len();
-        Self { grid, pos, n, m }
+    fn create_from_input(lines: io::Lines<impl BufRead>) -> Result<Self> {
+        let mut grid = Grid::parse(lines)?;
+        let pos = grid
+            .find(&b'^')
+            .ok_or(AdventError::invalid("no guard on the map"))?;
+        grid[&pos] = b'.';
+        Ok(Self { grid, pos })
     }
 
     fn solve(&mut self) -> u32 {
         let mut count = 1;
-        self.grid[self.pos.y as usize][self.pos.x as usize] = b'X';
+        self.grid[&self.pos] = b'X';
         let mut dir = Direction::from_type(DirectionType::Up);
         loop {
-            if !self.pos.check_move_by(&dir, self.n, self.m) {
-                break;
-            }
             let next_pos = &self.pos + &dir;
-            if self.grid[next_pos.y as usize][next_pos.x as usize] == b'#' {
-                dir.turn_right();
-            } else if self.grid[next_pos.y as usize][next_pos.x as usize] == b'.' {
-                count += 1;
-                self.grid[next_pos.y as usize][next_pos.x as usize] = b'X';
-                self.pos = next_pos;
-            } else {
-                self.pos = next_pos;
+            match self.grid.get(&next_pos) {
+                None => break,
+                Some(b'#') => dir.turn_right(),
+                Some(b'.') => {
+                    count += 1;
+                    self.grid[&next_pos] = b'X';
+                    self.pos = next_pos;
+                }
+                Some(_) => self.pos = next_pos,
             }
         }
         count
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut solver = Solver::create_from_input(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut solver = Solver::create_from_input(lines)?;
     let count = solver.solve();
-    format!("{count}")
+    Ok(format!("{count}"))
 }
 
 struct MetaSolver {
@@ -73,58 +60,46 @@ struct MetaSolver {
 
 #[derive(Debug, Clone)]
 struct TestSolver {
-    grid: Vec<Vec<u8>>,
-    visited: Vec<Vec<HashSet<DirectionType>>>,
+    grid: Grid<u8>,
+    visited: Grid<HashSet<DirectionType>>,
     pos: Position,
-    n: usize,
-    m: usize,
 }
 
 impl TestSolver {
     fn new(solver: &Solver) -> Self {
-        let mut visited = Vec::new();
-        for _ in 0..solver.m {
-            let mut row = Vec::new();
-            for _ in 0..solver.n {
-                row.push(HashSet::new());
-            }
-            visited.push(row);
-        }
+        let grid = solver.grid.clone();
+        let visited = Grid::new(grid.width(), grid.height(), HashSet::new());
         Self {
-            grid: solver.grid.clone(),
+            grid,
             visited,
             pos: solver.pos.clone(),
-            n: solver.n,
-            m: solver.m,
         }
     }
 
-    fn set_obstacle(&mut self, x: usize, y: usize) {
-        self.grid[y][x] = b'#';
+    fn set_obstacle(&mut self, pos: &Position) {
+        self.grid[pos] = b'#';
     }
 
     fn has_loop(&mut self) -> bool {
-        let mut has_loop = false;
         let mut dir = Direction::from_type(DirectionType::Up);
         let mut dir_type = dir.to_type().unwrap();
-        self.visited[self.pos.y as usize][self.pos.x as usize].insert(dir_type);
+        self.visited[&self.pos].insert(dir_type);
         loop {
-            if !self.pos.check_move_by(&dir, self.n, self.m) {
-                break;
-            }
             let next_pos = &self.pos + &dir;
-            if self.grid[next_pos.y as usize][next_pos.x as usize] == b'#' {
-                dir.turn_right();
-                dir_type = dir.to_type().unwrap();
-            } else if self.visited[next_pos.y as usize][next_pos.x as usize].contains(&dir_type) {
-                has_loop = true;
-                break;
-            } else {
-                self.visited[next_pos.y as usize][next_pos.x as usize].insert(dir_type);
-                self.pos = next_pos;
+            match self.grid.get(&next_pos) {
+                None => return false,
+                Some(b'#') => {
+                    dir.turn_right();
+                    dir_type = dir.to_type().unwrap();
+                }
+                Some(_) => {
+                    if !self.visited[&next_pos].insert(dir_type) {
+                        return true;
+                    }
+                    self.pos = next_pos;
+                }
             }
         }
-        has_loop
     }
 }
 
@@ -132,50 +107,49 @@ impl MetaSolver {
     fn solve(&self) -> u32 {
         let mut map_solver = self.orig_solver.clone();
         _ = map_solver.solve();
-        let map = map_solver.grid;
-        let mut count = 0;
-        for (i, row) in map.iter().enumerate() {
-            for (j, item) in row.iter().enumerate() {
-                if i == self.orig_solver.pos.y as usize && j == self.orig_solver.pos.x as usize {
-                    continue;
-                }
-                if *item == b'X' {
-                    let mut test_solver = TestSolver::new(&self.orig_solver);
-                    test_solver.set_obstacle(j, i);
-                    if test_solver.has_loop() {
-                        count += 1;
-                    }
-                }
-            }
-        }
-        count
+        let candidates: Vec<Position> = map_solver
+            .grid
+            .find_all(&b'X')
+            .filter(|pos| *pos != self.orig_solver.pos)
+            .collect();
+        parallel::map(&candidates, |pos| {
+            let mut test_solver = TestSolver::new(&self.orig_solver);
+            test_solver.set_obstacle(pos);
+            test_solver.has_loop()
+        })
+        .filter(|&has_loop| has_loop)
+        .count() as u32
     }
 }
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let solver = Solver::create_from_input(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let solver = Solver::create_from_input(lines)?;
     let meta_solver = MetaSolver {
         orig_solver: solver,
     };
     let count = meta_solver.solve();
-    format!("{count}")
+    Ok(format!("{count}"))
 }
 
 #[cfg(test)]
 mod test {
-    use super::execute;
-    use crate::read_lines;
+    use super::{riddle_1, riddle_2};
+    use std::io::BufRead;
 
-    #[test]
-    fn test_2024_6_1() {
-        let lines = read_lines("data/2024/6.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "5312");
-    }
+    const EXAMPLE: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
 
     #[test]
-    fn test_2024_6_2() {
-        let lines = read_lines("data/2024/6.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "1748");
+    fn test_2024_6_example() {
+        assert_eq!(riddle_1(EXAMPLE.as_bytes().lines()).unwrap(), "41");
+        assert_eq!(riddle_2(EXAMPLE.as_bytes().lines()).unwrap(), "6");
     }
 }
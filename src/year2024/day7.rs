@@ -1,12 +1,9 @@
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::parse_lines;
+use anyhow::{anyhow, Result};
+
+solution!(2024, 7, "Bridge Repair", parse = Solver::create_from_input);
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum Operation {
@@ -21,18 +18,18 @@ struct Solver {
 }
 
 impl Solver {
-    fn create_from_input(lines: io::Lines<io::BufReader<File>>) -> Self {
-        let mut calculations = Vec::new();
-        for line in lines {
-            let row = line
-                .unwrap()
-                .split(' ')
-                .map(|x| x.trim_end_matches(":").to_string())
-                .map(|x| x.parse::<u64>().unwrap())
-                .collect::<Vec<u64>>();
-            calculations.push(row);
-        }
-        Self { calculations }
+    fn create_from_input(lines: io::Lines<impl BufRead>) -> Result<Self> {
+        let calculations = parse_lines(lines, |line| {
+            let (result, numbers) = line
+                .split_once(": ")
+                .ok_or_else(|| anyhow!("expected '<result>: <numbers>'"))?;
+            let mut row = vec![result.parse::<u64>()?];
+            for x in numbers.split(' ') {
+                row.push(x.parse::<u64>()?);
+            }
+            Ok(row)
+        })?;
+        Ok(Self { calculations })
     }
 
     fn is_valid(op: Operation, value: u64, idx: usize, row: &[u64]) -> bool {
@@ -91,34 +88,14 @@ impl Solver {
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let solver = Solver::create_from_input(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let solver = Solver::create_from_input(lines)?;
     let sum = solver.solve1();
-    format!("{sum}")
+    Ok(format!("{sum}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let solver = Solver::create_from_input(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let solver = Solver::create_from_input(lines)?;
     let sum = solver.solve2();
-    format!("{sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2024_7_1() {
-        let lines = read_lines("data/2024/7.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "5702958180383");
-    }
-
-    #[test]
-    fn test_2024_7_2() {
-        let lines = read_lines("data/2024/7.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "92612386119138");
-    }
+    Ok(format!("{sum}"))
 }
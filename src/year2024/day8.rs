@@ -1,15 +1,16 @@
 use std::collections::{HashMap, HashSet};
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-use advent::Position;
+use crate::Position;
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use anyhow::Result;
+
+solution!(
+    2024,
+    8,
+    "Resonant Collinearity",
+    parse = Solver::create_from_input
+);
 
 #[derive(Debug, Clone)]
 struct Solver {
@@ -19,12 +20,12 @@ struct Solver {
 }
 
 impl Solver {
-    fn create_from_input(lines: io::Lines<io::BufReader<File>>) -> Self {
+    fn create_from_input(lines: io::Lines<impl BufRead>) -> Result<Self> {
         let mut n = 0;
         let mut m = 0;
         let mut antennas: HashMap<u8, Vec<Position>> = HashMap::new();
         for line in lines {
-            let row = line.unwrap().as_bytes().to_vec();
+            let row = line?.as_bytes().to_vec();
             m = row.len();
             for (i, element) in row.iter().enumerate() {
                 if *element != b'.' {
@@ -40,7 +41,7 @@ impl Solver {
             }
             n += 1;
         }
-        Self { antennas, n, m }
+        Ok(Self { antennas, n, m })
     }
 
     fn solve1(&mut self) -> usize {
@@ -92,34 +93,14 @@ impl Solver {
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut solver = Solver::create_from_input(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut solver = Solver::create_from_input(lines)?;
     let count = solver.solve1();
-    format!("{count}")
+    Ok(format!("{count}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut solver = Solver::create_from_input(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut solver = Solver::create_from_input(lines)?;
     let count = solver.solve2();
-    format!("{count}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2024_8_1() {
-        let lines = read_lines("data/2024/8.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "247");
-    }
-
-    #[test]
-    fn test_2024_8_2() {
-        let lines = read_lines("data/2024/8.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "861");
-    }
+    Ok(format!("{count}"))
 }
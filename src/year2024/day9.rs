@@ -1,13 +1,15 @@
 use std::cmp::Ordering;
-use std::{fs::File, io};
+use std::io::{self, BufRead};
 
-pub fn execute(part: u32, lines: io::Lines<io::BufReader<File>>) -> String {
-    match part {
-        1 => riddle_1(lines),
-        2 => riddle_2(lines),
-        _ => format!("Error: part {part} not found!"),
-    }
-}
+use crate::{next_line, parse_digits, AdventError};
+use anyhow::{bail, Result};
+
+solution!(
+    2024,
+    9,
+    "Disk Fragmenter",
+    parse = Solver::create_from_input
+);
 
 #[derive(Debug, Clone)]
 struct Solver {
@@ -15,22 +17,18 @@ struct Solver {
 }
 
 impl Solver {
-    fn create_from_input(mut lines: io::Lines<io::BufReader<File>>) -> Self {
-        let fat: Vec<u8> = lines
-            .next()
-            .unwrap()
-            .unwrap()
-            .as_bytes()
-            .iter()
-            .map(|x| *x - b'0')
-            .collect();
-        Self { fat }
+    fn create_from_input(mut lines: io::Lines<impl BufRead>) -> Result<Self> {
+        let fat = parse_digits(&next_line(&mut lines)?)?;
+        if fat.is_empty() {
+            bail!(AdventError::invalid("empty disk map"));
+        }
+        Ok(Self { fat })
     }
 
     fn solve(&self) -> usize {
         let mut new_fat = vec![(0usize, self.fat[0] as usize)];
         let mut last = self.fat.len() - 1;
-        if last % 2 != 0 {
+        if !last.is_multiple_of(2) {
             last -= 1;
         }
         let mut last_left = self.fat[last];
@@ -90,23 +88,23 @@ struct Solver2 {
 }
 
 impl Solver2 {
-    fn create_from_input(mut lines: io::Lines<io::BufReader<File>>) -> Self {
-        let mut file_list: Vec<FileEntry> = lines
-            .next()
-            .unwrap()
-            .unwrap()
-            .as_bytes()
+    fn create_from_input(mut lines: io::Lines<impl BufRead>) -> Result<Self> {
+        let fat = parse_digits(&next_line(&mut lines)?)?;
+        if fat.is_empty() {
+            bail!(AdventError::invalid("empty disk map"));
+        }
+        let mut file_list: Vec<FileEntry> = fat
             .iter()
             .enumerate()
             .map(|(i, x)| FileEntry {
-                len: (*x - b'0') as usize,
+                len: *x as usize,
                 idx: if i % 2 == 0 { Some(i / 2) } else { None },
                 prev: if i == 0 { None } else { Some(i - 1) },
                 next: Some(i + 1),
             })
             .collect();
         file_list.last_mut().unwrap().next = None;
-        Self { file_list }
+        Ok(Self { file_list })
     }
 
     fn next(&self, i: usize) -> usize {
@@ -195,34 +193,14 @@ impl Solver2 {
     }
 }
 
-pub fn riddle_1(lines: io::Lines<io::BufReader<File>>) -> String {
-    let solver = Solver::create_from_input(lines);
+pub fn riddle_1(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let solver = Solver::create_from_input(lines)?;
     let check_sum = solver.solve();
-    format!("{check_sum}")
+    Ok(format!("{check_sum}"))
 }
 
-pub fn riddle_2(lines: io::Lines<io::BufReader<File>>) -> String {
-    let mut solver = Solver2::create_from_input(lines);
+pub fn riddle_2(lines: io::Lines<impl BufRead>) -> Result<String> {
+    let mut solver = Solver2::create_from_input(lines)?;
     let check_sum = solver.solve();
-    format!("{check_sum}")
-}
-
-#[cfg(test)]
-mod test {
-    use super::execute;
-    use crate::read_lines;
-
-    #[test]
-    fn test_2024_9_1() {
-        let lines = read_lines("data/2024/9.txt").unwrap();
-        let result = execute(1, lines);
-        assert_eq!(result, "6288707484810");
-    }
-
-    #[test]
-    fn test_2024_9_2() {
-        let lines = read_lines("data/2024/9.txt").unwrap();
-        let result = execute(2, lines);
-        assert_eq!(result, "6311837662089");
-    }
+    Ok(format!("{check_sum}"))
 }